@@ -0,0 +1,353 @@
+//! Terminal key and mouse input via crossterm, for roguelikes and TUI tools
+//!
+//! Most terminals only report key presses. Release events, and hence
+//! [`Input::KeyHeld`], are only available when the terminal supports and has
+//! been asked for them with
+//! [`KeyboardEnhancementFlags::REPORT_EVENT_TYPES`](crossterm::event::KeyboardEnhancementFlags::REPORT_EVENT_TYPES).
+//! Mouse input requires
+//! [`EnableMouseCapture`](crossterm::event::EnableMouseCapture).
+
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+
+/// Identifies a source of input data
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    /// A key was pressed, or repeated by the terminal while held
+    KeyPressed(Key),
+    /// Whether a key is held, where the terminal reports releases
+    KeyHeld(Key),
+    MouseButtonPressed(MouseButton),
+    MouseButtonHeld(MouseButton),
+    /// Pointer motion, in terminal cells
+    MouseMotion,
+}
+
+impl Input {
+    /// Look up the [`Input`]s produced by a crossterm event
+    ///
+    /// Useful for building binding UIs. Call [`enact::Session::check_type`] to
+    /// filter out inputs which are inappropriate for a specific action.
+    pub fn from_event(event: &Event) -> Vec<Self> {
+        match *event {
+            Event::Key(ref event) if event.kind == KeyEventKind::Press => {
+                let Some(key) = Key::from_event(event) else {
+                    return vec![];
+                };
+                vec![Input::KeyPressed(key), Input::KeyHeld(key)]
+            }
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(button),
+                ..
+            }) => vec![
+                Input::MouseButtonPressed(button),
+                Input::MouseButtonHeld(button),
+            ],
+            _ => vec![],
+        }
+    }
+}
+
+impl enact::Input for Input {
+    const NAME: &'static str = "terminal";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::KeyHeld(_) | Input::MouseButtonHeld(_) => V::visit::<bool>(),
+            Input::KeyPressed(_) | Input::MouseButtonPressed(_) => V::visit::<()>(),
+            Input::MouseMotion => V::visit::<mint::Vector2<f64>>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        if let Some(key) = Key::parse(s) {
+            return vec![Input::KeyPressed(key), Input::KeyHeld(key)];
+        }
+        if let Some(button) = parse_mouse_button(s) {
+            return vec![
+                Input::MouseButtonPressed(button),
+                Input::MouseButtonHeld(button),
+            ];
+        }
+        if s.eq_ignore_ascii_case("mouse") {
+            return vec![Input::MouseMotion];
+        }
+        vec![]
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            Input::KeyPressed(k) | Input::KeyHeld(k) => k.format(),
+            Input::MouseButtonPressed(b) | Input::MouseButtonHeld(b) => {
+                format_mouse_button(b).to_owned()
+            }
+            Input::MouseMotion => "mouse".to_owned(),
+        }
+    }
+
+    fn names() -> Vec<String> {
+        KEY_NAMES
+            .iter()
+            .map(|&(name, _)| name)
+            .chain(MOUSE_BUTTON_NAMES.iter().map(|&(name, _)| name))
+            .chain(["mouse"])
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn device_family(&self) -> enact::DeviceFamily {
+        enact::DeviceFamily::KeyboardMouse
+    }
+
+    /// `kbm/` followed by the input's name, e.g. `kbm/enter` or `kbm/f5`
+    ///
+    /// Keys with modifiers, and characters other than letters and digits,
+    /// have no glyph.
+    fn glyph(&self) -> Option<String> {
+        let name = match *self {
+            Input::KeyPressed(k) | Input::KeyHeld(k) if !k.ctrl && !k.alt => k.format(),
+            Input::MouseButtonPressed(b) | Input::MouseButtonHeld(b) => {
+                format_mouse_button(b).to_owned()
+            }
+            Input::MouseMotion => "mouse".to_owned(),
+            _ => return None,
+        };
+        enact::DeviceFamily::KeyboardMouse.glyph(&name)
+    }
+}
+
+/// A key, with the modifiers that terminals report reliably
+///
+/// Written with optional `ctrl+` and `alt+` prefixes, e.g. `"ctrl+c"`.
+/// Letters are case-insensitive, since terminals report the character
+/// produced rather than the physical key.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("space", KeyCode::Char(' ')),
+    ("enter", KeyCode::Enter),
+    ("escape", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("page up", KeyCode::PageUp),
+    ("page down", KeyCode::PageDown),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+];
+
+impl Key {
+    /// Interpret a key event, if it's for a key with a name
+    fn from_event(event: &KeyEvent) -> Option<Self> {
+        let code = match event.code {
+            KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+            // Shift+Tab
+            KeyCode::BackTab => KeyCode::Tab,
+            KeyCode::F(n) => KeyCode::F(n),
+            code if KEY_NAMES.iter().any(|&(_, c)| c == code) => code,
+            _ => return None,
+        };
+        Some(Key {
+            code,
+            ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+            alt: event.modifiers.contains(KeyModifiers::ALT),
+        })
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let mut key = Key {
+            code: KeyCode::Null,
+            ctrl: false,
+            alt: false,
+        };
+        let mut rest = s;
+        loop {
+            if let Some(r) = strip_prefix_ignore_case(rest, "ctrl+") {
+                key.ctrl = true;
+                rest = r;
+            } else if let Some(r) = strip_prefix_ignore_case(rest, "alt+") {
+                key.alt = true;
+                rest = r;
+            } else {
+                break;
+            }
+        }
+        key.code = if let Some(&(_, code)) = KEY_NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(rest))
+        {
+            code
+        } else if let Some(n) = rest
+            .strip_prefix(['f', 'F'])
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|&n| n >= 1)
+        {
+            KeyCode::F(n)
+        } else {
+            let mut chars = rest.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            KeyCode::Char(c.to_ascii_lowercase())
+        };
+        Some(key)
+    }
+
+    fn format(self) -> String {
+        let mut out = String::new();
+        if self.ctrl {
+            out.push_str("ctrl+");
+        }
+        if self.alt {
+            out.push_str("alt+");
+        }
+        match KEY_NAMES.iter().find(|&&(_, code)| code == self.code) {
+            Some(&(name, _)) => out.push_str(name),
+            None => match self.code {
+                KeyCode::F(n) => out.push_str(&format!("f{n}")),
+                KeyCode::Char(c) => out.push(c),
+                _ => out.push_str("<unknown>"),
+            },
+        }
+        out
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &s[prefix.len()..])
+}
+
+const MOUSE_BUTTON_NAMES: [(&str, MouseButton); 3] = [
+    ("mouse left", MouseButton::Left),
+    ("mouse right", MouseButton::Right),
+    ("mouse middle", MouseButton::Middle),
+];
+
+fn parse_mouse_button(s: &str) -> Option<MouseButton> {
+    MOUSE_BUTTON_NAMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        .map(|&(_, button)| button)
+}
+
+fn format_mouse_button(button: MouseButton) -> &'static str {
+    MOUSE_BUTTON_NAMES
+        .iter()
+        .find(|&&(_, b)| b == button)
+        .map(|&(name, _)| name)
+        .unwrap()
+}
+
+/// Translates crossterm events into inputs
+///
+/// Tracks the pointer position to compute [`Input::MouseMotion`], and held
+/// keys to match releases to presses.
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    /// Pointer position from the previous mouse event, as (column, row)
+    last_mouse_position: Option<(u16, u16)>,
+    /// Keys reported pressed and not yet released
+    held: Vec<Key>,
+}
+
+impl Handler {
+    /// Create a handler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update action states in `seat` to account for any inputs in `event`
+    /// according to `bindings`
+    pub fn handle(
+        &mut self,
+        event: &Event,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        match *event {
+            Event::Key(ref event) => {
+                let Some(key) = Key::from_event(event) else {
+                    return;
+                };
+                match event.kind {
+                    KeyEventKind::Press | KeyEventKind::Repeat => {
+                        if event.kind == KeyEventKind::Press {
+                            if !self.held.contains(&key) {
+                                self.held.push(key);
+                            }
+                            bindings.handle(&Input::KeyHeld(key), true, seat).unwrap();
+                        }
+                        bindings.handle(&Input::KeyPressed(key), (), seat).unwrap();
+                    }
+                    KeyEventKind::Release => {
+                        // Modifiers may have changed since the press, so
+                        // release whatever was pressed with the same key
+                        let mut released = false;
+                        self.held.retain(|&held| {
+                            if held.code != key.code {
+                                return true;
+                            }
+                            bindings.handle(&Input::KeyHeld(held), false, seat).unwrap();
+                            released = true;
+                            false
+                        });
+                        if !released {
+                            // Pressed before this handler saw any events
+                            bindings.handle(&Input::KeyHeld(key), false, seat).unwrap();
+                        }
+                    }
+                }
+            }
+            Event::Mouse(MouseEvent {
+                kind, column, row, ..
+            }) => {
+                if let Some((x, y)) = self.last_mouse_position.replace((column, row))
+                    && (x, y) != (column, row)
+                {
+                    let delta = [
+                        f64::from(column) - f64::from(x),
+                        f64::from(row) - f64::from(y),
+                    ];
+                    bindings
+                        .handle(&Input::MouseMotion, mint::Vector2::from(delta), seat)
+                        .unwrap();
+                }
+                match kind {
+                    MouseEventKind::Down(button) => {
+                        bindings
+                            .handle(&Input::MouseButtonHeld(button), true, seat)
+                            .unwrap();
+                        bindings
+                            .handle(&Input::MouseButtonPressed(button), (), seat)
+                            .unwrap();
+                    }
+                    MouseEventKind::Up(button) => {
+                        bindings
+                            .handle(&Input::MouseButtonHeld(button), false, seat)
+                            .unwrap();
+                    }
+                    _ => {}
+                }
+            }
+            Event::FocusLost => {
+                self.last_mouse_position = None;
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Derive macros for enact. Use through the `derive` feature of enact.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, LitStr, parse_macro_input, spanned::Spanned};
+
+/// See `enact::ActionEnum`
+#[proc_macro_derive(ActionEnum, attributes(action))]
+pub fn derive_action_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match action_enum(&input) {
+        Ok(x) => x.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn action_enum(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Enum(ref data) = input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "ActionEnum can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "ActionEnum cannot be derived for generic enums",
+        ));
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let kind = format_ident!("{}Kind", name);
+
+    let mut variants = Vec::new();
+    let mut names = Vec::new();
+    let mut payloads = Vec::new();
+    let mut patterns = Vec::new();
+    let mut constructors = Vec::new();
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let (payload, pattern, constructor) = match variant.fields {
+            Fields::Unit => (
+                quote!(()),
+                quote!(#name::#ident),
+                quote!(|()| #name::#ident),
+            ),
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                (
+                    quote!(#ty),
+                    quote!(#name::#ident(..)),
+                    quote!(#name::#ident),
+                )
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "ActionEnum variants must have no fields or a single unnamed field",
+                ));
+            }
+        };
+        let mut action_name = snake_case(&ident.to_string());
+        for attr in &variant.attrs {
+            if !attr.path().is_ident("action") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    action_name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported action attribute"))
+                }
+            })?;
+        }
+        variants.push(ident);
+        names.push(action_name);
+        payloads.push(payload);
+        patterns.push(pattern);
+        constructors.push(constructor);
+    }
+    let indices = 0..variants.len();
+    let indices2 = 0..variants.len();
+
+    Ok(quote! {
+        /// Identifies a variant of
+        #[doc = concat!("[`", stringify!(#name), "`]")]
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        #vis enum #kind {
+            #(#variants,)*
+        }
+
+        impl ::enact::ActionEnum for #name {
+            type Kind = #kind;
+
+            const NAMES: &'static [&'static str] = &[#(#names),*];
+
+            fn kind(&self) -> #kind {
+                match *self {
+                    #(#patterns => #kind::#variants,)*
+                }
+            }
+
+            fn index(kind: #kind) -> usize {
+                kind as usize
+            }
+
+            fn create_actions(
+                session: &mut ::enact::Session,
+            ) -> ::core::result::Result<::enact::__private::Vec<::enact::ActionId>, ::enact::DuplicateAction> {
+                ::core::result::Result::Ok(::enact::__private::vec![
+                    #(session.create_action::<#payloads>(#names)?.id(),)*
+                ])
+            }
+
+            fn poll(
+                seat: &::enact::Seat,
+                actions: &[::enact::ActionId],
+                index: usize,
+            ) -> ::core::option::Option<Self> {
+                match index {
+                    #(#indices => seat
+                        .poll(::enact::__private::action::<#payloads>(actions[#indices2]))
+                        .map(#constructors),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    })
+}
+
+/// Convert `UpperCamelCase` to `snake_case`
+fn snake_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,159 @@
+//! Input read directly from Linux evdev devices, for headless or non-winit
+//! applications such as kiosks and embedded cabinets
+//!
+//! Open devices with [`Devices::open`], then call [`Devices::handle`]
+//! regularly, e.g. once per frame. Reading `/dev/input` usually requires
+//! membership in the `input` group.
+
+use std::{io, path::PathBuf};
+
+use evdev::{AbsoluteAxisCode, Device, EventSummary, KeyCode, RelativeAxisCode};
+
+/// Identifies a source of input data
+///
+/// Written using the lowercase names of kernel event codes, e.g. `key_space`,
+/// `btn_south`, `abs_x`, or `rel_wheel`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    KeyHeld(KeyCode),
+    KeyPressed(KeyCode),
+    /// Position of an absolute axis, scaled from its range to `0..=1`, or
+    /// `-1..=1` for ranges including negative values
+    Absolute(AbsoluteAxisCode),
+    /// Motion along a relative axis, e.g. a mouse or scroll wheel, in device
+    /// units
+    Relative(RelativeAxisCode),
+}
+
+impl enact::Input for Input {
+    const NAME: &'static str = "evdev";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::KeyHeld(_) => V::visit::<bool>(),
+            Input::KeyPressed(_) => V::visit::<()>(),
+            Input::Absolute(_) | Input::Relative(_) => V::visit::<f64>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        let upper = s.trim().to_ascii_uppercase();
+        if let Ok(key) = upper.parse::<KeyCode>() {
+            return vec![Input::KeyHeld(key), Input::KeyPressed(key)];
+        }
+        if let Ok(axis) = upper.parse::<AbsoluteAxisCode>() {
+            return vec![Input::Absolute(axis)];
+        }
+        if let Ok(axis) = upper.parse::<RelativeAxisCode>() {
+            return vec![Input::Relative(axis)];
+        }
+        vec![]
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            Input::KeyHeld(k) | Input::KeyPressed(k) => format!("{k:?}"),
+            Input::Absolute(a) => format!("{a:?}"),
+            Input::Relative(r) => format!("{r:?}"),
+        }
+        .to_ascii_lowercase()
+    }
+}
+
+/// A set of open evdev devices
+pub struct Devices {
+    devices: Vec<OpenDevice>,
+}
+
+struct OpenDevice {
+    path: PathBuf,
+    device: Device,
+    /// Range of each absolute axis, indexed by code
+    ranges: Vec<(i32, i32)>,
+}
+
+impl Devices {
+    /// Open every device whose name satisfies `filter`
+    ///
+    /// For example, `Devices::open(|name| name.contains("Arcade"))`. Devices
+    /// which can't be opened or configured, e.g. for lack of permission, are
+    /// skipped.
+    pub fn open(mut filter: impl FnMut(&str) -> bool) -> Self {
+        let mut devices = Vec::new();
+        for (path, device) in evdev::enumerate() {
+            if !filter(device.name().unwrap_or("")) {
+                continue;
+            }
+            if device.set_nonblocking(true).is_err() {
+                continue;
+            }
+            let Ok(abs_state) = device.get_abs_state() else {
+                continue;
+            };
+            let ranges = abs_state
+                .iter()
+                .map(|info| (info.minimum, info.maximum))
+                .collect();
+            devices.push(OpenDevice {
+                path,
+                device,
+                ranges,
+            });
+        }
+        Self { devices }
+    }
+
+    /// Paths of the open devices, e.g. `/dev/input/event3`
+    pub fn paths(&self) -> impl Iterator<Item = &std::path::Path> + '_ {
+        self.devices.iter().map(|d| d.path.as_path())
+    }
+
+    /// Update action states in `seat` to account for all events since the
+    /// last call, according to `bindings`
+    ///
+    /// Devices which fail, e.g. because they were unplugged, are closed.
+    pub fn handle(&mut self, bindings: &impl enact::HandleInput, seat: &mut enact::Seat) {
+        self.devices.retain_mut(|open| {
+            let ranges = &open.ranges;
+            let events = match open.device.fetch_events() {
+                Ok(events) => events,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return true,
+                Err(_) => return false,
+            };
+            for event in events {
+                match event.destructure() {
+                    EventSummary::Key(_, key, value) => match value {
+                        0 => bindings.handle(&Input::KeyHeld(key), false, seat).unwrap(),
+                        1 => {
+                            bindings.handle(&Input::KeyHeld(key), true, seat).unwrap();
+                            bindings.handle(&Input::KeyPressed(key), (), seat).unwrap();
+                        }
+                        // Autorepeat
+                        _ => {}
+                    },
+                    EventSummary::AbsoluteAxis(_, axis, value) => {
+                        let (min, max) = ranges.get(axis.0 as usize).copied().unwrap_or((0, 0));
+                        // In `f64`, since extreme ranges overflow `i32`
+                        let (value, min, max) = (f64::from(value), f64::from(min), f64::from(max));
+                        let scaled = if min < 0.0 {
+                            value / max.max(-min).max(1.0)
+                        } else {
+                            (value - min) / (max - min).max(1.0)
+                        };
+                        bindings
+                            .handle(&Input::Absolute(axis), scaled.clamp(-1.0, 1.0), seat)
+                            .unwrap();
+                    }
+                    EventSummary::RelativeAxis(_, axis, value) => {
+                        bindings
+                            .handle(&Input::Relative(axis), f64::from(value), seat)
+                            .unwrap();
+                    }
+                    _ => {}
+                }
+            }
+            true
+        });
+    }
+}
@@ -0,0 +1,121 @@
+/*
+ * C API for enact
+ *
+ * Link against the enact_ffi static or dynamic library. See the enact-ffi
+ * crate documentation for an overview. Every function ignores null handles.
+ * Strings are NUL-terminated UTF-8. A handle may be moved between threads,
+ * but not used by several at once.
+ */
+
+#ifndef ENACT_H
+#define ENACT_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Returned in place of an action by enact_session_create_action on failure */
+#define ENACT_INVALID UINT32_MAX
+
+/* Type of an action's payload */
+typedef enum EnactType {
+    ENACT_UNIT = 0,
+    ENACT_BOOL = 1,
+    ENACT_F64 = 2,
+    ENACT_VEC2 = 3,
+} EnactType;
+
+/* A two-dimensional vector, e.g. a stick position or mouse motion */
+typedef struct EnactVec2 {
+    double x;
+    double y;
+} EnactVec2;
+
+/* The set of actions an application understands */
+typedef struct EnactSession EnactSession;
+/* Loads bindings from configs, binding inputs from the "ffi" source */
+typedef struct EnactFactory EnactFactory;
+/* Maps inputs to actions */
+typedef struct EnactBindings EnactBindings;
+/* The state of every action for one user */
+typedef struct EnactSeat EnactSeat;
+
+EnactSession *enact_session_new(void);
+void enact_session_free(EnactSession *session);
+/* Create an action named `name` carrying `ty` payloads
+ *
+ * Returns ENACT_INVALID if the name is already taken or `ty` is not an
+ * EnactType. */
+uint32_t enact_session_create_action(EnactSession *session, const char *name, uint32_t ty);
+
+EnactFactory *enact_factory_new(void);
+void enact_factory_free(EnactFactory *factory);
+/* Load bindings from a RON config
+ *
+ * Parts of the config which can't be loaded are skipped and reported by
+ * enact_bindings_error. A config which can't be parsed at all produces empty
+ * bindings. Returns null only if `factory` or `session` is null. */
+EnactBindings *enact_factory_load(const EnactFactory *factory, EnactSession *session,
+                                  const char *config);
+
+void enact_bindings_free(EnactBindings *bindings);
+/* Number of errors reported while loading `bindings` */
+size_t enact_bindings_error_count(const EnactBindings *bindings);
+/* Description of the `index`th error reported while loading `bindings`, or
+ * null if out of range; valid until `bindings` is freed */
+const char *enact_bindings_error(const EnactBindings *bindings, size_t index);
+/* Run filters affected by inputs handled since the last call
+ *
+ * Call after handling a batch of inputs, e.g. once per frame, before reading
+ * actions. */
+void enact_bindings_update(const EnactBindings *bindings, EnactSeat *seat);
+
+/* Change the state of the input named `input` */
+void enact_bindings_handle_unit(const EnactBindings *bindings, EnactSeat *seat,
+                                const char *input);
+void enact_bindings_handle_bool(const EnactBindings *bindings, EnactSeat *seat,
+                                const char *input, bool value);
+void enact_bindings_handle_f64(const EnactBindings *bindings, EnactSeat *seat,
+                               const char *input, double value);
+void enact_bindings_handle_vec2(const EnactBindings *bindings, EnactSeat *seat,
+                                const char *input, EnactVec2 value);
+
+EnactSeat *enact_seat_new(void);
+void enact_seat_free(EnactSeat *seat);
+/* Discard state changes not consumed by polling
+ *
+ * Must be called regularly, e.g. at the end of each frame. */
+void enact_seat_flush(EnactSeat *seat);
+
+/* Write the current state of `action` to `out`
+ *
+ * Returns false, leaving `out` unchanged, if `action` has no state or a
+ * different payload type. */
+bool enact_seat_get_bool(const EnactSeat *seat, const EnactSession *session, uint32_t action,
+                         bool *out);
+bool enact_seat_get_f64(const EnactSeat *seat, const EnactSession *session, uint32_t action,
+                        double *out);
+bool enact_seat_get_vec2(const EnactSeat *seat, const EnactSession *session, uint32_t action,
+                         EnactVec2 *out);
+
+/* Whether a unit action occurred and hasn't been polled since */
+bool enact_seat_poll_unit(const EnactSeat *seat, const EnactSession *session, uint32_t action);
+/* Write the next state change of `action` not yet polled to `out`
+ *
+ * Returns false, leaving `out` unchanged, if there is none. */
+bool enact_seat_poll_bool(const EnactSeat *seat, const EnactSession *session, uint32_t action,
+                          bool *out);
+bool enact_seat_poll_f64(const EnactSeat *seat, const EnactSession *session, uint32_t action,
+                         double *out);
+bool enact_seat_poll_vec2(const EnactSeat *seat, const EnactSession *session, uint32_t action,
+                          EnactVec2 *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,463 @@
+//! C API, for engines written in C or C++ and bindings for other languages
+//!
+//! Declared in `include/enact.h`. Exposes [`Session`], [`BindingsFactory`],
+//! [`Bindings`], and [`Seat`] through opaque handles, so that the same RON
+//! configs and filters can be used as from Rust. Actions carry one of a fixed
+//! set of payload types: unit, `bool`, `double`, or [`EnactVec2`], and are
+//! identified by the index returned from [`enact_session_create_action`].
+//!
+//! The application translates its own events into inputs and passes them by
+//! name, e.g. `enact_bindings_handle_bool(bindings, seat, "space", true)`.
+//! Configs bind these under the `"ffi"` source:
+//!
+//! ```ron
+//! (sources: [(type: "ffi", bindings: {"jump": ["space"]})])
+//! ```
+//!
+//! # Safety
+//!
+//! Every handle passed to a function must be null or have been returned by
+//! the corresponding `_new` or `_load` function and not yet freed. Null
+//! handles are ignored. Strings are NUL-terminated UTF-8. A handle may be
+//! moved between threads, but not used by several at once.
+
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::{CStr, CString, c_char};
+
+use enact::{Action, Bindings, BindingsFactory, Config, InputTypeVisitor, Seat, Session};
+
+/// Returned in place of an action by [`enact_session_create_action`] on
+/// failure
+pub const ENACT_INVALID: u32 = u32::MAX;
+
+/// Type of an action's payload, passed as a `u32`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum EnactType {
+    Unit = 0,
+    Bool = 1,
+    F64 = 2,
+    Vec2 = 3,
+}
+
+impl EnactType {
+    const ALL: [Self; 4] = [Self::Unit, Self::Bool, Self::F64, Self::Vec2];
+
+    fn from_raw(ty: u32) -> Option<Self> {
+        Self::ALL.get(ty as usize).copied()
+    }
+}
+
+/// A two-dimensional vector, e.g. a stick position or mouse motion
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[repr(C)]
+pub struct EnactVec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl From<EnactVec2> for mint::Vector2<f64> {
+    fn from(v: EnactVec2) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+
+impl From<mint::Vector2<f64>> for EnactVec2 {
+    fn from(v: mint::Vector2<f64>) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+
+/// An input supplied by the application, named arbitrarily
+///
+/// Each name may produce any payload type, so it can be bound to any action.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Input {
+    pub name: String,
+    pub ty: EnactType,
+}
+
+impl enact::Input for Input {
+    const NAME: &'static str = "ffi";
+
+    fn visit_type<V: InputTypeVisitor>(&self) -> V::Output {
+        match self.ty {
+            EnactType::Unit => V::visit::<()>(),
+            EnactType::Bool => V::visit::<bool>(),
+            EnactType::F64 => V::visit::<f64>(),
+            EnactType::Vec2 => V::visit::<mint::Vector2<f64>>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        let name = s.trim();
+        if name.is_empty() {
+            return vec![];
+        }
+        EnactType::ALL
+            .iter()
+            .map(|&ty| Input {
+                name: name.to_owned(),
+                ty,
+            })
+            .collect()
+    }
+
+    fn to_string(&self) -> String {
+        self.name.clone()
+    }
+}
+
+/// A [`Session`] and the actions created in it
+pub struct EnactSession {
+    session: Session,
+    actions: Vec<AnyAction>,
+}
+
+#[derive(Copy, Clone)]
+enum AnyAction {
+    Unit(Action<()>),
+    Bool(Action<bool>),
+    F64(Action<f64>),
+    Vec2(Action<mint::Vector2<f64>>),
+}
+
+impl EnactSession {
+    fn action(&self, action: u32) -> Option<AnyAction> {
+        self.actions.get(action as usize).copied()
+    }
+}
+
+/// A [`BindingsFactory`] accepting [`Input`]s
+pub struct EnactFactory(BindingsFactory);
+
+/// [`Bindings`] and the errors reported while loading them
+pub struct EnactBindings {
+    bindings: Bindings,
+    errors: Vec<CString>,
+}
+
+pub struct EnactSeat(Seat);
+
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(s) }.to_str().ok()
+}
+
+fn error_string(message: String) -> CString {
+    // Interior NULs can only come from the config itself
+    CString::new(message.replace('\0', "\\0")).unwrap()
+}
+
+unsafe fn free<T>(handle: *mut T) {
+    if !handle.is_null() {
+        drop(unsafe { Box::from_raw(handle) });
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn enact_session_new() -> *mut EnactSession {
+    Box::into_raw(Box::new(EnactSession {
+        session: Session::new(),
+        actions: Vec::new(),
+    }))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_session_free(session: *mut EnactSession) {
+    unsafe { free(session) }
+}
+
+/// Create an action named `name` carrying `ty` payloads
+///
+/// Returns [`ENACT_INVALID`] if the name is already taken or `ty` is not an
+/// [`EnactType`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_session_create_action(
+    session: *mut EnactSession,
+    name: *const c_char,
+    ty: u32,
+) -> u32 {
+    let (Some(session), Some(name), Some(ty)) = (
+        unsafe { session.as_mut() },
+        unsafe { to_str(name) },
+        EnactType::from_raw(ty),
+    ) else {
+        return ENACT_INVALID;
+    };
+    let inner = &mut session.session;
+    let action = match ty {
+        EnactType::Unit => inner.create_action(name).map(AnyAction::Unit),
+        EnactType::Bool => inner.create_action(name).map(AnyAction::Bool),
+        EnactType::F64 => inner.create_action(name).map(AnyAction::F64),
+        EnactType::Vec2 => inner.create_action(name).map(AnyAction::Vec2),
+    };
+    let Ok(action) = action else {
+        return ENACT_INVALID;
+    };
+    session.actions.push(action);
+    (session.actions.len() - 1) as u32
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn enact_factory_new() -> *mut EnactFactory {
+    let mut factory = BindingsFactory::new();
+    factory.register_source::<Input>();
+    Box::into_raw(Box::new(EnactFactory(factory)))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_factory_free(factory: *mut EnactFactory) {
+    unsafe { free(factory) }
+}
+
+/// Load bindings from a RON config
+///
+/// Parts of the config which can't be loaded are skipped and reported by
+/// [`enact_bindings_error`]. A config which can't be parsed at all produces
+/// empty bindings. Returns null only if `factory` or `session` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_factory_load(
+    factory: *const EnactFactory,
+    session: *mut EnactSession,
+    config: *const c_char,
+) -> *mut EnactBindings {
+    let (Some(factory), Some(session)) = (unsafe { factory.as_ref() }, unsafe { session.as_mut() })
+    else {
+        return std::ptr::null_mut();
+    };
+    let config = match unsafe { to_str(config) } {
+        Some(config) => Config::from_ron(config).map_err(|e| e.to_string()),
+        None => Err("config is null or not UTF-8".to_owned()),
+    };
+    let (bindings, errors) = match config {
+        Ok(config) => {
+            let (bindings, errors) = factory.0.load(&mut session.session, &config);
+            let errors = errors
+                .into_iter()
+                .map(|e| error_string(format!("{e:?}")))
+                .collect();
+            (bindings, errors)
+        }
+        Err(e) => (Bindings::new(), vec![error_string(e)]),
+    };
+    Box::into_raw(Box::new(EnactBindings { bindings, errors }))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_bindings_free(bindings: *mut EnactBindings) {
+    unsafe { free(bindings) }
+}
+
+/// Number of errors reported while loading `bindings`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_bindings_error_count(bindings: *const EnactBindings) -> usize {
+    unsafe { bindings.as_ref() }.map_or(0, |x| x.errors.len())
+}
+
+/// Description of the `index`th error reported while loading `bindings`, or
+/// null if out of range
+///
+/// Valid until `bindings` is freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_bindings_error(
+    bindings: *const EnactBindings,
+    index: usize,
+) -> *const c_char {
+    unsafe { bindings.as_ref() }
+        .and_then(|x| x.errors.get(index))
+        .map_or(std::ptr::null(), |e| e.as_ptr())
+}
+
+/// Run filters affected by inputs handled since the last call
+///
+/// Call after handling a batch of inputs, e.g. once per frame, before reading
+/// actions.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_bindings_update(
+    bindings: *const EnactBindings,
+    seat: *mut EnactSeat,
+) {
+    if let (Some(bindings), Some(seat)) = (unsafe { bindings.as_ref() }, unsafe { seat.as_mut() }) {
+        bindings.bindings.update(&mut seat.0);
+    }
+}
+
+unsafe fn handle<T: Clone + 'static>(
+    bindings: *const EnactBindings,
+    seat: *mut EnactSeat,
+    input: *const c_char,
+    ty: EnactType,
+    data: T,
+) {
+    let (Some(bindings), Some(seat), Some(name)) = (
+        unsafe { bindings.as_ref() },
+        unsafe { seat.as_mut() },
+        unsafe { to_str(input) },
+    ) else {
+        return;
+    };
+    let input = Input {
+        name: name.to_owned(),
+        ty,
+    };
+    bindings.bindings.handle(&input, data, &mut seat.0).unwrap();
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_bindings_handle_unit(
+    bindings: *const EnactBindings,
+    seat: *mut EnactSeat,
+    input: *const c_char,
+) {
+    unsafe { handle(bindings, seat, input, EnactType::Unit, ()) }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn enact_seat_new() -> *mut EnactSeat {
+    Box::into_raw(Box::new(EnactSeat(Seat::new())))
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_seat_free(seat: *mut EnactSeat) {
+    unsafe { free(seat) }
+}
+
+/// Discard state changes not consumed by polling
+///
+/// Must be called regularly, e.g. at the end of each frame.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_seat_flush(seat: *mut EnactSeat) {
+    if let Some(seat) = unsafe { seat.as_mut() } {
+        seat.0.flush();
+    }
+}
+
+/// Look up `action` and read it from `seat` with `read`
+unsafe fn read<T>(
+    seat: *const EnactSeat,
+    session: *const EnactSession,
+    action: u32,
+    read: impl FnOnce(&Seat, AnyAction) -> Option<T>,
+) -> Option<T> {
+    let seat = unsafe { seat.as_ref() }?;
+    let action = unsafe { session.as_ref() }?.action(action)?;
+    read(&seat.0, action)
+}
+
+/// Write `value` to `out`, if both are present
+unsafe fn output<T>(out: *mut T, value: Option<T>) -> bool {
+    let Some(value) = value else {
+        return false;
+    };
+    if let Some(out) = unsafe { out.as_mut() } {
+        *out = value;
+    }
+    true
+}
+
+/// Whether a unit action occurred and hasn't been polled since
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn enact_seat_poll_unit(
+    seat: *const EnactSeat,
+    session: *const EnactSession,
+    action: u32,
+) -> bool {
+    unsafe {
+        read(seat, session, action, |seat, action| match action {
+            AnyAction::Unit(action) => seat.poll(action),
+            _ => None,
+        })
+    }
+    .is_some()
+}
+
+/// Generate the functions for a payload type
+macro_rules! payload {
+    ($variant:ident, $ty:ty, $inner:ty, $handle:ident, $get:ident, $poll:ident) => {
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $handle(
+            bindings: *const EnactBindings,
+            seat: *mut EnactSeat,
+            input: *const c_char,
+            value: $ty,
+        ) {
+            unsafe {
+                handle(
+                    bindings,
+                    seat,
+                    input,
+                    EnactType::$variant,
+                    <$inner>::from(value),
+                )
+            }
+        }
+
+        /// Write the current state of `action` to `out`
+        ///
+        /// Returns false, leaving `out` unchanged, if `action` has no state or
+        /// a different payload type.
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $get(
+            seat: *const EnactSeat,
+            session: *const EnactSession,
+            action: u32,
+            out: *mut $ty,
+        ) -> bool {
+            unsafe {
+                let value = read(seat, session, action, |seat, action| match action {
+                    AnyAction::$variant(action) => seat.get(action).map(<$ty>::from),
+                    _ => None,
+                });
+                output(out, value)
+            }
+        }
+
+        /// Write the next state change of `action` not yet polled to `out`
+        ///
+        /// Returns false, leaving `out` unchanged, if there is none.
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $poll(
+            seat: *const EnactSeat,
+            session: *const EnactSession,
+            action: u32,
+            out: *mut $ty,
+        ) -> bool {
+            unsafe {
+                let value = read(seat, session, action, |seat, action| match action {
+                    AnyAction::$variant(action) => seat.poll(action).map(<$ty>::from),
+                    _ => None,
+                });
+                output(out, value)
+            }
+        }
+    };
+}
+
+payload!(
+    Bool,
+    bool,
+    bool,
+    enact_bindings_handle_bool,
+    enact_seat_get_bool,
+    enact_seat_poll_bool
+);
+payload!(
+    F64,
+    f64,
+    f64,
+    enact_bindings_handle_f64,
+    enact_seat_get_f64,
+    enact_seat_poll_f64
+);
+payload!(
+    Vec2,
+    EnactVec2,
+    mint::Vector2<f64>,
+    enact_bindings_handle_vec2,
+    enact_seat_get_vec2,
+    enact_seat_poll_vec2
+);
@@ -0,0 +1,216 @@
+use godot::{
+    classes::{FileAccess, INode, IRefCounted, InputEvent, Node, RefCounted},
+    global::Error,
+    prelude::*,
+};
+
+use crate::{Handler, Input, JoypadInput};
+
+/// Actions and the bindings loaded for them
+#[derive(GodotClass)]
+#[class(base = RefCounted)]
+pub struct EnactSession {
+    session: enact::Session,
+    factory: enact::BindingsFactory,
+    bindings: enact::Bindings,
+    actions: Vec<(StringName, AnyAction)>,
+}
+
+#[derive(Copy, Clone)]
+enum AnyAction {
+    Unit(enact::Action<()>),
+    Bool(enact::Action<bool>),
+    F64(enact::Action<f64>),
+    Vector2(enact::Action<mint::Vector2<f64>>),
+}
+
+impl AnyAction {
+    fn get(self, seat: &enact::Seat) -> Option<Variant> {
+        match self {
+            AnyAction::Unit(_) => None,
+            AnyAction::Bool(x) => seat.get(x).map(|x| x.to_variant()),
+            AnyAction::F64(x) => seat.get(x).map(|x| x.to_variant()),
+            AnyAction::Vector2(x) => seat.get(x).map(to_variant),
+        }
+    }
+
+    fn poll(self, seat: &enact::Seat) -> Option<Variant> {
+        match self {
+            AnyAction::Unit(x) => seat.poll(x).map(|()| Variant::nil()),
+            AnyAction::Bool(x) => seat.poll(x).map(|x| x.to_variant()),
+            AnyAction::F64(x) => seat.poll(x).map(|x| x.to_variant()),
+            AnyAction::Vector2(x) => seat.poll(x).map(to_variant),
+        }
+    }
+}
+
+fn to_variant(v: mint::Vector2<f64>) -> Variant {
+    Vector2::new(v.x as real, v.y as real).to_variant()
+}
+
+#[godot_api]
+impl IRefCounted for EnactSession {
+    fn init(_base: Base<RefCounted>) -> Self {
+        let mut factory = enact::BindingsFactory::new();
+        factory.register_source::<Input>();
+        factory.register_source::<JoypadInput>();
+        Self {
+            session: enact::Session::new(),
+            factory,
+            bindings: enact::Bindings::new(),
+            actions: Vec::new(),
+        }
+    }
+}
+
+#[godot_api]
+impl EnactSession {
+    /// Define an action carrying values of `value_type`
+    ///
+    /// `value_type` is one of `TYPE_NIL` for actions without a value, such as
+    /// jumping, `TYPE_BOOL`, `TYPE_FLOAT`, or `TYPE_VECTOR2`. Returns false if
+    /// the name is already taken or the type isn't supported.
+    #[func]
+    fn add_action(&mut self, name: StringName, value_type: VariantType) -> bool {
+        let session = &mut self.session;
+        let id = name.to_string();
+        let action = match value_type {
+            VariantType::NIL => session.create_action(&id).map(AnyAction::Unit),
+            VariantType::BOOL => session.create_action(&id).map(AnyAction::Bool),
+            VariantType::FLOAT => session.create_action(&id).map(AnyAction::F64),
+            VariantType::VECTOR2 => session.create_action(&id).map(AnyAction::Vector2),
+            _ => return false,
+        };
+        let Ok(action) = action else {
+            return false;
+        };
+        self.actions.push((name, action));
+        true
+    }
+
+    /// Replace the current bindings with those in a RON config
+    ///
+    /// Returns descriptions of any parts of the config which couldn't be
+    /// loaded. If the config can't be parsed at all, the current bindings are
+    /// kept.
+    #[func]
+    fn load_config(&mut self, config: GString) -> PackedStringArray {
+        let config = match enact::Config::from_ron(&config.to_string()) {
+            Ok(config) => config,
+            Err(e) => return strings([e.to_string()]),
+        };
+        let (bindings, errors) = self.factory.load(&mut self.session, &config);
+        self.bindings = bindings;
+        strings(errors.iter().map(|e| format!("{e:?}")))
+    }
+
+    /// Like `load_config`, reading the config from `path`, e.g. a `res://`
+    /// path
+    #[func]
+    fn load_config_file(&mut self, path: GString) -> PackedStringArray {
+        let config = FileAccess::get_file_as_string(&path);
+        if FileAccess::get_open_error() != Error::OK {
+            return strings([format!("couldn't read {path}")]);
+        }
+        self.load_config(config)
+    }
+}
+
+fn strings(x: impl IntoIterator<Item = String>) -> PackedStringArray {
+    x.into_iter().map(|x| GString::from(x.as_str())).collect()
+}
+
+/// The state of every action for one player, driven by unhandled input
+/// events
+#[derive(GodotClass)]
+#[class(base = Node)]
+pub struct EnactSeat {
+    /// Session whose bindings and actions are used
+    #[var]
+    session: Option<Gd<EnactSession>>,
+    /// Ignore keyboard and mouse events, e.g. for players using joypads
+    #[export]
+    ignore_keyboard_mouse: bool,
+    /// Only handle events from this joypad device, or from all joypads if -1
+    #[export]
+    joypad: i32,
+    seat: enact::Seat,
+    base: Base<Node>,
+}
+
+#[godot_api]
+impl INode for EnactSeat {
+    fn init(base: Base<Node>) -> Self {
+        Self {
+            session: None,
+            ignore_keyboard_mouse: false,
+            joypad: -1,
+            seat: enact::Seat::new(),
+            base,
+        }
+    }
+
+    fn unhandled_input(&mut self, event: Gd<InputEvent>) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        let handler = Handler {
+            ignore_keyboard_mouse: self.ignore_keyboard_mouse,
+            joypad: (self.joypad >= 0).then_some(self.joypad),
+        };
+        handler.handle(&event, &session.bind().bindings, &mut self.seat);
+    }
+
+    fn process(&mut self, _delta: f64) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        // Signals are emitted after releasing the session, so that handlers
+        // may use it
+        let mut changes = Vec::new();
+        {
+            let session = session.bind();
+            session.bindings.update(&mut self.seat);
+            for &(ref name, action) in &session.actions {
+                while let Some(value) = action.poll(&self.seat) {
+                    let unit = matches!(action, AnyAction::Unit(_));
+                    changes.push((name.clone(), unit, value));
+                }
+            }
+        }
+        self.seat.flush();
+        for (name, unit, value) in changes {
+            if unit {
+                self.signals().action_triggered().emit(&name);
+            } else {
+                self.signals().action_changed().emit(&name, &value);
+            }
+        }
+    }
+}
+
+#[godot_api]
+impl EnactSeat {
+    /// A unit action occurred
+    #[signal]
+    fn action_triggered(action: StringName);
+
+    /// The value of a non-unit action changed
+    #[signal]
+    fn action_changed(action: StringName, value: Variant);
+
+    /// Current value of `action`, or null if it has none
+    #[func]
+    fn get_action(&self, action: StringName) -> Variant {
+        let Some(session) = &self.session else {
+            return Variant::nil();
+        };
+        let session = session.bind();
+        session
+            .actions
+            .iter()
+            .find(|(name, _)| *name == action)
+            .and_then(|&(_, x)| x.get(&self.seat))
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,533 @@
+//! Godot 4 extension, for prototyping in Godot while sharing binding configs
+//! with a Rust core
+//!
+//! Load the built library with a `.gdextension` file such as the
+//! `enact.gdextension` alongside this crate. It registers two classes:
+//!
+//! - `EnactSession`, which defines actions with `add_action` and loads RON
+//!   configs with `load_config` or `load_config_file`, returning any errors.
+//! - `EnactSeat`, a node which translates unhandled `InputEvent`s into inputs
+//!   for its `session`'s bindings, and each frame emits `action_triggered`
+//!   for unit actions and `action_changed` for others. Current states are
+//!   available from `get_action`. Local multiplayer games can use one seat per
+//!   player, restricted to different joypads.
+//!
+//! Keyboard and mouse inputs are written as in `enact-winit`, and share its
+//! source name, so the same config can be used in Godot and native builds.
+//! Joypad inputs are written with Godot's names, e.g. `"left shoulder"` or
+//! `"left x"`, under the `joypad` source.
+//!
+//! Rust code embedding Godot can use [`Input`], [`JoypadInput`], and
+//! [`Handler`] directly.
+
+mod classes;
+
+use godot::{
+    classes::{
+        InputEvent, InputEventJoypadButton, InputEventJoypadMotion, InputEventKey,
+        InputEventMouseButton, InputEventMouseMotion,
+    },
+    global::{JoyAxis, JoyButton, Key as Keycode, KeyLocation, MouseButton},
+    prelude::*,
+};
+
+pub use classes::{EnactSeat, EnactSession};
+
+struct Extension;
+
+#[gdextension]
+unsafe impl ExtensionLibrary for Extension {}
+
+/// Identifies a keyboard or mouse source of input data
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    KeyHeld(Key),
+    MouseButtonHeld(MouseButton),
+    KeyPressed(Key),
+    MouseButtonPressed(MouseButton),
+    MouseMotion,
+}
+
+impl enact::Input for Input {
+    /// Matches `enact-winit`, so that configs are portable between them
+    const NAME: &'static str = "winit";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::KeyHeld(_) | Input::MouseButtonHeld(_) => V::visit::<bool>(),
+            Input::KeyPressed(_) | Input::MouseButtonPressed(_) => V::visit::<()>(),
+            Input::MouseMotion => V::visit::<mint::Vector2<f64>>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        if let Some(key) = Key::parse(s) {
+            return vec![Input::KeyHeld(key), Input::KeyPressed(key)];
+        }
+        if let Some(button) = parse_mouse_button(s) {
+            return vec![
+                Input::MouseButtonHeld(button),
+                Input::MouseButtonPressed(button),
+            ];
+        }
+        if s.eq_ignore_ascii_case("mouse") {
+            return vec![Input::MouseMotion];
+        }
+        vec![]
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            Input::KeyHeld(k) | Input::KeyPressed(k) => k.format(),
+            Input::MouseButtonHeld(b) | Input::MouseButtonPressed(b) => format_mouse_button(b),
+            Input::MouseMotion => "mouse".to_owned(),
+        }
+    }
+
+    fn names() -> Vec<String> {
+        KEYS.iter()
+            .map(|&(name, _)| name)
+            .chain(MOUSE_BUTTONS.iter().map(|&(name, _)| name))
+            .chain(["mouse"])
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn device_family(&self) -> enact::DeviceFamily {
+        enact::DeviceFamily::KeyboardMouse
+    }
+
+    /// Matches `enact-winit`, e.g. `kbm/space` or `kbm/mouse-left`
+    ///
+    /// Keys without a portable name have no glyph.
+    fn glyph(&self) -> Option<String> {
+        let name = match *self {
+            Input::KeyHeld(k) | Input::KeyPressed(k) => k.name()?.to_owned(),
+            Input::MouseButtonHeld(b) | Input::MouseButtonPressed(b) => format_mouse_button(b),
+            Input::MouseMotion => "mouse".to_owned(),
+        };
+        enact::DeviceFamily::KeyboardMouse.glyph(&name)
+    }
+}
+
+/// A physical key
+///
+/// Keys without a portable name are written as `<godot N>`, where `N` is the
+/// Godot keycode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: Keycode,
+    /// Whether this is the right-hand copy of a modifier key
+    pub right: bool,
+}
+
+impl Key {
+    /// The key which produced `event`
+    pub fn from_event(event: &InputEventKey) -> Self {
+        let code = match event.get_physical_keycode() {
+            Keycode::NONE => event.get_keycode(),
+            code => code,
+        };
+        Self {
+            code,
+            right: event.get_location() == KeyLocation::RIGHT,
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if let Some(&(_, key)) = KEYS
+            .iter()
+            .chain(KEY_ALIASES)
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        {
+            return Some(key);
+        }
+        let code = s.strip_prefix("<godot ")?.strip_suffix('>')?;
+        Some(Self {
+            code: Keycode::from_ord(code.parse().ok()?),
+            right: false,
+        })
+    }
+
+    /// Portable name of the key, if any
+    fn name(self) -> Option<&'static str> {
+        KEYS.iter()
+            .find(|&&(_, key)| key == self)
+            .map(|&(name, _)| name)
+    }
+
+    fn format(self) -> String {
+        match self.name() {
+            Some(name) => name.to_owned(),
+            None => format!("<godot {}>", self.code.ord()),
+        }
+    }
+}
+
+const fn key(code: Keycode) -> Key {
+    Key { code, right: false }
+}
+
+const fn right(code: Keycode) -> Key {
+    Key { code, right: true }
+}
+
+/// Portable key names, matching `enact-winit`
+const KEYS: &[(&str, Key)] = &[
+    ("a", key(Keycode::A)),
+    ("b", key(Keycode::B)),
+    ("c", key(Keycode::C)),
+    ("d", key(Keycode::D)),
+    ("e", key(Keycode::E)),
+    ("f", key(Keycode::F)),
+    ("g", key(Keycode::G)),
+    ("h", key(Keycode::H)),
+    ("i", key(Keycode::I)),
+    ("j", key(Keycode::J)),
+    ("k", key(Keycode::K)),
+    ("l", key(Keycode::L)),
+    ("m", key(Keycode::M)),
+    ("n", key(Keycode::N)),
+    ("o", key(Keycode::O)),
+    ("p", key(Keycode::P)),
+    ("q", key(Keycode::Q)),
+    ("r", key(Keycode::R)),
+    ("s", key(Keycode::S)),
+    ("t", key(Keycode::T)),
+    ("u", key(Keycode::U)),
+    ("v", key(Keycode::V)),
+    ("w", key(Keycode::W)),
+    ("x", key(Keycode::X)),
+    ("y", key(Keycode::Y)),
+    ("z", key(Keycode::Z)),
+    ("0", key(Keycode::KEY_0)),
+    ("1", key(Keycode::KEY_1)),
+    ("2", key(Keycode::KEY_2)),
+    ("3", key(Keycode::KEY_3)),
+    ("4", key(Keycode::KEY_4)),
+    ("5", key(Keycode::KEY_5)),
+    ("6", key(Keycode::KEY_6)),
+    ("7", key(Keycode::KEY_7)),
+    ("8", key(Keycode::KEY_8)),
+    ("9", key(Keycode::KEY_9)),
+    ("f1", key(Keycode::F1)),
+    ("f2", key(Keycode::F2)),
+    ("f3", key(Keycode::F3)),
+    ("f4", key(Keycode::F4)),
+    ("f5", key(Keycode::F5)),
+    ("f6", key(Keycode::F6)),
+    ("f7", key(Keycode::F7)),
+    ("f8", key(Keycode::F8)),
+    ("f9", key(Keycode::F9)),
+    ("f10", key(Keycode::F10)),
+    ("f11", key(Keycode::F11)),
+    ("f12", key(Keycode::F12)),
+    ("space", key(Keycode::SPACE)),
+    ("enter", key(Keycode::ENTER)),
+    ("escape", key(Keycode::ESCAPE)),
+    ("tab", key(Keycode::TAB)),
+    ("backspace", key(Keycode::BACKSPACE)),
+    ("delete", key(Keycode::DELETE)),
+    ("insert", key(Keycode::INSERT)),
+    ("home", key(Keycode::HOME)),
+    ("end", key(Keycode::END)),
+    ("page up", key(Keycode::PAGEUP)),
+    ("page down", key(Keycode::PAGEDOWN)),
+    ("up", key(Keycode::UP)),
+    ("down", key(Keycode::DOWN)),
+    ("left", key(Keycode::LEFT)),
+    ("right", key(Keycode::RIGHT)),
+    ("left shift", key(Keycode::SHIFT)),
+    ("right shift", right(Keycode::SHIFT)),
+    ("left ctrl", key(Keycode::CTRL)),
+    ("right ctrl", right(Keycode::CTRL)),
+    ("left alt", key(Keycode::ALT)),
+    ("right alt", right(Keycode::ALT)),
+    ("caps lock", key(Keycode::CAPSLOCK)),
+    ("`", key(Keycode::QUOTELEFT)),
+    ("-", key(Keycode::MINUS)),
+    ("=", key(Keycode::EQUAL)),
+    ("[", key(Keycode::BRACKETLEFT)),
+    ("]", key(Keycode::BRACKETRIGHT)),
+    ("\\", key(Keycode::BACKSLASH)),
+    (";", key(Keycode::SEMICOLON)),
+    ("'", key(Keycode::APOSTROPHE)),
+    (",", key(Keycode::COMMA)),
+    (".", key(Keycode::PERIOD)),
+    ("/", key(Keycode::SLASH)),
+];
+
+/// Alternative spellings accepted by `enact-winit`
+const KEY_ALIASES: &[(&str, Key)] = &[
+    ("spacebar", key(Keycode::SPACE)),
+    ("return", key(Keycode::ENTER)),
+    ("esc", key(Keycode::ESCAPE)),
+    ("del", key(Keycode::DELETE)),
+    ("ins", key(Keycode::INSERT)),
+    ("pgup", key(Keycode::PAGEUP)),
+    ("pgdn", key(Keycode::PAGEDOWN)),
+    ("shift", key(Keycode::SHIFT)),
+    ("lshift", key(Keycode::SHIFT)),
+    ("rshift", right(Keycode::SHIFT)),
+    ("ctrl", key(Keycode::CTRL)),
+    ("lctrl", key(Keycode::CTRL)),
+    ("left control", key(Keycode::CTRL)),
+    ("rctrl", right(Keycode::CTRL)),
+    ("right control", right(Keycode::CTRL)),
+    ("alt", key(Keycode::ALT)),
+    ("lalt", key(Keycode::ALT)),
+    ("ralt", right(Keycode::ALT)),
+    ("altgr", right(Keycode::ALT)),
+    ("grave", key(Keycode::QUOTELEFT)),
+    ("tilde", key(Keycode::QUOTELEFT)),
+    ("minus", key(Keycode::MINUS)),
+    ("equals", key(Keycode::EQUAL)),
+];
+
+/// Mouse buttons, named as in `enact-winit`
+///
+/// Other buttons, including the wheel, are ignored.
+const MOUSE_BUTTONS: &[(&str, MouseButton)] = &[
+    ("mouse left", MouseButton::LEFT),
+    ("mouse right", MouseButton::RIGHT),
+    ("mouse middle", MouseButton::MIDDLE),
+    ("mouse back", MouseButton::XBUTTON1),
+    ("mouse forward", MouseButton::XBUTTON2),
+];
+
+fn parse_mouse_button(s: &str) -> Option<MouseButton> {
+    MOUSE_BUTTONS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(s.trim()))
+        .map(|&(_, button)| button)
+}
+
+fn format_mouse_button(button: MouseButton) -> String {
+    MOUSE_BUTTONS
+        .iter()
+        .find(|&&(_, b)| b == button)
+        .map_or_else(
+            || format!("mouse {}", button.ord()),
+            |&(name, _)| name.to_owned(),
+        )
+}
+
+/// Identifies a joypad source of input data
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum JoypadInput {
+    ButtonHeld(JoyButton),
+    ButtonPressed(JoyButton),
+    /// Position in `-1..=1`, or `0..=1` for triggers
+    Axis(JoyAxis),
+}
+
+impl enact::Input for JoypadInput {
+    const NAME: &'static str = "joypad";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            JoypadInput::ButtonHeld(_) => V::visit::<bool>(),
+            JoypadInput::ButtonPressed(_) => V::visit::<()>(),
+            JoypadInput::Axis(_) => V::visit::<f64>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        let s = s.trim();
+        if let Some(&(_, button)) = JOY_BUTTONS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        {
+            return vec![
+                JoypadInput::ButtonHeld(button),
+                JoypadInput::ButtonPressed(button),
+            ];
+        }
+        if let Some(&(_, axis)) = JOY_AXES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        {
+            return vec![JoypadInput::Axis(axis)];
+        }
+        vec![]
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            JoypadInput::ButtonHeld(b) | JoypadInput::ButtonPressed(b) => {
+                JOY_BUTTONS.iter().find(|&&(_, x)| x == b).map_or_else(
+                    || format!("button {}", b.ord()),
+                    |&(name, _)| name.to_owned(),
+                )
+            }
+            JoypadInput::Axis(a) => JOY_AXES
+                .iter()
+                .find(|&&(_, x)| x == a)
+                .map_or_else(|| format!("axis {}", a.ord()), |&(name, _)| name.to_owned()),
+        }
+    }
+
+    fn names() -> Vec<String> {
+        JOY_BUTTONS
+            .iter()
+            .map(|&(name, _)| name)
+            .chain(JOY_AXES.iter().map(|&(name, _)| name))
+            .map(str::to_owned)
+            .collect()
+    }
+}
+
+const JOY_BUTTONS: &[(&str, JoyButton)] = &[
+    ("a", JoyButton::A),
+    ("b", JoyButton::B),
+    ("x", JoyButton::X),
+    ("y", JoyButton::Y),
+    ("back", JoyButton::BACK),
+    ("guide", JoyButton::GUIDE),
+    ("start", JoyButton::START),
+    ("left stick", JoyButton::LEFT_STICK),
+    ("right stick", JoyButton::RIGHT_STICK),
+    ("left shoulder", JoyButton::LEFT_SHOULDER),
+    ("right shoulder", JoyButton::RIGHT_SHOULDER),
+    ("dpad up", JoyButton::DPAD_UP),
+    ("dpad down", JoyButton::DPAD_DOWN),
+    ("dpad left", JoyButton::DPAD_LEFT),
+    ("dpad right", JoyButton::DPAD_RIGHT),
+    ("misc", JoyButton::MISC1),
+    ("paddle 1", JoyButton::PADDLE1),
+    ("paddle 2", JoyButton::PADDLE2),
+    ("paddle 3", JoyButton::PADDLE3),
+    ("paddle 4", JoyButton::PADDLE4),
+    ("touchpad", JoyButton::TOUCHPAD),
+];
+
+const JOY_AXES: &[(&str, JoyAxis)] = &[
+    ("left x", JoyAxis::LEFT_X),
+    ("left y", JoyAxis::LEFT_Y),
+    ("right x", JoyAxis::RIGHT_X),
+    ("right y", JoyAxis::RIGHT_Y),
+    ("left trigger", JoyAxis::TRIGGER_LEFT),
+    ("right trigger", JoyAxis::TRIGGER_RIGHT),
+];
+
+/// Translates Godot input events into inputs according to configurable
+/// policies
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Handler {
+    /// Ignore keyboard and mouse events, e.g. for seats of players using
+    /// joypads
+    pub ignore_keyboard_mouse: bool,
+    /// Only handle events from this joypad device
+    pub joypad: Option<i32>,
+}
+
+impl Handler {
+    /// Create a handler with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update action states in `seat` to account for any inputs in `event`
+    /// according to `bindings`
+    ///
+    /// Handles key, mouse button, mouse motion, joypad button, and joypad
+    /// motion events, ignoring all others. Key echoes are ignored.
+    pub fn handle(
+        &self,
+        event: &Gd<InputEvent>,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        let event = event.clone();
+        let event = match event.try_cast::<InputEventJoypadButton>() {
+            Ok(event) => {
+                if !self.accepts_joypad(event.get_device()) {
+                    return;
+                }
+                let button = event.get_button_index();
+                let pressed = event.is_pressed();
+                bindings
+                    .handle(&JoypadInput::ButtonHeld(button), pressed, seat)
+                    .unwrap();
+                if pressed {
+                    bindings
+                        .handle(&JoypadInput::ButtonPressed(button), (), seat)
+                        .unwrap();
+                }
+                return;
+            }
+            Err(event) => event,
+        };
+        let event = match event.try_cast::<InputEventJoypadMotion>() {
+            Ok(event) => {
+                if self.accepts_joypad(event.get_device()) {
+                    bindings
+                        .handle(
+                            &JoypadInput::Axis(event.get_axis()),
+                            f64::from(event.get_axis_value()),
+                            seat,
+                        )
+                        .unwrap();
+                }
+                return;
+            }
+            Err(event) => event,
+        };
+        if self.ignore_keyboard_mouse {
+            return;
+        }
+        let event = match event.try_cast::<InputEventKey>() {
+            Ok(event) => {
+                if event.is_echo() {
+                    return;
+                }
+                let key = Key::from_event(&event);
+                let pressed = event.is_pressed();
+                bindings
+                    .handle(&Input::KeyHeld(key), pressed, seat)
+                    .unwrap();
+                if pressed {
+                    bindings.handle(&Input::KeyPressed(key), (), seat).unwrap();
+                }
+                return;
+            }
+            Err(event) => event,
+        };
+        let event = match event.try_cast::<InputEventMouseButton>() {
+            Ok(event) => {
+                let button = event.get_button_index();
+                if !MOUSE_BUTTONS.iter().any(|&(_, b)| b == button) {
+                    return;
+                }
+                let pressed = event.is_pressed();
+                bindings
+                    .handle(&Input::MouseButtonHeld(button), pressed, seat)
+                    .unwrap();
+                if pressed {
+                    bindings
+                        .handle(&Input::MouseButtonPressed(button), (), seat)
+                        .unwrap();
+                }
+                return;
+            }
+            Err(event) => event,
+        };
+        if let Ok(event) = event.try_cast::<InputEventMouseMotion>() {
+            let delta = event.get_relative();
+            bindings
+                .handle(
+                    &Input::MouseMotion,
+                    mint::Vector2::<f64>::from([delta.x as f64, delta.y as f64]),
+                    seat,
+                )
+                .unwrap();
+        }
+    }
+
+    fn accepts_joypad(&self, device: i32) -> bool {
+        self.joypad.is_none_or(|x| x == device)
+    }
+}
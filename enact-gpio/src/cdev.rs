@@ -0,0 +1,60 @@
+//! Pins wired directly to a Linux GPIO chip
+
+use std::{io, path::Path};
+
+use gpio_cdev::{Chip, LineRequestFlags, MultiLineHandle};
+
+use crate::{Event, Source};
+
+/// [`Source`] reading pins of a GPIO character device, e.g. `/dev/gpiochip0`
+///
+/// Pins are numbered by their line offsets on the chip. They're sampled each
+/// time they're polled, so presses shorter than the polling interval may be
+/// missed.
+pub struct Gpio {
+    pins: Vec<u32>,
+    handle: MultiLineHandle,
+    /// States as of the last poll
+    last: Vec<u8>,
+}
+
+impl Gpio {
+    /// Request `pins` of the chip at `path` as inputs
+    ///
+    /// If `active_low`, pins are active while pulled low, as is usual for
+    /// buttons wired to ground with pull-up resistors.
+    pub fn open(path: impl AsRef<Path>, pins: &[u32], active_low: bool) -> io::Result<Self> {
+        let mut chip = Chip::new(path).map_err(io::Error::other)?;
+        let lines = chip.get_lines(pins).map_err(io::Error::other)?;
+        let mut flags = LineRequestFlags::INPUT;
+        if active_low {
+            flags |= LineRequestFlags::ACTIVE_LOW;
+        }
+        // Default values only apply to outputs, but must be supplied
+        let handle = lines
+            .request(flags, &vec![0; pins.len()], "enact")
+            .map_err(io::Error::other)?;
+        Ok(Self {
+            pins: pins.to_vec(),
+            handle,
+            // Report every pin's initial state
+            last: vec![u8::MAX; pins.len()],
+        })
+    }
+}
+
+impl Source for Gpio {
+    fn poll(&mut self, out: &mut Vec<Event>) -> io::Result<()> {
+        let values = self.handle.get_values().map_err(io::Error::other)?;
+        for ((&pin, last), value) in self.pins.iter().zip(&mut self.last).zip(values) {
+            if value != *last {
+                *last = value;
+                out.push(Event::Pin {
+                    pin,
+                    active: value != 0,
+                });
+            }
+        }
+        Ok(())
+    }
+}
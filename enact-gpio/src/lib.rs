@@ -0,0 +1,299 @@
+//! Digital pins and rotary encoders as inputs, for arcade cabinets and kiosks
+//!
+//! Pin and encoder states are read from a [`Source`] and translated into
+//! inputs by a [`Handler`]. Enable the `cdev` feature to read pins wired
+//! directly to a Linux GPIO chip via `cdev::Gpio`. Pins and encoders wired
+//! to a microcontroller can instead be reported over a serial port or any
+//! other stream with [`Serial`], whose `Serial::open` requires the `serial`
+//! feature.
+//!
+//! Bindings name pins and encoders by number, e.g. `"pin 17"` or
+//! `"encoder 0"`. A pin binding expands to both [`Input::PinHeld`] and
+//! [`Input::PinPressed`], and binds whichever suits the action's type.
+
+#[cfg(feature = "cdev")]
+pub mod cdev;
+
+use std::io::{self, BufRead};
+
+/// Identifies a source of input data
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    /// Whether a pin is active, written as `pin <n>`
+    PinHeld(u32),
+    /// A pin became active, written as `pin <n>`
+    PinPressed(u32),
+    /// Rotation of an encoder, in counts, written as `encoder <n>`
+    Encoder(u32),
+}
+
+impl enact::Input for Input {
+    const NAME: &'static str = "gpio";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::PinHeld(_) => V::visit::<bool>(),
+            Input::PinPressed(_) => V::visit::<()>(),
+            Input::Encoder(_) => V::visit::<f64>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        let Some((kind, n)) = s.trim().split_once(' ') else {
+            return vec![];
+        };
+        let Ok(n) = n.trim().parse() else {
+            return vec![];
+        };
+        match kind {
+            "pin" => vec![Input::PinHeld(n), Input::PinPressed(n)],
+            "encoder" => vec![Input::Encoder(n)],
+            _ => vec![],
+        }
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            Input::PinHeld(n) | Input::PinPressed(n) => format!("pin {n}"),
+            Input::Encoder(n) => format!("encoder {n}"),
+        }
+    }
+}
+
+/// A change reported by a [`Source`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A pin's state, written as `pin <n> <0|1>`
+    Pin { pin: u32, active: bool },
+    /// An encoder's rotation since it was last reported, written as
+    /// `encoder <n> <counts>`
+    Encoder { encoder: u32, counts: i32 },
+}
+
+impl Event {
+    /// Parse an event written as described for each variant, e.g. `pin 3 1`
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut words = s.split_whitespace();
+        let kind = words.next()?;
+        let n = words.next()?.parse().ok()?;
+        let value = words.next()?;
+        if words.next().is_some() {
+            return None;
+        }
+        match kind {
+            "pin" => Some(Event::Pin {
+                pin: n,
+                active: match value {
+                    "0" => false,
+                    "1" => true,
+                    _ => return None,
+                },
+            }),
+            "encoder" => Some(Event::Encoder {
+                encoder: n,
+                counts: value.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A source of pin and encoder states
+pub trait Source {
+    /// Report every change since the last call to `out`
+    ///
+    /// Must not block.
+    fn poll(&mut self, out: &mut Vec<Event>) -> io::Result<()>;
+}
+
+/// Reads [`Event`]s written one per line, e.g. by a microcontroller over a
+/// serial port
+///
+/// The stream should be non-blocking or have a short timeout; reads which
+/// time out are taken to mean no data is available. Unrecognized lines are
+/// ignored.
+pub struct Serial<R> {
+    reader: io::BufReader<R>,
+    line: String,
+}
+
+impl<R: io::Read> Serial<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: io::BufReader::new(reader),
+            line: String::new(),
+        }
+    }
+}
+
+#[cfg(feature = "serial")]
+impl Serial<Box<dyn serialport::SerialPort>> {
+    /// Open the serial port at `path`, e.g. `/dev/ttyACM0`
+    pub fn open(path: &str, baud_rate: u32) -> serialport::Result<Self> {
+        let port = serialport::new(path, baud_rate)
+            .timeout(std::time::Duration::ZERO)
+            .open()?;
+        Ok(Self::new(port))
+    }
+}
+
+impl<R: io::Read> Source for Serial<R> {
+    fn poll(&mut self, out: &mut Vec<Event>) -> io::Result<()> {
+        loop {
+            // Partial lines are kept until the rest arrives
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return Ok(()),
+                Ok(_) if !self.line.ends_with('\n') => continue,
+                Ok(_) => {}
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    return Ok(());
+                }
+                // Line noise
+                Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                    self.line.clear();
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+            out.extend(Event::parse(&self.line));
+            self.line.clear();
+        }
+    }
+}
+
+/// Translates [`Event`]s into inputs
+///
+/// Encoders may be reported directly, or decoded from the quadrature signals
+/// of a pair of pins registered with [`add_encoder`](Self::add_encoder).
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    /// Pins currently active
+    held: Vec<u32>,
+    encoders: Vec<Quadrature>,
+    events: Vec<Event>,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Quadrature {
+    encoder: u32,
+    a: u32,
+    b: u32,
+    /// Latest states of `a` and `b` as bits 1 and 0
+    state: u8,
+    /// Which of `a` and `b` have been reported, as in `state`
+    seen: u8,
+}
+
+impl Handler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode `encoder` from the quadrature signals on pins `a` and `b`
+    ///
+    /// Each edge counts once, so most mechanical encoders produce four counts
+    /// per detent. Changes to `a` and `b` no longer produce pin inputs. Fast
+    /// encoders such as spinners may turn faster than a polled [`Source`] can
+    /// sample them, in which case they should be decoded by a microcontroller
+    /// and reported as [`Event::Encoder`] instead.
+    pub fn add_encoder(&mut self, encoder: u32, a: u32, b: u32) {
+        self.encoders.push(Quadrature {
+            encoder,
+            a,
+            b,
+            state: 0,
+            seen: 0,
+        });
+    }
+
+    /// Handle every event reported by `source` since the last call
+    pub fn poll(
+        &mut self,
+        source: &mut impl Source,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) -> io::Result<()> {
+        let mut events = std::mem::take(&mut self.events);
+        let result = source.poll(&mut events);
+        for event in events.drain(..) {
+            self.handle(event, bindings, seat);
+        }
+        self.events = events;
+        result
+    }
+
+    /// Handle a single event
+    pub fn handle(
+        &mut self,
+        event: Event,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        match event {
+            Event::Pin { pin, active } => {
+                if let Some(quadrature) =
+                    self.encoders.iter_mut().find(|x| x.a == pin || x.b == pin)
+                {
+                    let bit = if quadrature.a == pin { 2 } else { 1 };
+                    let state = if active {
+                        quadrature.state | bit
+                    } else {
+                        quadrature.state & !bit
+                    };
+                    let counts = QUADRATURE[usize::from(quadrature.state << 2 | state)];
+                    quadrature.state = state;
+                    // Initial states aren't motion
+                    let known = quadrature.seen == 3;
+                    quadrature.seen |= bit;
+                    if known && counts != 0.0 {
+                        bindings
+                            .handle(&Input::Encoder(quadrature.encoder), counts, seat)
+                            .unwrap();
+                    }
+                    return;
+                }
+                if active == self.held.contains(&pin) {
+                    return;
+                }
+                if active {
+                    self.held.push(pin);
+                } else {
+                    self.held.retain(|&x| x != pin);
+                }
+                bindings.handle(&Input::PinHeld(pin), active, seat).unwrap();
+                if active {
+                    bindings.handle(&Input::PinPressed(pin), (), seat).unwrap();
+                }
+            }
+            Event::Encoder { encoder, counts } => {
+                if counts != 0 {
+                    bindings
+                        .handle(&Input::Encoder(encoder), f64::from(counts), seat)
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    /// Release every active pin, e.g. when a [`Source`] is disconnected
+    pub fn reset(&mut self, bindings: &impl enact::HandleInput, seat: &mut enact::Seat) {
+        for pin in std::mem::take(&mut self.held) {
+            bindings.handle(&Input::PinHeld(pin), false, seat).unwrap();
+        }
+    }
+}
+
+/// Counts for each transition between quadrature states, indexed by the
+/// previous state followed by the next
+const QUADRATURE: [f64; 16] = [
+    0.0, -1.0, 1.0, 0.0, //
+    1.0, 0.0, 0.0, -1.0, //
+    -1.0, 0.0, 0.0, 1.0, //
+    0.0, 1.0, -1.0, 0.0,
+];
@@ -0,0 +1,211 @@
+//! Sony DualSense features which generic gamepad APIs don't expose
+//!
+//! Parses the controller's USB input reports, which can be read with any HID
+//! library. Bluetooth reports use a different layout and aren't supported.
+
+/// Identifies a source of input data
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    /// Position of the first touch on the touchpad, from `(0, 0)` at the top
+    /// left to `(1, 1)` at the bottom right
+    TouchPosition,
+    /// Whether the touchpad is being touched
+    Touching,
+    /// Whether the touchpad is clicked
+    TouchpadHeld,
+    TouchpadPressed,
+    /// Whether the microphone mute button is held
+    MicHeld,
+    MicPressed,
+    /// Angular velocity in degrees per second, as (pitch, yaw, roll)
+    ///
+    /// Uncalibrated, so expect a small bias at rest.
+    Gyro,
+}
+
+impl enact::Input for Input {
+    const NAME: &'static str = "dualsense";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::TouchPosition => V::visit::<mint::Vector2<f64>>(),
+            Input::Touching | Input::TouchpadHeld | Input::MicHeld => V::visit::<bool>(),
+            Input::TouchpadPressed | Input::MicPressed => V::visit::<()>(),
+            Input::Gyro => V::visit::<mint::Vector3<f64>>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        match &*s.trim().to_ascii_lowercase() {
+            "touch" => vec![Input::TouchPosition, Input::Touching],
+            "touchpad" => vec![Input::TouchpadHeld, Input::TouchpadPressed],
+            "mic" => vec![Input::MicHeld, Input::MicPressed],
+            "gyro" => vec![Input::Gyro],
+            _ => vec![],
+        }
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            Input::TouchPosition | Input::Touching => "touch",
+            Input::TouchpadHeld | Input::TouchpadPressed => "touchpad",
+            Input::MicHeld | Input::MicPressed => "mic",
+            Input::Gyro => "gyro",
+        }
+        .to_owned()
+    }
+
+    fn names() -> Vec<String> {
+        ["touch", "touchpad", "mic", "gyro"]
+            .map(str::to_owned)
+            .into()
+    }
+
+    fn device_family(&self) -> enact::DeviceFamily {
+        enact::DeviceFamily::PlayStation
+    }
+
+    /// `pad/playstation/` followed by the input's name, e.g.
+    /// `pad/playstation/touchpad`
+    fn glyph(&self) -> Option<String> {
+        enact::DeviceFamily::PlayStation.glyph(&enact::Input::to_string(self))
+    }
+}
+
+/// ID of USB input reports
+pub const INPUT_REPORT_ID: u8 = 0x01;
+/// ID of USB output reports
+pub const OUTPUT_REPORT_ID: u8 = 0x02;
+
+const TOUCHPAD_WIDTH: f64 = 1920.0;
+const TOUCHPAD_HEIGHT: f64 = 1080.0;
+/// Nominal gyro units per degree per second, for a range of ±2000°/s
+const GYRO_UNITS_PER_DEGREE: f64 = 32768.0 / 2000.0;
+
+/// Translates DualSense input reports into inputs
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    /// State from the previous report, as [touching, touchpad, mic]
+    last: Option<[bool; 3]>,
+}
+
+impl Handler {
+    /// Create a handler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update action states in `seat` to account for a USB input report,
+    /// including the leading report ID, according to `bindings`
+    ///
+    /// Reports with other IDs, or which are too short, are ignored.
+    pub fn handle_report(
+        &mut self,
+        report: &[u8],
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        if report.len() < 41 || report[0] != INPUT_REPORT_ID {
+            return;
+        }
+        let touchpad = report[10] & 0x02 != 0;
+        let mic = report[10] & 0x04 != 0;
+        let touch = &report[33..37];
+        // The high bit is set when not touching
+        let touching = touch[0] & 0x80 == 0;
+
+        let last = self.last.replace([touching, touchpad, mic]);
+        let changed = |i: usize, now: bool| last.is_none_or(|last| last[i] != now);
+        if changed(0, touching) {
+            bindings.handle(&Input::Touching, touching, seat).unwrap();
+        }
+        for (i, held, pressed, now) in [
+            (1, Input::TouchpadHeld, Input::TouchpadPressed, touchpad),
+            (2, Input::MicHeld, Input::MicPressed, mic),
+        ] {
+            if changed(i, now) {
+                bindings.handle(&held, now, seat).unwrap();
+                if now {
+                    bindings.handle(&pressed, (), seat).unwrap();
+                }
+            }
+        }
+        if touching {
+            let x = u16::from(touch[1]) | (u16::from(touch[2] & 0x0f) << 8);
+            let y = u16::from(touch[2] >> 4) | (u16::from(touch[3]) << 4);
+            bindings
+                .handle(
+                    &Input::TouchPosition,
+                    mint::Vector2 {
+                        x: f64::from(x) / (TOUCHPAD_WIDTH - 1.0),
+                        y: f64::from(y) / (TOUCHPAD_HEIGHT - 1.0),
+                    },
+                    seat,
+                )
+                .unwrap();
+        }
+        let gyro = |i: usize| {
+            f64::from(i16::from_le_bytes([report[16 + 2 * i], report[17 + 2 * i]]))
+                / GYRO_UNITS_PER_DEGREE
+        };
+        bindings
+            .handle(
+                &Input::Gyro,
+                mint::Vector3 {
+                    x: gyro(0),
+                    y: gyro(1),
+                    z: gyro(2),
+                },
+                seat,
+            )
+            .unwrap();
+    }
+}
+
+/// Resistance applied by an adaptive trigger
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TriggerEffect {
+    /// No resistance
+    Off,
+    /// Constant resistance of `strength` beyond `start`
+    Continuous { start: u8, strength: u8 },
+    /// Resistance of `strength` between `start` and `end`, like a trigger's
+    /// break point
+    Section { start: u8, end: u8, strength: u8 },
+}
+
+impl TriggerEffect {
+    /// Mode byte and parameters, as in an output report
+    fn encode(self) -> [u8; 11] {
+        let mut out = [0; 11];
+        match self {
+            TriggerEffect::Off => out[0] = 0x05,
+            TriggerEffect::Continuous { start, strength } => {
+                out[..3].copy_from_slice(&[0x01, start, strength]);
+            }
+            TriggerEffect::Section {
+                start,
+                end,
+                strength,
+            } => {
+                out[..4].copy_from_slice(&[0x02, start, end, strength]);
+            }
+        }
+        out
+    }
+}
+
+/// Build a USB output report setting the adaptive trigger effects
+///
+/// Write the result to the device with a HID library. Other controller
+/// features, such as rumble and lights, are left unchanged.
+pub fn trigger_report(left: TriggerEffect, right: TriggerEffect) -> [u8; 48] {
+    let mut report = [0; 48];
+    report[0] = OUTPUT_REPORT_ID;
+    // Valid flags: right and left trigger effects
+    report[1] = 0x04 | 0x08;
+    report[11..22].copy_from_slice(&right.encode());
+    report[22..33].copy_from_slice(&left.encode());
+    report
+}
@@ -0,0 +1,255 @@
+//! Joysticks with many axes, buttons, and hat switches, such as HOTAS
+//! setups for flight and space sims
+//!
+//! Inputs are written `js [DEVICE] axis N`, `js [DEVICE] button N`, `js
+//! [DEVICE] hat N`, or `js [DEVICE] hat N DIRECTION`, e.g. `"js throttle axis
+//! 3"` or `"js hat 1 up"`, where `DEVICE` is an optional single-word label
+//! distinguishing multiple devices, and `N` is the control's index as reported
+//! by the device.
+
+use rustc_hash::FxHashMap;
+
+use crate::{hat_direction, normalize};
+
+/// Identifies a source of input data
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Input {
+    /// Label of the device, if any
+    pub device: Option<String>,
+    pub control: Control,
+}
+
+/// A control on a joystick
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Control {
+    /// Position, normalized to `0..=1` or `-1..=1` according to the axis's
+    /// range
+    Axis(u16),
+    ButtonHeld(u16),
+    ButtonPressed(u16),
+    /// Direction of a hat switch, with `y` pointing up
+    Hat(u8),
+    /// Whether a hat switch is pushed towards a direction, including
+    /// diagonals adjacent to it, as for a D-pad
+    HatHeld(u8, HatDirection),
+    HatPressed(u8, HatDirection),
+}
+
+/// A direction a hat switch can be pushed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HatDirection {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl HatDirection {
+    const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    fn name(self) -> &'static str {
+        match self {
+            HatDirection::Up => "up",
+            HatDirection::Right => "right",
+            HatDirection::Down => "down",
+            HatDirection::Left => "left",
+        }
+    }
+
+    /// Whether hat position `n`, clockwise from up, includes this direction
+    fn contains(self, n: u8) -> bool {
+        let center = self as u8 * 2;
+        n < 8 && (n == center || (n + 1) % 8 == center || n == (center + 1) % 8)
+    }
+}
+
+impl enact::Input for Input {
+    const NAME: &'static str = "joystick";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match self.control {
+            Control::Axis(_) => V::visit::<f64>(),
+            Control::ButtonHeld(_) | Control::HatHeld(..) => V::visit::<bool>(),
+            Control::ButtonPressed(_) | Control::HatPressed(..) => V::visit::<()>(),
+            Control::Hat(_) => V::visit::<mint::Vector2<f64>>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        let words = s.split_whitespace().collect::<Vec<_>>();
+        let Some((&"js", mut rest)) = words.split_first() else {
+            return vec![];
+        };
+        let mut device = None;
+        if let Some((&first, tail)) = rest.split_first()
+            && !["axis", "button", "hat"].contains(&first)
+        {
+            device = Some(first.to_owned());
+            rest = tail;
+        }
+        let controls = match *rest {
+            ["axis", n] => n.parse().map(|n| vec![Control::Axis(n)]),
+            ["button", n] => n
+                .parse()
+                .map(|n| vec![Control::ButtonHeld(n), Control::ButtonPressed(n)]),
+            ["hat", n] => n.parse().map(|n| vec![Control::Hat(n)]),
+            ["hat", n, dir] => {
+                let Some(dir) = HatDirection::ALL.into_iter().find(|d| d.name() == dir) else {
+                    return vec![];
+                };
+                n.parse()
+                    .map(|n| vec![Control::HatHeld(n, dir), Control::HatPressed(n, dir)])
+            }
+            _ => return vec![],
+        };
+        controls
+            .unwrap_or_default()
+            .into_iter()
+            .map(|control| Input {
+                device: device.clone(),
+                control,
+            })
+            .collect()
+    }
+
+    fn to_string(&self) -> String {
+        let mut out = "js ".to_owned();
+        if let Some(ref device) = self.device {
+            out.push_str(device);
+            out.push(' ');
+        }
+        match self.control {
+            Control::Axis(n) => out.push_str(&format!("axis {n}")),
+            Control::ButtonHeld(n) | Control::ButtonPressed(n) => {
+                out.push_str(&format!("button {n}"))
+            }
+            Control::Hat(n) => out.push_str(&format!("hat {n}")),
+            Control::HatHeld(n, dir) | Control::HatPressed(n, dir) => {
+                out.push_str(&format!("hat {n} {}", dir.name()))
+            }
+        }
+        out
+    }
+}
+
+/// Translates a single joystick's state into inputs
+///
+/// Use one per physical device, each with a distinct label if more than one
+/// is connected.
+#[derive(Debug, Clone)]
+pub struct Joystick {
+    device: Option<String>,
+    /// Raw range of each axis, where known
+    ranges: FxHashMap<u16, (i32, i32)>,
+    buttons: FxHashMap<u16, bool>,
+    /// Position of each hat, clockwise from up, or 8 if centered
+    hats: FxHashMap<u8, u8>,
+}
+
+impl Joystick {
+    /// Axis range assumed by default
+    pub const DEFAULT_RANGE: (i32, i32) = (-32768, 32767);
+
+    /// Create a handler for a device with an optional single-word `label`,
+    /// e.g. from [`enact::Bindings::device_alias`]
+    pub fn new(label: Option<&str>) -> Self {
+        Self {
+            device: label.map(str::to_owned),
+            ranges: FxHashMap::default(),
+            buttons: FxHashMap::default(),
+            hats: FxHashMap::default(),
+        }
+    }
+
+    /// Set the raw range of axis `n`, e.g. from a HID report descriptor
+    ///
+    /// Ranges including negative values are normalized to `-1..=1`, and
+    /// others, like most throttles, to `0..=1`.
+    pub fn set_axis_range(&mut self, n: u16, min: i32, max: i32) {
+        self.ranges.insert(n, (min, max));
+    }
+
+    fn input(&self, control: Control) -> Input {
+        Input {
+            device: self.device.clone(),
+            control,
+        }
+    }
+
+    /// Report the raw position of axis `n`
+    pub fn handle_axis(
+        &mut self,
+        n: u16,
+        value: i32,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        let (min, max) = self.ranges.get(&n).copied().unwrap_or(Self::DEFAULT_RANGE);
+        bindings
+            .handle(
+                &self.input(Control::Axis(n)),
+                normalize(value, min, max),
+                seat,
+            )
+            .unwrap();
+    }
+
+    /// Report whether button `n` is held
+    pub fn handle_button(
+        &mut self,
+        n: u16,
+        held: bool,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        if self.buttons.insert(n, held) == Some(held) {
+            return;
+        }
+        bindings
+            .handle(&self.input(Control::ButtonHeld(n)), held, seat)
+            .unwrap();
+        if held {
+            bindings
+                .handle(&self.input(Control::ButtonPressed(n)), (), seat)
+                .unwrap();
+        }
+    }
+
+    /// Report the position of hat `n`, as one of eight directions clockwise
+    /// from up, or `None` if centered
+    pub fn handle_hat(
+        &mut self,
+        n: u8,
+        position: Option<u8>,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        let position = position.filter(|&p| p < 8).unwrap_or(8);
+        let old = self.hats.insert(n, position);
+        if old == Some(position) {
+            return;
+        }
+        bindings
+            .handle(
+                &self.input(Control::Hat(n)),
+                hat_direction(i32::from(position)),
+                seat,
+            )
+            .unwrap();
+        for dir in HatDirection::ALL {
+            let held = dir.contains(position);
+            if old.is_some_and(|old| dir.contains(old) == held) {
+                continue;
+            }
+            bindings
+                .handle(&self.input(Control::HatHeld(n, dir)), held, seat)
+                .unwrap();
+            if held {
+                bindings
+                    .handle(&self.input(Control::HatPressed(n, dir)), (), seat)
+                    .unwrap();
+            }
+        }
+    }
+}
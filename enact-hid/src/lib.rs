@@ -0,0 +1,232 @@
+//! Arbitrary HID usages as inputs, for sim hardware and button boxes that no
+//! gamepad library recognizes
+//!
+//! This crate doesn't talk to devices itself. Read reports with a HID library
+//! such as `hidapi`, decode them according to the device's report
+//! descriptor, and pass each field's value to [`Handler::handle`]. Devices
+//! with features that don't map cleanly onto usages are handled by
+//! device-specific modules like [`dualsense`] and [`streamdeck`], and
+//! [`joystick`] offers friendlier names for flight sticks and the like.
+
+use rustc_hash::FxHashMap;
+
+pub mod dualsense;
+pub mod joystick;
+pub mod streamdeck;
+
+/// A HID usage, identifying the meaning of a control
+///
+/// Written as `button N` for the button page, by name for common generic
+/// desktop controls (e.g. `x`, `rz`, `hat`), and otherwise as `usage
+/// PAGE:ID` in hexadecimal, e.g. `usage 0x0002:0x00c4`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Usage {
+    pub page: u16,
+    pub id: u16,
+}
+
+impl Usage {
+    /// Generic desktop page
+    pub const GENERIC_DESKTOP: u16 = 0x01;
+    /// Button page, where the usage ID is the button number
+    pub const BUTTON: u16 = 0x09;
+    /// Hat switch in the generic desktop page
+    pub const HAT_SWITCH: Usage = Usage {
+        page: Self::GENERIC_DESKTOP,
+        id: 0x39,
+    };
+
+    /// Identify usage `id` within usage page `page`
+    pub const fn new(page: u16, id: u16) -> Self {
+        Self { page, id }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if let Some(n) = s.strip_prefix("button ") {
+            return Some(Usage::new(Self::BUTTON, n.trim().parse().ok()?));
+        }
+        if let Some(&(_, id)) = GENERIC_DESKTOP_NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        {
+            return Some(Usage::new(Self::GENERIC_DESKTOP, id));
+        }
+        let (page, id) = s.strip_prefix("usage ")?.split_once(':')?;
+        Some(Usage::new(parse_hex(page)?, parse_hex(id)?))
+    }
+
+    fn format(self) -> String {
+        if self.page == Self::BUTTON {
+            return format!("button {}", self.id);
+        }
+        if self.page == Self::GENERIC_DESKTOP
+            && let Some(&(name, _)) = GENERIC_DESKTOP_NAMES.iter().find(|&&(_, id)| id == self.id)
+        {
+            return name.to_owned();
+        }
+        format!("usage {:#06x}:{:#06x}", self.page, self.id)
+    }
+}
+
+fn parse_hex(s: &str) -> Option<u16> {
+    u16::from_str_radix(s.trim().strip_prefix("0x")?, 16).ok()
+}
+
+const GENERIC_DESKTOP_NAMES: [(&str, u16); 10] = [
+    ("x", 0x30),
+    ("y", 0x31),
+    ("z", 0x32),
+    ("rx", 0x33),
+    ("ry", 0x34),
+    ("rz", 0x35),
+    ("slider", 0x36),
+    ("dial", 0x37),
+    ("wheel", 0x38),
+    ("hat", 0x39),
+];
+
+/// Identifies a source of input data
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    /// Whether a control's value is nonzero
+    Held(Usage),
+    /// A control's value became nonzero
+    Pressed(Usage),
+    /// A control's value, scaled from its logical range to `0..=1`, or
+    /// `-1..=1` for ranges including negative values
+    Axis(Usage),
+    /// Direction of a hat switch, with `y` pointing up
+    Hat(Usage),
+}
+
+impl enact::Input for Input {
+    const NAME: &'static str = "hid";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::Held(_) => V::visit::<bool>(),
+            Input::Pressed(_) => V::visit::<()>(),
+            Input::Axis(_) => V::visit::<f64>(),
+            Input::Hat(_) => V::visit::<mint::Vector2<f64>>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        let Some(usage) = Usage::parse(s) else {
+            return vec![];
+        };
+        vec![
+            Input::Held(usage),
+            Input::Pressed(usage),
+            Input::Axis(usage),
+            Input::Hat(usage),
+        ]
+    }
+
+    fn to_string(&self) -> String {
+        let (Input::Held(usage) | Input::Pressed(usage) | Input::Axis(usage) | Input::Hat(usage)) =
+            *self;
+        usage.format()
+    }
+
+    fn names() -> Vec<String> {
+        GENERIC_DESKTOP_NAMES
+            .iter()
+            .map(|&(name, _)| name.to_owned())
+            .collect()
+    }
+}
+
+/// Translates decoded HID report fields into inputs
+///
+/// Remembers each control's value, so that only changes produce inputs.
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    values: FxHashMap<Usage, i32>,
+}
+
+impl Handler {
+    /// Create a handler with no remembered values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report that the control identified by `usage` has `value`, within the
+    /// logical range `min..=max` from the report descriptor
+    ///
+    /// Hat switches are interpreted as eight directions clockwise from up,
+    /// starting at `min`, with out-of-range values meaning centered.
+    pub fn handle(
+        &mut self,
+        usage: Usage,
+        value: i32,
+        min: i32,
+        max: i32,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        let old = self.values.insert(usage, value);
+        if old == Some(value) {
+            return;
+        }
+        let held = value != 0;
+        if old.is_none_or(|old| (old != 0) != held) {
+            bindings.handle(&Input::Held(usage), held, seat).unwrap();
+            if held {
+                bindings.handle(&Input::Pressed(usage), (), seat).unwrap();
+            }
+        }
+        bindings
+            .handle(&Input::Axis(usage), normalize(value, min, max), seat)
+            .unwrap();
+        if usage == Usage::HAT_SWITCH {
+            bindings
+                .handle(&Input::Hat(usage), hat_direction(value - min), seat)
+                .unwrap();
+        }
+    }
+
+    /// Forget all control values, e.g. when a device is disconnected
+    ///
+    /// Held controls are released.
+    pub fn reset(&mut self, bindings: &impl enact::HandleInput, seat: &mut enact::Seat) {
+        for (usage, value) in self.values.drain() {
+            if value != 0 {
+                bindings.handle(&Input::Held(usage), false, seat).unwrap();
+            }
+        }
+    }
+}
+
+/// Scale `value` from `min..=max` to `-1..=1` if `min` is negative, or
+/// `0..=1` otherwise
+fn normalize(value: i32, min: i32, max: i32) -> f64 {
+    // Computed in `f64`, which represents every `i32` and their differences,
+    // since devices may report extreme logical ranges
+    let (value, min, max) = (f64::from(value), f64::from(min), f64::from(max));
+    let x = if min < 0.0 {
+        value / max.max(-min).max(1.0)
+    } else {
+        (value - min) / (max - min).max(1.0)
+    };
+    x.clamp(-1.0, 1.0)
+}
+
+/// Unit vector for hat switch position `n`, clockwise from up
+fn hat_direction(n: i32) -> mint::Vector2<f64> {
+    const D: f64 = std::f64::consts::FRAC_1_SQRT_2;
+    let [x, y] = match n {
+        0 => [0.0, 1.0],
+        1 => [D, D],
+        2 => [1.0, 0.0],
+        3 => [D, -D],
+        4 => [0.0, -1.0],
+        5 => [-D, -D],
+        6 => [-1.0, 0.0],
+        7 => [-D, D],
+        _ => [0.0, 0.0],
+    };
+    mint::Vector2 { x, y }
+}
@@ -0,0 +1,211 @@
+//! Elgato Stream Deck keys, for macro pads as rebindable inputs
+//!
+//! Parses the input reports of current Stream Deck models, which can be read
+//! with any HID library, and builds the reports that set key images and
+//! brightness. To label each key with what it does, look up the actions bound
+//! to it with [`Bindings::bindings_for`](enact::Bindings::bindings_for) and
+//! write a corresponding image with [`image_reports`]. The original Stream
+//! Deck and the first Stream Deck Mini use an older protocol and aren't
+//! supported.
+
+/// Identifies a source of input data
+///
+/// Keys are numbered from 0, left to right and then top to bottom, and
+/// written as `key N`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    KeyHeld(u8),
+    KeyPressed(u8),
+}
+
+impl enact::Input for Input {
+    const NAME: &'static str = "streamdeck";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::KeyHeld(_) => V::visit::<bool>(),
+            Input::KeyPressed(_) => V::visit::<()>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        let Some(key) = s
+            .trim()
+            .strip_prefix("key ")
+            .and_then(|n| n.trim().parse().ok())
+        else {
+            return vec![];
+        };
+        vec![Input::KeyHeld(key), Input::KeyPressed(key)]
+    }
+
+    fn to_string(&self) -> String {
+        let (Input::KeyHeld(key) | Input::KeyPressed(key)) = *self;
+        format!("key {key}")
+    }
+}
+
+/// Elgato's USB vendor ID
+pub const VENDOR_ID: u16 = 0x0fd9;
+/// ID of input reports
+pub const INPUT_REPORT_ID: u8 = 0x01;
+/// ID of output reports
+pub const OUTPUT_REPORT_ID: u8 = 0x02;
+/// Length of output reports, including the report ID
+pub const OUTPUT_REPORT_LEN: usize = 1024;
+/// Length of an image output report's header, including the report ID
+const IMAGE_HEADER_LEN: usize = 8;
+
+/// A supported Stream Deck model
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Model {
+    pub name: &'static str,
+    pub product_id: u16,
+    pub keys: u8,
+    pub columns: u8,
+    /// Width and height of key images in pixels, or 0 for models without
+    /// displays
+    pub image_size: u16,
+}
+
+/// Every supported model
+pub const MODELS: [Model; 5] = [
+    Model {
+        name: "Stream Deck",
+        product_id: 0x006d,
+        keys: 15,
+        columns: 5,
+        image_size: 72,
+    },
+    Model {
+        name: "Stream Deck MK.2",
+        product_id: 0x0080,
+        keys: 15,
+        columns: 5,
+        image_size: 72,
+    },
+    Model {
+        name: "Stream Deck XL",
+        product_id: 0x006c,
+        keys: 32,
+        columns: 8,
+        image_size: 96,
+    },
+    Model {
+        name: "Stream Deck XL",
+        product_id: 0x008f,
+        keys: 32,
+        columns: 8,
+        image_size: 96,
+    },
+    Model {
+        name: "Stream Deck Pedal",
+        product_id: 0x0086,
+        keys: 3,
+        columns: 3,
+        image_size: 0,
+    },
+];
+
+impl Model {
+    /// Look up a model by its USB product ID
+    pub fn from_product_id(product_id: u16) -> Option<&'static Self> {
+        MODELS.iter().find(|x| x.product_id == product_id)
+    }
+}
+
+/// Translates Stream Deck input reports into inputs
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    /// Whether each key was held as of the previous report
+    held: Vec<bool>,
+}
+
+impl Handler {
+    /// Create a handler with every key released
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update action states in `seat` to account for an input report,
+    /// including the leading report ID, according to `bindings`
+    ///
+    /// Reports with other IDs, or which are too short, are ignored.
+    pub fn handle_report(
+        &mut self,
+        report: &[u8],
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        if report.len() < 4 || report[0] != INPUT_REPORT_ID {
+            return;
+        }
+        let count = usize::from(u16::from_le_bytes([report[2], report[3]]));
+        let Some(keys) = report[4..].get(..count) else {
+            return;
+        };
+        if self.held.len() < count {
+            self.held.resize(count, false);
+        }
+        for (key, (&state, held)) in (0..=u8::MAX).zip(keys.iter().zip(&mut self.held)) {
+            let now = state != 0;
+            if now == *held {
+                continue;
+            }
+            *held = now;
+            bindings.handle(&Input::KeyHeld(key), now, seat).unwrap();
+            if now {
+                bindings.handle(&Input::KeyPressed(key), (), seat).unwrap();
+            }
+        }
+    }
+
+    /// Release every held key, e.g. when the device is disconnected
+    pub fn reset(&mut self, bindings: &impl enact::HandleInput, seat: &mut enact::Seat) {
+        for (key, held) in (0..=u8::MAX).zip(&mut self.held) {
+            if std::mem::take(held) {
+                bindings.handle(&Input::KeyHeld(key), false, seat).unwrap();
+            }
+        }
+    }
+}
+
+/// Build the output reports setting `key`'s image to `jpeg`
+///
+/// `jpeg` must be a square JPEG of the model's [`Model::image_size`], rotated
+/// by 180°. Write each report to the device in order with a HID library.
+pub fn image_reports(key: u8, jpeg: &[u8]) -> Vec<[u8; OUTPUT_REPORT_LEN]> {
+    const PAGE: usize = OUTPUT_REPORT_LEN - IMAGE_HEADER_LEN;
+    let pages = jpeg.len().div_ceil(PAGE).max(1);
+    (0..pages)
+        .map(|page| {
+            let chunk = &jpeg[page * PAGE..jpeg.len().min((page + 1) * PAGE)];
+            let len = (chunk.len() as u16).to_le_bytes();
+            let index = (page as u16).to_le_bytes();
+            let mut report = [0; OUTPUT_REPORT_LEN];
+            report[..IMAGE_HEADER_LEN].copy_from_slice(&[
+                OUTPUT_REPORT_ID,
+                // Set key image
+                0x07,
+                key,
+                u8::from(page + 1 == pages),
+                len[0],
+                len[1],
+                index[0],
+                index[1],
+            ]);
+            report[IMAGE_HEADER_LEN..][..chunk.len()].copy_from_slice(chunk);
+            report
+        })
+        .collect()
+}
+
+/// Build a feature report setting display brightness to `percent`
+///
+/// Send the result to the device as a feature report with a HID library.
+pub fn brightness_report(percent: u8) -> [u8; 32] {
+    let mut report = [0; 32];
+    report[..3].copy_from_slice(&[0x03, 0x08, percent.min(100)]);
+    report
+}
@@ -0,0 +1,266 @@
+//! An egui window for inspecting live input state
+//!
+//! Shows the latest value of every action, recent inputs, the filter graph,
+//! and how often each input source is producing events. Route inputs through
+//! [`Inspector::observe`] so that they can be recorded, then call
+//! [`Inspector::show`] once per frame:
+//!
+//! ```ignore
+//! enact_winit::handle(&event, &inspector.observe(&bindings), &mut seat);
+//! // ...
+//! inspector.show(ctx, &session, &bindings, &seat);
+//! ```
+
+use std::{cell::RefCell, collections::VecDeque, time::Duration};
+
+use enact::{Bindings, HandleInput, Input, Seat, Session, TypeError};
+
+/// Period over which event rates are measured
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Records inputs and displays live input state
+#[derive(Debug)]
+pub struct Inspector {
+    /// Whether the window is shown
+    pub open: bool,
+    /// Number of recent inputs to list
+    pub max_events: usize,
+    log: RefCell<Log>,
+}
+
+#[derive(Debug, Default)]
+struct Log {
+    /// Most recent inputs, oldest first
+    events: VecDeque<Event>,
+    /// Times of inputs from each source within the last [`RATE_WINDOW`],
+    /// oldest first
+    rates: Vec<(&'static str, VecDeque<Duration>)>,
+}
+
+#[derive(Debug)]
+struct Event {
+    /// [`Seat::now`] when the input was handled
+    at: Duration,
+    /// [`Input::NAME`] of the input's source
+    source: &'static str,
+    input: String,
+}
+
+impl Default for Inspector {
+    fn default() -> Self {
+        Self {
+            open: true,
+            max_events: 32,
+            log: RefCell::default(),
+        }
+    }
+}
+
+impl Inspector {
+    /// Create an inspector with an open window and no recorded inputs
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap `bindings` to record inputs passing through them
+    pub fn observe<'a, H: HandleInput>(&'a self, bindings: &'a H) -> Observe<'a, H> {
+        Observe {
+            inspector: self,
+            bindings,
+        }
+    }
+
+    /// Forget all recorded inputs
+    pub fn clear(&mut self) {
+        *self.log.get_mut() = Log::default();
+    }
+
+    fn record<I: Input>(&self, input: &I, at: Duration) {
+        let mut log = self.log.borrow_mut();
+        log.events.push_back(Event {
+            at,
+            source: I::NAME,
+            input: input.to_string(),
+        });
+        while log.events.len() > self.max_events {
+            log.events.pop_front();
+        }
+        let times = match log.rates.iter().position(|&(name, _)| name == I::NAME) {
+            Some(i) => &mut log.rates[i].1,
+            None => {
+                log.rates.push((I::NAME, VecDeque::new()));
+                &mut log.rates.last_mut().unwrap().1
+            }
+        };
+        times.push_back(at);
+        expire(times, at);
+    }
+
+    /// Show the inspector window, if [`open`](Self::open)
+    ///
+    /// `session`, `bindings`, and `seat` should be those the application is
+    /// using for input.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        session: &Session,
+        bindings: &Bindings,
+        seat: &Seat,
+    ) {
+        let mut open = self.open;
+        egui::Window::new("Input")
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| self.ui(ui, session, bindings, seat));
+        self.open = open;
+    }
+
+    /// Draw the inspector's contents into `ui`, e.g. to embed it in a panel
+    pub fn ui(&self, ui: &mut egui::Ui, session: &Session, bindings: &Bindings, seat: &Seat) {
+        let now = seat.now();
+        egui::CollapsingHeader::new("Actions")
+            .default_open(true)
+            .show(ui, |ui| actions(ui, session, seat));
+        egui::CollapsingHeader::new("Recent inputs")
+            .default_open(true)
+            .show(ui, |ui| self.events(ui, now));
+        egui::CollapsingHeader::new("Sources")
+            .default_open(true)
+            .show(ui, |ui| self.sources(ui, bindings, seat, now));
+        egui::CollapsingHeader::new("Filters").show(ui, |ui| filters(ui, session, bindings));
+    }
+
+    fn events(&self, ui: &mut egui::Ui, now: Duration) {
+        let log = self.log.borrow();
+        if log.events.is_empty() {
+            ui.weak("none");
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .id_salt("enact-inspector-events")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                egui::Grid::new("enact-inspector-events")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for event in log.events.iter().rev() {
+                            let age = now.saturating_sub(event.at);
+                            ui.weak(format!("-{:.2}s", age.as_secs_f64()));
+                            ui.label(event.source);
+                            ui.monospace(&event.input);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn sources(&self, ui: &mut egui::Ui, bindings: &Bindings, seat: &Seat, now: Duration) {
+        if let Some(last) = seat.last_source() {
+            ui.label(format!("Last used: {} ({:?})", last.source, last.family));
+        }
+        let mut log = self.log.borrow_mut();
+        if log.rates.is_empty() {
+            ui.weak("no inputs yet");
+            return;
+        }
+        egui::Grid::new("enact-inspector-sources")
+            .striped(true)
+            .show(ui, |ui| {
+                for (source, times) in &mut log.rates {
+                    expire(times, now);
+                    let rate = times.len() as f64 / RATE_WINDOW.as_secs_f64();
+                    ui.label(*source);
+                    ui.monospace(format!("{rate:.0}/s"));
+                    if !bindings.is_source_enabled(source) {
+                        ui.weak("disabled");
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+/// Discard `times` older than [`RATE_WINDOW`] before `now`
+fn expire(times: &mut VecDeque<Duration>, now: Duration) {
+    while times.front().is_some_and(|&t| t + RATE_WINDOW < now) {
+        times.pop_front();
+    }
+}
+
+fn actions(ui: &mut egui::Ui, session: &Session, seat: &Seat) {
+    let snapshot = seat.debug_snapshot(session);
+    if snapshot.is_empty() {
+        ui.weak("no action state");
+        return;
+    }
+    egui::Grid::new("enact-inspector-actions")
+        .striped(true)
+        .show(ui, |ui| {
+            for action in snapshot {
+                ui.label(&action.name).on_hover_text(action.type_name);
+                match action.value {
+                    Some(ref value) => ui.monospace(value),
+                    None => ui.weak("?"),
+                };
+                if action.queued > 0 {
+                    ui.weak(format!("{} queued", action.queued));
+                }
+                ui.end_row();
+            }
+        });
+}
+
+fn filters(ui: &mut egui::Ui, session: &Session, bindings: &Bindings) {
+    let names = |actions: Vec<enact::ActionId>| {
+        actions
+            .into_iter()
+            .filter(|&a| session.contains(a))
+            .map(|a| session.action_name(a))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut any = false;
+    for filter in bindings.filters() {
+        any = true;
+        ui.horizontal_wrapped(|ui| {
+            ui.strong(bindings.filter_ty(filter));
+            ui.monospace(names(bindings.filter_sources(filter)));
+            ui.label("→");
+            ui.monospace(names(bindings.filter_targets(filter)));
+        });
+    }
+    if !any {
+        ui.weak("none");
+    }
+}
+
+/// [`HandleInput`] implementation that records inputs in an [`Inspector`]
+///
+/// Returned by [`Inspector::observe`].
+pub struct Observe<'a, H> {
+    inspector: &'a Inspector,
+    bindings: &'a H,
+}
+
+impl<H: HandleInput> HandleInput for Observe<'_, H> {
+    fn handle<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+    ) -> Result<(), TypeError> {
+        self.inspector.record(input, seat.now());
+        self.bindings.handle(input, data, seat)
+    }
+
+    fn handle_consumed<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+        consumed: bool,
+    ) -> Result<(), TypeError> {
+        self.inspector.record(input, seat.now());
+        self.bindings.handle_consumed(input, data, seat, consumed)
+    }
+}
@@ -0,0 +1,300 @@
+//! Inputs received over the network, for companion apps that turn a phone
+//! into a controller, and for remote test automation
+//!
+//! Clients send [`RecordedInput`]s encoded as JSON, naming inputs the same way
+//! as configs do, e.g.
+//! `{"source":"winit","input":"space","value":{"Bool":true}}`. Rust clients
+//! can produce messages with [`encode`]. A [`Server`] accepts clients over TCP,
+//! with one message per line, or with the `websocket` feature over WebSocket,
+//! with one message per text frame. Call [`Server::poll`] regularly, e.g. once
+//! per frame, to pass received inputs to [`Bindings`].
+//!
+//! Anyone who can connect can control the application, so servers should
+//! only listen on trusted networks, or on the loopback interface with
+//! connections forwarded from an authenticated channel.
+
+use std::{
+    fmt,
+    io::{self, BufRead, Read},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use enact::{Bindings, Input, InputValue, RecordedInput, Seat};
+
+/// Encode `input` producing `data` as a message, if `data` is of a type
+/// covered by [`InputValue`]
+pub fn encode<I: Input, T: 'static>(input: &I, data: &T) -> Option<String> {
+    Some(serde_json::to_string(&RecordedInput::new(input, data)?).unwrap())
+}
+
+/// Accepts clients and passes the inputs they send to [`Bindings`]
+pub struct Server {
+    listener: TcpListener,
+    #[cfg(feature = "websocket")]
+    websocket: bool,
+    clients: Vec<Client>,
+}
+
+struct Client {
+    addr: SocketAddr,
+    connection: Connection,
+    /// `bool` inputs last sent as `true`, to release on disconnect
+    held: Vec<RecordedInput>,
+}
+
+#[cfg(feature = "websocket")]
+type MidHandshake = tungstenite::handshake::MidHandshake<
+    tungstenite::ServerHandshake<TcpStream, tungstenite::handshake::server::NoCallback>,
+>;
+
+enum Connection {
+    Tcp {
+        reader: io::BufReader<TcpStream>,
+        line: Vec<u8>,
+    },
+    /// `None` only while the handshake is being resumed
+    #[cfg(feature = "websocket")]
+    Handshake(Option<MidHandshake>),
+    #[cfg(feature = "websocket")]
+    WebSocket(tungstenite::WebSocket<TcpStream>),
+}
+
+impl Server {
+    /// Accept clients sending newline-separated messages over TCP at `addr`
+    ///
+    /// Clients sending a line longer than 64 KiB are disconnected.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            #[cfg(feature = "websocket")]
+            websocket: false,
+            clients: Vec::new(),
+        })
+    }
+
+    /// Accept WebSocket clients at `addr`, e.g. browsers
+    #[cfg(feature = "websocket")]
+    pub fn bind_websocket(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let mut server = Self::bind(addr)?;
+        server.websocket = true;
+        Ok(server)
+    }
+
+    /// The address being listened on, e.g. to find the port chosen when
+    /// binding to port 0
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Addresses of connected clients
+    pub fn clients(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        self.clients.iter().map(|client| client.addr)
+    }
+
+    /// Accept new clients, and pass every input received since the last call
+    /// to `bindings`
+    ///
+    /// Never blocks. Inputs a disconnected client left held are released.
+    /// Messages which can't be handled are skipped and reported.
+    pub fn poll(&mut self, bindings: &Bindings, seat: &mut Seat) -> Vec<Error> {
+        self.accept();
+        let mut errors = Vec::new();
+        let mut messages = Vec::new();
+        self.clients.retain_mut(|client| {
+            let open = client.connection.receive(&mut messages);
+            for message in messages.drain(..) {
+                // Blank lines may be sent to keep connections alive
+                if message.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<RecordedInput>(&message) {
+                    Ok(input) => client.handle(input, bindings, seat, &mut errors),
+                    Err(e) => errors.push(Error::Malformed {
+                        client: client.addr,
+                        reason: e.to_string(),
+                    }),
+                }
+            }
+            if !open {
+                client.release(bindings, seat);
+            }
+            open
+        });
+        errors
+    }
+
+    /// Disconnect every client, releasing inputs they left held
+    pub fn disconnect_all(&mut self, bindings: &Bindings, seat: &mut Seat) {
+        for mut client in self.clients.drain(..) {
+            client.release(bindings, seat);
+        }
+    }
+
+    fn accept(&mut self) {
+        // Other errors, e.g. from running out of file descriptors, are
+        // transient
+        while let Ok((stream, addr)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_err() {
+                continue;
+            }
+            #[cfg(feature = "websocket")]
+            if self.websocket {
+                let connection = match tungstenite::accept(stream) {
+                    Ok(ws) => Connection::WebSocket(ws),
+                    Err(tungstenite::HandshakeError::Interrupted(mid)) => {
+                        Connection::Handshake(Some(mid))
+                    }
+                    Err(tungstenite::HandshakeError::Failure(_)) => continue,
+                };
+                self.clients.push(Client::new(addr, connection));
+                continue;
+            }
+            let connection = Connection::Tcp {
+                reader: io::BufReader::new(stream),
+                line: Vec::new(),
+            };
+            self.clients.push(Client::new(addr, connection));
+        }
+    }
+}
+
+impl Client {
+    fn new(addr: SocketAddr, connection: Connection) -> Self {
+        Self {
+            addr,
+            connection,
+            held: Vec::new(),
+        }
+    }
+
+    fn handle(
+        &mut self,
+        input: RecordedInput,
+        bindings: &Bindings,
+        seat: &mut Seat,
+        errors: &mut Vec<Error>,
+    ) {
+        if !input.handle(bindings, seat) {
+            errors.push(Error::Unrecognized {
+                client: self.addr,
+                input,
+            });
+            return;
+        }
+        let InputValue::Bool(held) = input.value else {
+            return;
+        };
+        self.held
+            .retain(|x| x.source != input.source || x.input != input.input);
+        if held {
+            self.held.push(input);
+        }
+    }
+
+    fn release(&mut self, bindings: &Bindings, seat: &mut Seat) {
+        for mut input in self.held.drain(..) {
+            input.value = InputValue::Bool(false);
+            input.handle(bindings, seat);
+        }
+    }
+}
+
+/// Longest line, in bytes, a TCP client may send before being disconnected
+const MAX_LINE_LEN: usize = 64 * 1024;
+
+impl Connection {
+    /// Read every complete message received into `out`
+    ///
+    /// Returns whether the connection remains open.
+    fn receive(&mut self, out: &mut Vec<String>) -> bool {
+        match *self {
+            Connection::Tcp {
+                ref mut reader,
+                ref mut line,
+            } => loop {
+                // Partial lines are kept as bytes until the rest arrives, since
+                // a read may end mid-character
+                let limit = (MAX_LINE_LEN + 1 - line.len()) as u64;
+                match reader.by_ref().take(limit).read_until(b'\n', line) {
+                    Ok(0) => return false,
+                    Ok(_) if line.last() != Some(&b'\n') => {
+                        if line.len() > MAX_LINE_LEN {
+                            return false;
+                        }
+                    }
+                    Ok(_) => {
+                        out.push(String::from_utf8_lossy(line).into_owned());
+                        line.clear();
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => return true,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(_) => return false,
+                }
+            },
+            #[cfg(feature = "websocket")]
+            Connection::Handshake(ref mut mid) => {
+                match mid.take().unwrap().handshake() {
+                    Ok(ws) => *self = Connection::WebSocket(ws),
+                    Err(tungstenite::HandshakeError::Interrupted(next)) => {
+                        *mid = Some(next);
+                        return true;
+                    }
+                    Err(tungstenite::HandshakeError::Failure(_)) => return false,
+                }
+                self.receive(out)
+            }
+            #[cfg(feature = "websocket")]
+            Connection::WebSocket(ref mut ws) => loop {
+                match ws.read() {
+                    Ok(tungstenite::Message::Text(text)) => out.push(text.as_str().to_owned()),
+                    Ok(tungstenite::Message::Close(_)) => return false,
+                    // Pings are answered automatically
+                    Ok(_) => {}
+                    Err(tungstenite::Error::Io(ref e)) if e.kind() == io::ErrorKind::WouldBlock => {
+                        // Send any queued pongs
+                        return match ws.flush() {
+                            Ok(()) => true,
+                            Err(tungstenite::Error::Io(ref e)) => {
+                                e.kind() == io::ErrorKind::WouldBlock
+                            }
+                            Err(_) => false,
+                        };
+                    }
+                    Err(_) => return false,
+                }
+            },
+        }
+    }
+}
+
+/// A message that couldn't be handled, from [`Server::poll`]
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// The message isn't a valid [`RecordedInput`]
+    Malformed { client: SocketAddr, reason: String },
+    /// No input from the source is bound, or the input isn't valid or
+    /// doesn't produce the given type of value
+    Unrecognized {
+        client: SocketAddr,
+        input: RecordedInput,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::Malformed { client, ref reason } => {
+                write!(f, "malformed message from {client}: {reason}")
+            }
+            Error::Unrecognized { client, ref input } => write!(
+                f,
+                "unrecognized input {:?} from {:?} sent by {client}",
+                input.input, input.source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
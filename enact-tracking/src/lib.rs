@@ -0,0 +1,206 @@
+//! Eye gaze and head pose as inputs, for head-look in sims and for
+//! accessibility
+//!
+//! Poses come from a [`Tracker`]. [`OpenTrack`] receives them from OpenTrack's
+//! "UDP over network" output, which in turn supports most webcam and IR head
+//! trackers; implement the trait to use another source, such as an eye
+//! tracker's SDK. Call [`Handler::poll`] regularly, e.g. once per frame, then
+//! shape the inputs with the usual filters.
+
+use std::{io, net::UdpSocket};
+
+/// Identifies a source of input data
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    /// Point on the screen being looked at, written as `gaze`
+    ///
+    /// Normalized so that the screen's center is the origin and its edges are
+    /// at ±1, with `y` pointing up.
+    Gaze,
+    /// Head position in meters relative to the tracker's center, written as
+    /// `head position`
+    ///
+    /// `x` points right, `y` up, and `z` back, towards the user.
+    HeadPosition,
+    /// Head orientation in radians, written as `head rotation`
+    ///
+    /// `x` is yaw, positive turning left; `y` is pitch, positive looking up;
+    /// and `z` is roll, positive tilting left.
+    HeadRotation,
+    /// Whether the tracker currently sees the user, written as `tracking`
+    Tracking,
+}
+
+const NAMES: [(&str, Input); 4] = [
+    ("gaze", Input::Gaze),
+    ("head position", Input::HeadPosition),
+    ("head rotation", Input::HeadRotation),
+    ("tracking", Input::Tracking),
+];
+
+impl enact::Input for Input {
+    const NAME: &'static str = "tracking";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::Gaze => V::visit::<mint::Vector2<f64>>(),
+            Input::HeadPosition | Input::HeadRotation => V::visit::<mint::Vector3<f64>>(),
+            Input::Tracking => V::visit::<bool>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        NAMES
+            .iter()
+            .filter(|&&(name, _)| name == s.trim())
+            .map(|&(_, input)| input)
+            .collect()
+    }
+
+    fn to_string(&self) -> String {
+        let (name, _) = NAMES.iter().find(|&&(_, x)| x == *self).unwrap();
+        (*name).to_owned()
+    }
+
+    fn names() -> Vec<String> {
+        NAMES.iter().map(|&(name, _)| name.to_owned()).collect()
+    }
+}
+
+/// The latest state reported by a [`Tracker`]
+///
+/// Units and axes are as described for each [`Input`]. Fields are `None`
+/// while the user isn't tracked, or if the tracker doesn't measure them.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Sample {
+    pub gaze: Option<mint::Vector2<f64>>,
+    pub head_position: Option<mint::Vector3<f64>>,
+    pub head_rotation: Option<mint::Vector3<f64>>,
+}
+
+/// A source of gaze or head pose data
+pub trait Tracker {
+    /// The newest sample since the last call, if any
+    ///
+    /// Must not block.
+    fn poll(&mut self) -> io::Result<Option<Sample>>;
+}
+
+/// Receives head poses from OpenTrack's "UDP over network" output
+///
+/// Each datagram holds six little-endian `f64`s: position in centimeters, then
+/// yaw, pitch, and roll in degrees, which are converted to the units of
+/// [`Input::HeadPosition`] and [`Input::HeadRotation`].
+pub struct OpenTrack {
+    socket: UdpSocket,
+}
+
+impl OpenTrack {
+    /// OpenTrack's default port
+    pub const PORT: u16 = 4242;
+
+    /// Listen on `port` on every interface
+    pub fn bind(port: u16) -> io::Result<Self> {
+        let socket = UdpSocket::bind(("0.0.0.0", port))?;
+        socket.set_nonblocking(true)?;
+        Ok(Self { socket })
+    }
+}
+
+impl Tracker for OpenTrack {
+    fn poll(&mut self) -> io::Result<Option<Sample>> {
+        let mut latest = None;
+        let mut buf = [0; 48];
+        loop {
+            match self.socket.recv(&mut buf) {
+                Ok(48) => {}
+                // Not from OpenTrack
+                Ok(_) => continue,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(latest),
+                Err(e) => return Err(e),
+            }
+            let [x, y, z, yaw, pitch, roll] = std::array::from_fn(|i| {
+                f64::from_le_bytes(buf[i * 8..(i + 1) * 8].try_into().unwrap())
+            });
+            latest = Some(Sample {
+                gaze: None,
+                head_position: Some([x / 100.0, y / 100.0, z / 100.0].into()),
+                head_rotation: Some(
+                    [-yaw.to_radians(), pitch.to_radians(), -roll.to_radians()].into(),
+                ),
+            });
+        }
+    }
+}
+
+/// Translates [`Sample`]s into inputs
+///
+/// Remembers the previous sample, so that only changes produce inputs.
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    last: Sample,
+    tracking: bool,
+}
+
+impl Handler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle the newest sample from `tracker`, if any
+    pub fn poll(
+        &mut self,
+        tracker: &mut impl Tracker,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) -> io::Result<()> {
+        if let Some(sample) = tracker.poll()? {
+            self.handle(&sample, bindings, seat);
+        }
+        Ok(())
+    }
+
+    /// Handle a single sample
+    ///
+    /// The user is considered tracked while any field is present.
+    pub fn handle(
+        &mut self,
+        sample: &Sample,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        if let Some(gaze) = sample.gaze.filter(|&x| Some(x) != self.last.gaze) {
+            bindings.handle(&Input::Gaze, gaze, seat).unwrap();
+        }
+        if let Some(position) = sample
+            .head_position
+            .filter(|&x| Some(x) != self.last.head_position)
+        {
+            bindings
+                .handle(&Input::HeadPosition, position, seat)
+                .unwrap();
+        }
+        if let Some(rotation) = sample
+            .head_rotation
+            .filter(|&x| Some(x) != self.last.head_rotation)
+        {
+            bindings
+                .handle(&Input::HeadRotation, rotation, seat)
+                .unwrap();
+        }
+        let tracking = *sample != Sample::default();
+        if tracking != self.tracking {
+            self.tracking = tracking;
+            bindings.handle(&Input::Tracking, tracking, seat).unwrap();
+        }
+        self.last = *sample;
+    }
+
+    /// Forget the previous sample, e.g. when the tracker is disconnected
+    ///
+    /// Tracking is lost.
+    pub fn reset(&mut self, bindings: &impl enact::HandleInput, seat: &mut enact::Seat) {
+        self.handle(&Sample::default(), bindings, seat);
+    }
+}
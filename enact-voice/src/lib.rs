@@ -0,0 +1,167 @@
+//! Spoken phrases as inputs, e.g. for accessibility
+//!
+//! Speech recognition is delegated to a [`Recognizer`]. Enable the `whisper`
+//! feature for an offline engine, `whisper::WhisperRecognizer`, or implement
+//! the trait for another. Engines which don't consume audio directly, such as
+//! platform speech APIs, can instead pass their [`Event`]s straight to
+//! [`Handler::handle`].
+//!
+//! Bindings name the phrases to listen for, e.g. `"say open map"`.
+
+#[cfg(feature = "whisper")]
+pub mod whisper;
+
+/// Identifies a source of input data
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    /// A phrase was recognized, written as `say <phrase>`
+    ///
+    /// Phrases are matched ignoring case and punctuation, anywhere within an
+    /// utterance.
+    Said(String),
+    /// Whether the user is speaking, written as `speaking`
+    Speaking,
+}
+
+impl enact::Input for Input {
+    const NAME: &'static str = "voice";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::Said(_) => V::visit::<()>(),
+            Input::Speaking => V::visit::<bool>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        let s = s.trim();
+        if s == "speaking" {
+            return vec![Input::Speaking];
+        }
+        s.strip_prefix("say ")
+            .map(normalize)
+            .filter(|x| !x.is_empty())
+            .map(Input::Said)
+            .into_iter()
+            .collect()
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            Input::Said(ref phrase) => format!("say {phrase}"),
+            Input::Speaking => "speaking".to_owned(),
+        }
+    }
+}
+
+/// `s` in lowercase, with punctuation removed and words separated by single
+/// spaces
+fn normalize(s: &str) -> String {
+    s.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Something reported by a [`Recognizer`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    SpeechStarted,
+    SpeechEnded,
+    /// Text recognized in an utterance
+    Transcript(String),
+}
+
+/// A speech recognition engine
+pub trait Recognizer {
+    /// Process audio, reporting anything recognized to `out`
+    ///
+    /// `samples` are mono, at the sample rate the engine expects.
+    fn feed(&mut self, samples: &[f32], out: &mut Vec<Event>);
+
+    /// Hint that only `phrases` are of interest, e.g. to restrict a grammar
+    /// or bias a model towards them
+    fn set_vocabulary(&mut self, phrases: &[String]) {
+        let _ = phrases;
+    }
+}
+
+/// Every phrase bound in `bindings`, for [`Recognizer::set_vocabulary`]
+pub fn phrases(bindings: &enact::Bindings) -> Vec<String> {
+    let mut out = bindings
+        .bindings_for_any::<Input>()
+        .into_iter()
+        .filter_map(|(input, _)| match input {
+            Input::Said(phrase) => Some(phrase),
+            Input::Speaking => None,
+        })
+        .collect::<Vec<_>>();
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// Translates recognizer events into inputs
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    speaking: bool,
+    events: Vec<Event>,
+}
+
+impl Handler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pass `samples` to `recognizer`, and handle the resulting events
+    pub fn feed(
+        &mut self,
+        recognizer: &mut impl Recognizer,
+        samples: &[f32],
+        bindings: &enact::Bindings,
+        seat: &mut enact::Seat,
+    ) {
+        let mut events = std::mem::take(&mut self.events);
+        recognizer.feed(samples, &mut events);
+        for event in events.drain(..) {
+            self.handle(&event, bindings, seat);
+        }
+        self.events = events;
+    }
+
+    /// Handle a single event
+    ///
+    /// Transcripts produce an input for every bound phrase they contain, in
+    /// order of appearance.
+    pub fn handle(&mut self, event: &Event, bindings: &enact::Bindings, seat: &mut enact::Seat) {
+        match *event {
+            Event::SpeechStarted | Event::SpeechEnded => {
+                let speaking = *event == Event::SpeechStarted;
+                if speaking != self.speaking {
+                    self.speaking = speaking;
+                    bindings.handle(&Input::Speaking, speaking, seat).unwrap();
+                }
+            }
+            Event::Transcript(ref text) => {
+                let text = format!(" {} ", normalize(text));
+                let mut found = phrases(bindings)
+                    .into_iter()
+                    .filter_map(|phrase| Some((text.find(&format!(" {phrase} "))?, phrase)))
+                    .collect::<Vec<_>>();
+                found.sort_unstable();
+                for (_, phrase) in found {
+                    bindings.handle(&Input::Said(phrase), (), seat).unwrap();
+                }
+            }
+        }
+    }
+
+    /// Stop listening, e.g. when the microphone is disconnected
+    ///
+    /// Ends any ongoing speech.
+    pub fn reset(&mut self, bindings: &enact::Bindings, seat: &mut enact::Seat) {
+        self.handle(&Event::SpeechEnded, bindings, seat);
+    }
+}
@@ -0,0 +1,118 @@
+//! Offline recognition with whisper.cpp
+
+use whisper_rs::{
+    FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperError,
+    WhisperState,
+};
+
+use crate::{Event, Recognizer};
+
+/// Sample rate expected by whisper models
+pub const SAMPLE_RATE: usize = 16_000;
+/// Samples per voice activity detection frame, 30ms
+const FRAME: usize = SAMPLE_RATE * 30 / 1000;
+/// Frames of silence ending an utterance, 600ms
+const SILENT_FRAMES: usize = 20;
+
+/// [`Recognizer`] running a whisper model locally
+///
+/// Expects 16kHz audio. Utterances are delimited by a simple energy threshold,
+/// and transcribed once they end, so phrases are recognized after a short
+/// pause.
+pub struct WhisperRecognizer {
+    state: WhisperState,
+    /// Root mean square amplitude above which audio is considered speech
+    threshold: f32,
+    /// Biases recognition towards bound phrases
+    prompt: String,
+    /// Samples not yet making up a full frame
+    pending: Vec<f32>,
+    /// Samples of the current utterance, if any
+    utterance: Option<Vec<f32>>,
+    /// Consecutive silent frames in the current utterance
+    silence: usize,
+}
+
+impl WhisperRecognizer {
+    /// Load the model at `path`, e.g. `ggml-base.en.bin`
+    pub fn new(path: &str) -> Result<Self, WhisperError> {
+        let context = WhisperContext::new_with_params(path, WhisperContextParameters::default())?;
+        Ok(Self {
+            state: context.create_state()?,
+            threshold: 0.02,
+            prompt: String::new(),
+            pending: Vec::new(),
+            utterance: None,
+            silence: 0,
+        })
+    }
+
+    /// Set the root mean square amplitude above which audio is considered
+    /// speech
+    ///
+    /// Defaults to 0.02. Raise for noisy environments.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold;
+    }
+
+    fn transcribe(&mut self, mut samples: Vec<f32>) -> Option<String> {
+        // whisper.cpp rejects less than a second of audio
+        if samples.len() < SAMPLE_RATE {
+            samples.resize(SAMPLE_RATE, 0.0);
+        }
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        params.set_single_segment(true);
+        params.set_no_context(true);
+        if !self.prompt.is_empty() {
+            params.set_initial_prompt(&self.prompt);
+        }
+        self.state.full(params, &samples).ok()?;
+        let segments = self.state.full_n_segments().ok()?;
+        let mut text = String::new();
+        for i in 0..segments {
+            text.push_str(&self.state.full_get_segment_text_lossy(i).ok()?);
+        }
+        Some(text)
+    }
+}
+
+impl Recognizer for WhisperRecognizer {
+    fn feed(&mut self, samples: &[f32], out: &mut Vec<Event>) {
+        self.pending.extend_from_slice(samples);
+        let frames = self.pending.len() / FRAME;
+        let pending = std::mem::take(&mut self.pending);
+        for frame in pending.chunks_exact(FRAME).take(frames) {
+            let rms = (frame.iter().map(|x| x * x).sum::<f32>() / FRAME as f32).sqrt();
+            let loud = rms > self.threshold;
+            match self.utterance {
+                None if loud => {
+                    self.utterance = Some(frame.to_vec());
+                    self.silence = 0;
+                    out.push(Event::SpeechStarted);
+                }
+                None => {}
+                Some(ref mut utterance) => {
+                    utterance.extend_from_slice(frame);
+                    self.silence = if loud { 0 } else { self.silence + 1 };
+                    if self.silence < SILENT_FRAMES {
+                        continue;
+                    }
+                    let utterance = self.utterance.take().unwrap();
+                    out.push(Event::SpeechEnded);
+                    if let Some(text) = self.transcribe(utterance) {
+                        out.push(Event::Transcript(text));
+                    }
+                }
+            }
+        }
+        self.pending = pending[frames * FRAME..].to_vec();
+    }
+
+    fn set_vocabulary(&mut self, phrases: &[String]) {
+        self.prompt = phrases.join(", ");
+    }
+}
@@ -0,0 +1,473 @@
+//! Keyboard and mouse input from DOM events, for wasm applications not using
+//! winit
+//!
+//! Inputs are written the same way as in `enact-winit`, and share its source
+//! name, so the same config can be used in native and browser builds.
+
+use std::{cell::RefCell, rc::Rc};
+
+use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use web_sys::{EventTarget, KeyboardEvent, MouseEvent};
+
+/// Identifies a source of input data
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[non_exhaustive]
+pub enum Input {
+    KeyHeld(Key),
+    MouseButtonHeld(MouseButton),
+    KeyPressed(Key),
+    MouseButtonPressed(MouseButton),
+    /// Mouse motion, reported while the pointer is locked if
+    /// [`Handler::require_pointer_lock`] is set
+    MouseMotion,
+    /// Key repeat events generated by the browser while a key is held, when
+    /// [`Handler::key_repeat`] is set
+    ///
+    /// Written as the key name followed by `repeat`, e.g. `"down repeat"`.
+    KeyRepeated(Key),
+}
+
+impl Input {
+    /// Look up the [`Input`]s produced by a DOM event
+    ///
+    /// Useful for building binding UIs. Call [`enact::Session::check_type`] to
+    /// filter out inputs which are inappropriate for a specific action.
+    pub fn from_event(event: &web_sys::Event) -> Vec<Self> {
+        match &*event.type_() {
+            "keydown" => {
+                let Some(event) = event.dyn_ref::<KeyboardEvent>() else {
+                    return vec![];
+                };
+                if event.repeat() {
+                    return vec![];
+                }
+                let key = Key(event.code());
+                vec![Input::KeyPressed(key.clone()), Input::KeyHeld(key)]
+            }
+            "mousedown" => {
+                let Some(event) = event.dyn_ref::<MouseEvent>() else {
+                    return vec![];
+                };
+                let button = MouseButton(event.button());
+                vec![
+                    Input::MouseButtonPressed(button),
+                    Input::MouseButtonHeld(button),
+                ]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+impl enact::Input for Input {
+    /// Matches `enact-winit`, so that configs are portable between them
+    const NAME: &'static str = "winit";
+
+    fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            Input::KeyHeld(_) | Input::MouseButtonHeld(_) => V::visit::<bool>(),
+            Input::KeyPressed(_) | Input::MouseButtonPressed(_) | Input::KeyRepeated(_) => {
+                V::visit::<()>()
+            }
+            Input::MouseMotion => V::visit::<mint::Vector2<f64>>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        if let Some(key) = s.strip_suffix(" repeat").and_then(Key::parse) {
+            return vec![Input::KeyRepeated(key)];
+        }
+        if let Some(key) = Key::parse(s) {
+            return vec![Input::KeyHeld(key.clone()), Input::KeyPressed(key)];
+        }
+        if let Some(button) = MouseButton::parse(s) {
+            return vec![
+                Input::MouseButtonHeld(button),
+                Input::MouseButtonPressed(button),
+            ];
+        }
+        if s.eq_ignore_ascii_case("mouse") {
+            return vec![Input::MouseMotion];
+        }
+        vec![]
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            Input::KeyHeld(ref k) | Input::KeyPressed(ref k) => k.format(),
+            Input::MouseButtonHeld(b) | Input::MouseButtonPressed(b) => b.format(),
+            Input::MouseMotion => "mouse".to_owned(),
+            Input::KeyRepeated(ref k) => format!("{} repeat", k.format()),
+        }
+    }
+
+    fn names() -> Vec<String> {
+        KEYS.iter()
+            .map(|&(name, _)| name)
+            .chain(MOUSE_BUTTONS.iter().map(|&(name, _)| name))
+            .chain(["mouse"])
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn device_family(&self) -> enact::DeviceFamily {
+        enact::DeviceFamily::KeyboardMouse
+    }
+
+    /// Matches `enact-winit`, e.g. `kbm/space` or `kbm/mouse-left`
+    ///
+    /// Keys without a portable name have no glyph.
+    fn glyph(&self) -> Option<String> {
+        let name = match *self {
+            Input::KeyHeld(ref k) | Input::KeyPressed(ref k) | Input::KeyRepeated(ref k) => {
+                k.name()?.to_owned()
+            }
+            Input::MouseButtonHeld(b) | Input::MouseButtonPressed(b) => b.format(),
+            Input::MouseMotion => "mouse".to_owned(),
+        };
+        enact::DeviceFamily::KeyboardMouse.glyph(&name)
+    }
+}
+
+/// A physical key, identified by the DOM `KeyboardEvent.code` it produces
+///
+/// Keys without a portable name are written as `<web CODE>`.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct Key(pub String);
+
+/// Portable key names, and the DOM codes they correspond to
+///
+/// Matches `enact-winit`, whose key codes are named after DOM codes.
+const KEYS: &[(&str, &str)] = &[
+    ("a", "KeyA"),
+    ("b", "KeyB"),
+    ("c", "KeyC"),
+    ("d", "KeyD"),
+    ("e", "KeyE"),
+    ("f", "KeyF"),
+    ("g", "KeyG"),
+    ("h", "KeyH"),
+    ("i", "KeyI"),
+    ("j", "KeyJ"),
+    ("k", "KeyK"),
+    ("l", "KeyL"),
+    ("m", "KeyM"),
+    ("n", "KeyN"),
+    ("o", "KeyO"),
+    ("p", "KeyP"),
+    ("q", "KeyQ"),
+    ("r", "KeyR"),
+    ("s", "KeyS"),
+    ("t", "KeyT"),
+    ("u", "KeyU"),
+    ("v", "KeyV"),
+    ("w", "KeyW"),
+    ("x", "KeyX"),
+    ("y", "KeyY"),
+    ("z", "KeyZ"),
+    ("0", "Digit0"),
+    ("1", "Digit1"),
+    ("2", "Digit2"),
+    ("3", "Digit3"),
+    ("4", "Digit4"),
+    ("5", "Digit5"),
+    ("6", "Digit6"),
+    ("7", "Digit7"),
+    ("8", "Digit8"),
+    ("9", "Digit9"),
+    ("f1", "F1"),
+    ("f2", "F2"),
+    ("f3", "F3"),
+    ("f4", "F4"),
+    ("f5", "F5"),
+    ("f6", "F6"),
+    ("f7", "F7"),
+    ("f8", "F8"),
+    ("f9", "F9"),
+    ("f10", "F10"),
+    ("f11", "F11"),
+    ("f12", "F12"),
+    ("space", "Space"),
+    ("enter", "Enter"),
+    ("escape", "Escape"),
+    ("tab", "Tab"),
+    ("backspace", "Backspace"),
+    ("delete", "Delete"),
+    ("insert", "Insert"),
+    ("home", "Home"),
+    ("end", "End"),
+    ("page up", "PageUp"),
+    ("page down", "PageDown"),
+    ("up", "ArrowUp"),
+    ("down", "ArrowDown"),
+    ("left", "ArrowLeft"),
+    ("right", "ArrowRight"),
+    ("left shift", "ShiftLeft"),
+    ("right shift", "ShiftRight"),
+    ("left ctrl", "ControlLeft"),
+    ("right ctrl", "ControlRight"),
+    ("left alt", "AltLeft"),
+    ("right alt", "AltRight"),
+    ("caps lock", "CapsLock"),
+    ("`", "Backquote"),
+    ("-", "Minus"),
+    ("=", "Equal"),
+    ("[", "BracketLeft"),
+    ("]", "BracketRight"),
+    ("\\", "Backslash"),
+    (";", "Semicolon"),
+    ("'", "Quote"),
+    (",", "Comma"),
+    (".", "Period"),
+    ("/", "Slash"),
+];
+
+/// Alternative spellings accepted when parsing, as in `enact-winit`
+const KEY_ALIASES: &[(&str, &str)] = &[
+    ("spacebar", "Space"),
+    ("return", "Enter"),
+    ("esc", "Escape"),
+    ("del", "Delete"),
+    ("ins", "Insert"),
+    ("pgup", "PageUp"),
+    ("pgdn", "PageDown"),
+    ("lshift", "ShiftLeft"),
+    ("shift", "ShiftLeft"),
+    ("rshift", "ShiftRight"),
+    ("lctrl", "ControlLeft"),
+    ("ctrl", "ControlLeft"),
+    ("left control", "ControlLeft"),
+    ("rctrl", "ControlRight"),
+    ("right control", "ControlRight"),
+    ("lalt", "AltLeft"),
+    ("alt", "AltLeft"),
+    ("ralt", "AltRight"),
+    ("altgr", "AltRight"),
+    ("grave", "Backquote"),
+    ("tilde", "Backquote"),
+    ("minus", "Minus"),
+    ("equals", "Equal"),
+];
+
+impl Key {
+    fn parse(s: &str) -> Option<Self> {
+        if let Some(&(_, code)) = KEYS
+            .iter()
+            .chain(KEY_ALIASES)
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        {
+            return Some(Key(code.to_owned()));
+        }
+        // DOM codes, e.g. `KeyW`, ignoring case and spaces, as `enact-winit`
+        // accepts its own names
+        let squashed = s.replace(' ', "");
+        if let Some(&(_, code)) = KEYS
+            .iter()
+            .find(|(_, code)| code.eq_ignore_ascii_case(&squashed))
+        {
+            return Some(Key(code.to_owned()));
+        }
+        let code = s.strip_prefix("<web ")?.strip_suffix('>')?;
+        Some(Key(code.to_owned()))
+    }
+
+    /// Portable name of the key, if any
+    fn name(&self) -> Option<&'static str> {
+        KEYS.iter()
+            .find(|&&(_, code)| code == self.0)
+            .map(|&(name, _)| name)
+    }
+
+    fn format(&self) -> String {
+        match self.name() {
+            Some(name) => name.to_owned(),
+            None => format!("<web {}>", self.0),
+        }
+    }
+}
+
+/// A mouse button, identified by the DOM `MouseEvent.button` it produces
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct MouseButton(pub i16);
+
+const MOUSE_BUTTONS: &[(&str, i16)] = &[
+    ("mouse left", 0),
+    ("mouse middle", 1),
+    ("mouse right", 2),
+    ("mouse back", 3),
+    ("mouse forward", 4),
+];
+
+impl MouseButton {
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.to_ascii_lowercase();
+        if let Some(&(_, n)) = MOUSE_BUTTONS.iter().find(|&&(name, _)| name == s) {
+            return Some(MouseButton(n));
+        }
+        // Matches winit's numbering of other buttons
+        Some(MouseButton(s.strip_prefix("mouse ")?.parse().ok()?))
+    }
+
+    fn format(self) -> String {
+        match MOUSE_BUTTONS.iter().find(|&&(_, n)| n == self.0) {
+            Some(&(name, _)) => name.to_owned(),
+            None => format!("mouse {}", self.0),
+        }
+    }
+}
+
+/// Translates DOM events into inputs according to configurable policies
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    /// Only forward [`Input::MouseMotion`] while the document has locked the
+    /// pointer
+    ///
+    /// Prevents look controls from accumulating input while the cursor is
+    /// free to interact with menus.
+    pub require_pointer_lock: bool,
+    /// Resolution of the user's mouse, if known
+    ///
+    /// When set, [`Input::MouseMotion`] reports physical distance in inches
+    /// rather than raw device counts. See the equivalent option in
+    /// `enact-winit`.
+    pub mouse_counts_per_inch: Option<f64>,
+    /// Forward browser key repeat events as [`Input::KeyRepeated`]
+    pub key_repeat: bool,
+}
+
+impl Handler {
+    /// Create a handler with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update action states in `seat` to account for any inputs in `event`
+    /// according to `bindings`
+    ///
+    /// Handles `keydown`, `keyup`, `mousedown`, `mouseup`, and `mousemove`
+    /// events, ignoring all others.
+    pub fn handle(
+        &mut self,
+        event: &web_sys::Event,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        let ty = event.type_();
+        if let Some(event) = event.dyn_ref::<KeyboardEvent>() {
+            let key = Key(event.code());
+            match &*ty {
+                "keydown" if event.repeat() => {
+                    if !self.key_repeat {
+                        return;
+                    }
+                    bindings.handle(&Input::KeyRepeated(key), (), seat).unwrap();
+                }
+                "keydown" => {
+                    bindings
+                        .handle(&Input::KeyHeld(key.clone()), true, seat)
+                        .unwrap();
+                    bindings.handle(&Input::KeyPressed(key), (), seat).unwrap();
+                }
+                "keyup" => {
+                    bindings.handle(&Input::KeyHeld(key), false, seat).unwrap();
+                }
+                _ => {}
+            }
+        } else if let Some(event) = event.dyn_ref::<MouseEvent>() {
+            let button = MouseButton(event.button());
+            match &*ty {
+                "mousedown" => {
+                    bindings
+                        .handle(&Input::MouseButtonHeld(button), true, seat)
+                        .unwrap();
+                    bindings
+                        .handle(&Input::MouseButtonPressed(button), (), seat)
+                        .unwrap();
+                }
+                "mouseup" => {
+                    bindings
+                        .handle(&Input::MouseButtonHeld(button), false, seat)
+                        .unwrap();
+                }
+                "mousemove" if self.forward_mouse_motion() => {
+                    let scale = self.mouse_counts_per_inch.map_or(1.0, |cpi| cpi.recip());
+                    let (x, y) = (f64::from(event.movement_x()), f64::from(event.movement_y()));
+                    bindings
+                        .handle(
+                            &Input::MouseMotion,
+                            mint::Vector2::<f64>::from([x * scale, y * scale]),
+                            seat,
+                        )
+                        .unwrap();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn forward_mouse_motion(&self) -> bool {
+        !self.require_pointer_lock
+            || web_sys::window()
+                .and_then(|window| window.document())
+                .is_some_and(|document| document.pointer_lock_element().is_some())
+    }
+}
+
+/// DOM events which [`Handler`] understands
+const EVENT_TYPES: [&str; 5] = ["keydown", "keyup", "mousedown", "mouseup", "mousemove"];
+
+/// Buffers input events from a DOM [`EventTarget`] until they're handled
+///
+/// Since DOM events are delivered to callbacks, which can't easily borrow an
+/// application's [`enact::Bindings`] and [`enact::Seat`], they're queued
+/// until the application calls [`handle`](Self::handle), e.g. at the start of
+/// each frame. Listeners are removed when dropped.
+pub struct Listener {
+    target: EventTarget,
+    queue: Rc<RefCell<Vec<web_sys::Event>>>,
+    callback: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Listener {
+    /// Listen for input events on `target`, e.g. the window or a canvas
+    pub fn new(target: &EventTarget) -> Result<Self, JsValue> {
+        let queue = Rc::new(RefCell::new(Vec::new()));
+        let callback = Closure::<dyn FnMut(web_sys::Event)>::new({
+            let queue = queue.clone();
+            move |event| queue.borrow_mut().push(event)
+        });
+        let out = Self {
+            target: target.clone(),
+            queue,
+            callback,
+        };
+        for ty in EVENT_TYPES {
+            out.target
+                .add_event_listener_with_callback(ty, out.callback.as_ref().unchecked_ref())?;
+        }
+        Ok(out)
+    }
+
+    /// Handle all events received since the last call
+    pub fn handle(
+        &self,
+        handler: &mut Handler,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        let events = std::mem::take(&mut *self.queue.borrow_mut());
+        for event in &events {
+            handler.handle(event, bindings, seat);
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        for ty in EVENT_TYPES {
+            _ = self
+                .target
+                .remove_event_listener_with_callback(ty, self.callback.as_ref().unchecked_ref());
+        }
+    }
+}
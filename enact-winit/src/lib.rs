@@ -1,5 +1,5 @@
 use winit::{
-    event::{DeviceEvent, ElementState, KeyEvent, MouseButton, WindowEvent},
+    event::{DeviceEvent, ElementState, Ime, KeyEvent, MouseButton, WindowEvent},
     keyboard::{KeyCode, NativeKeyCode, PhysicalKey},
 };
 
@@ -12,6 +12,13 @@ pub enum Input {
     PhysicalKeyPressed(PhysicalKey),
     MouseButtonPressed(MouseButton),
     MouseMotion,
+    /// Key repeat events generated by the OS while a key is held, when
+    /// [`Handler::key_repeat`] is set
+    ///
+    /// Written as the key name followed by `repeat`, e.g. `"down repeat"`.
+    PhysicalKeyRepeated(PhysicalKey),
+    /// Text entered by the user, while [`Handler::text_entry`] is set
+    Text,
 }
 
 impl Input {
@@ -32,12 +39,18 @@ impl enact::Input for Input {
     fn visit_type<V: enact::InputTypeVisitor>(&self) -> V::Output {
         match *self {
             Input::PhysicalKeyHeld(_) | Input::MouseButtonHeld(_) => V::visit::<bool>(),
-            Input::PhysicalKeyPressed(_) | Input::MouseButtonPressed(_) => V::visit::<()>(),
+            Input::PhysicalKeyPressed(_)
+            | Input::MouseButtonPressed(_)
+            | Input::PhysicalKeyRepeated(_) => V::visit::<()>(),
             Input::MouseMotion => V::visit::<mint::Vector2<f64>>(),
+            Input::Text => V::visit::<String>(),
         }
     }
 
     fn from_str(s: &str) -> Vec<Self> {
+        if let Some(key) = s.strip_suffix(" repeat").and_then(parse_key) {
+            return vec![Input::PhysicalKeyRepeated(key)];
+        }
         if let Some(key) = parse_key(s) {
             return vec![Input::PhysicalKeyHeld(key), Input::PhysicalKeyPressed(key)];
         }
@@ -47,6 +60,12 @@ impl enact::Input for Input {
                 Input::MouseButtonPressed(button),
             ];
         }
+        if s.eq_ignore_ascii_case("mouse") {
+            return vec![Input::MouseMotion];
+        }
+        if s.eq_ignore_ascii_case("text") {
+            return vec![Input::Text];
+        }
         vec![]
     }
 
@@ -55,10 +74,50 @@ impl enact::Input for Input {
             Input::PhysicalKeyHeld(k) | Input::PhysicalKeyPressed(k) => format_key(k),
             Input::MouseButtonHeld(b) | Input::MouseButtonPressed(b) => format_mouse_button(b),
             Input::MouseMotion => "mouse".to_owned(),
+            Input::PhysicalKeyRepeated(k) => format!("{} repeat", format_key(k)),
+            Input::Text => "text".to_owned(),
         }
     }
+
+    fn names() -> Vec<String> {
+        KEY_NAMES
+            .iter()
+            .copied()
+            .chain(MOUSE_BUTTON_NAMES)
+            .chain(["mouse", "text"])
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn device_family(&self) -> enact::DeviceFamily {
+        enact::DeviceFamily::KeyboardMouse
+    }
+
+    /// `kbm/` followed by the input's name, e.g. `kbm/space` or
+    /// `kbm/mouse-left`
+    ///
+    /// Keys without a portable name and [`Input::Text`] have no glyph.
+    fn glyph(&self) -> Option<String> {
+        let name = match *self {
+            Input::PhysicalKeyHeld(PhysicalKey::Code(k))
+            | Input::PhysicalKeyPressed(PhysicalKey::Code(k))
+            | Input::PhysicalKeyRepeated(PhysicalKey::Code(k)) => format_keycode(k)?.to_owned(),
+            Input::MouseButtonHeld(b) | Input::MouseButtonPressed(b) => format_mouse_button(b),
+            Input::MouseMotion => "mouse".to_owned(),
+            _ => return None,
+        };
+        enact::DeviceFamily::KeyboardMouse.glyph(&name)
+    }
 }
 
+const MOUSE_BUTTON_NAMES: [&str; 5] = [
+    "mouse left",
+    "mouse right",
+    "mouse middle",
+    "mouse back",
+    "mouse forward",
+];
+
 fn parse_mouse_button(x: &str) -> Option<MouseButton> {
     Some(match &*x.to_ascii_lowercase() {
         "mouse left" => MouseButton::Left,
@@ -92,9 +151,7 @@ fn parse_key(x: &str) -> Option<PhysicalKey> {
     if let Some(code) = parse_keycode(x) {
         return Some(PhysicalKey::Code(code));
     }
-    let Some(x) = x.strip_prefix("<") else {
-        return None;
-    };
+    let x = x.strip_prefix("<")?;
     for (id, f) in [
         (
             "android",
@@ -123,7 +180,11 @@ fn parse_key(x: &str) -> Option<PhysicalKey> {
 
 fn format_key(k: PhysicalKey) -> String {
     match k {
-        PhysicalKey::Code(k) => format_keycode(k).to_owned(),
+        PhysicalKey::Code(k) => match format_keycode(k) {
+            Some(name) => name.to_owned(),
+            // Accepted by `parse_keycode`
+            None => format!("{k:?}"),
+        },
         PhysicalKey::Unidentified(k) => match k {
             NativeKeyCode::Unidentified => "<unknown>".to_owned(),
             NativeKeyCode::Android(n) => format!("<android {n}>"),
@@ -134,55 +195,337 @@ fn format_key(k: PhysicalKey) -> String {
     }
 }
 
+/// Defines the canonical name of each supported key, followed by any
+/// alternative spellings accepted when parsing
+///
+/// Winit's own name for each key, e.g. `KeyW` or `ArrowUp`, is also accepted,
+/// ignoring case and spaces. Keys without a portable name, listed after the
+/// `;`, are written as winit names them, e.g. `NumLock`.
 macro_rules! keycodes {
-    ($($variant:ident => $s:literal,)*) => {
+    ($($variant:ident => $s:literal $(| $alias:literal)*,)*; $($other:ident,)*) => {
+        const KEY_NAMES: &[&str] = &[$($s,)*];
+
         fn parse_keycode(x: &str) -> Option<KeyCode> {
             use KeyCode::*;
-            Some(match &*x.to_ascii_lowercase() {
-                $($s => $variant,)*
-                _ => return None,
+            let x = x.to_ascii_lowercase();
+            Some(match &*x {
+                $($s $(| $alias)* => $variant,)*
+                _ => {
+                    let x = x.replace(' ', "");
+                    return [$((stringify!($variant), $variant),)* $((stringify!($other), $other),)*]
+                        .into_iter()
+                        .find(|(name, _)| name.eq_ignore_ascii_case(&x))
+                        .map(|(_, code)| code);
+                }
             })
         }
 
-        fn format_keycode(x: KeyCode) -> &'static str {
+        /// Portable name of `x`, if any
+        fn format_keycode(x: KeyCode) -> Option<&'static str> {
             use KeyCode::*;
-            match x {
+            Some(match x {
                 $($variant => $s,)*
-                _ => todo!(),
-            }
+                _ => return None,
+            })
         }
     };
 }
 
 keycodes! {
-    KeyW => "w",
     KeyA => "a",
-    KeyS => "s",
+    KeyB => "b",
+    KeyC => "c",
     KeyD => "d",
-    Space => "space",
+    KeyE => "e",
+    KeyF => "f",
+    KeyG => "g",
+    KeyH => "h",
+    KeyI => "i",
+    KeyJ => "j",
+    KeyK => "k",
+    KeyL => "l",
+    KeyM => "m",
+    KeyN => "n",
+    KeyO => "o",
+    KeyP => "p",
+    KeyQ => "q",
+    KeyR => "r",
+    KeyS => "s",
+    KeyT => "t",
+    KeyU => "u",
+    KeyV => "v",
+    KeyW => "w",
+    KeyX => "x",
+    KeyY => "y",
+    KeyZ => "z",
+    Digit0 => "0",
+    Digit1 => "1",
+    Digit2 => "2",
+    Digit3 => "3",
+    Digit4 => "4",
+    Digit5 => "5",
+    Digit6 => "6",
+    Digit7 => "7",
+    Digit8 => "8",
+    Digit9 => "9",
+    F1 => "f1",
+    F2 => "f2",
+    F3 => "f3",
+    F4 => "f4",
+    F5 => "f5",
+    F6 => "f6",
+    F7 => "f7",
+    F8 => "f8",
+    F9 => "f9",
+    F10 => "f10",
+    F11 => "f11",
+    F12 => "f12",
+    Space => "space" | "spacebar",
+    Enter => "enter" | "return",
+    Escape => "escape" | "esc",
+    Tab => "tab",
+    Backspace => "backspace",
+    Delete => "delete" | "del",
+    Insert => "insert" | "ins",
+    Home => "home",
+    End => "end",
+    PageUp => "page up" | "pgup",
+    PageDown => "page down" | "pgdn",
+    ArrowUp => "up",
+    ArrowDown => "down",
+    ArrowLeft => "left",
+    ArrowRight => "right",
+    ShiftLeft => "left shift" | "lshift" | "shift",
+    ShiftRight => "right shift" | "rshift",
+    ControlLeft => "left ctrl" | "lctrl" | "ctrl" | "left control",
+    ControlRight => "right ctrl" | "rctrl" | "right control",
+    AltLeft => "left alt" | "lalt" | "alt",
+    AltRight => "right alt" | "ralt" | "altgr",
+    CapsLock => "caps lock",
+    Backquote => "`" | "grave" | "tilde",
+    Minus => "-" | "minus",
+    Equal => "=" | "equals",
+    BracketLeft => "[",
+    BracketRight => "]",
+    Backslash => "\\",
+    Semicolon => ";",
+    Quote => "'",
+    Comma => ",",
+    Period => ".",
+    Slash => "/",
+;
+    IntlBackslash, IntlRo, IntlYen, ContextMenu, SuperLeft, SuperRight, Convert,
+    KanaMode, Lang1, Lang2, Lang3, Lang4, Lang5, NonConvert, Help, NumLock, Numpad0,
+    Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9,
+    NumpadAdd, NumpadBackspace, NumpadClear, NumpadClearEntry, NumpadComma,
+    NumpadDecimal, NumpadDivide, NumpadEnter, NumpadEqual, NumpadHash, NumpadMemoryAdd,
+    NumpadMemoryClear, NumpadMemoryRecall, NumpadMemoryStore, NumpadMemorySubtract,
+    NumpadMultiply, NumpadParenLeft, NumpadParenRight, NumpadStar, NumpadSubtract, Fn,
+    FnLock, PrintScreen, ScrollLock, Pause, BrowserBack, BrowserFavorites,
+    BrowserForward, BrowserHome, BrowserRefresh, BrowserSearch, BrowserStop, Eject,
+    LaunchApp1, LaunchApp2, LaunchMail, MediaPlayPause, MediaSelect, MediaStop,
+    MediaTrackNext, MediaTrackPrevious, Power, Sleep, AudioVolumeDown, AudioVolumeMute,
+    AudioVolumeUp, WakeUp, Meta, Hyper, Turbo, Abort, Resume, Suspend, Again, Copy, Cut,
+    Find, Open, Paste, Props, Select, Undo, Hiragana, Katakana, F13, F14, F15, F16, F17,
+    F18, F19, F20, F21, F22, F23, F24, F25, F26, F27, F28, F29, F30, F31, F32, F33, F34,
+    F35,
 }
 
 /// Update action states in `seat` to account for any inputs in `event`
 /// according to `bindings`
 ///
-/// Convenience wrapper for [`Event::handle`]
-pub fn handle<E: Event>(event: &E, bindings: &enact::Bindings, seat: &mut enact::Seat) {
-    event.handle(bindings, seat);
+/// Convenience wrapper for [`Handler::handle`] with default options
+pub fn handle<E: Event>(event: &E, bindings: &impl enact::HandleInput, seat: &mut enact::Seat) {
+    Handler::new().handle(event, bindings, seat);
+}
+
+/// Translates winit events into inputs according to configurable policies
+#[derive(Debug, Clone, Default)]
+pub struct Handler {
+    /// Only forward [`Input::MouseMotion`] while
+    /// [`cursor_grabbed`](Self::cursor_grabbed) is set
+    ///
+    /// Prevents look controls from accumulating input while the cursor is
+    /// free to interact with menus.
+    pub require_cursor_grab: bool,
+    /// Whether the application has currently grabbed or locked the cursor
+    pub cursor_grabbed: bool,
+    /// Resolution of the user's mouse, if known
+    ///
+    /// When set, [`Input::MouseMotion`] reports physical distance in inches
+    /// rather than raw device counts, so that sensitivity settings (e.g.
+    /// degrees turned per inch) transfer between mice. Winit does not report
+    /// mouse resolution, so this must be supplied by the user.
+    pub mouse_counts_per_inch: Option<f64>,
+    /// Forward text typed by the user as [`Input::Text`]
+    ///
+    /// Should be set while a text field such as a chat box or console has
+    /// focus, and usually accompanied by
+    /// [`Window::set_ime_allowed`](winit::window::Window::set_ime_allowed).
+    pub text_entry: bool,
+    /// How to handle synthetic key events
+    ///
+    /// See [`SyntheticKeyPolicy`].
+    pub synthetic_keys: SyntheticKeyPolicy,
+    /// Forward OS key repeat events as [`Input::PhysicalKeyRepeated`]
+    ///
+    /// Useful for UI navigation, like scrolling through a list by holding an
+    /// arrow key.
+    pub key_repeat: bool,
+    /// Synthesize [`Input::MouseMotion`] from consecutive
+    /// [`WindowEvent::CursorMoved`] positions
+    ///
+    /// For platforms which don't report [`DeviceEvent::MouseMotion`], such as
+    /// some web and mobile targets. Has no effect once a raw motion event is
+    /// seen, to avoid counting motion twice. Unlike raw motion, synthesized
+    /// motion is measured in physical pixels after pointer acceleration, and
+    /// stops at the edges of the window unless the cursor is locked.
+    pub cursor_motion_fallback: bool,
+    /// Position from the previous [`WindowEvent::CursorMoved`]
+    last_cursor_position: Option<(f64, f64)>,
+    /// Whether a [`DeviceEvent::MouseMotion`] has been seen
+    raw_motion_seen: bool,
+}
+
+/// How to handle synthetic key events, which some platforms generate to
+/// report keys that were pressed or released while a window was unfocused
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum SyntheticKeyPolicy {
+    /// Handle synthetic events the same as real ones
+    #[default]
+    Real,
+    /// Update held state without producing press events, so that e.g.
+    /// focusing a window with a key held doesn't trigger actions
+    Resync,
+    /// Ignore synthetic events entirely
+    Ignore,
+}
+
+impl Handler {
+    /// Create a handler with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update action states in `seat` to account for any inputs in `event`
+    /// according to `bindings`
+    pub fn handle<E: Event>(
+        &mut self,
+        event: &E,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        event.handle(self, bindings, seat);
+    }
+
+    /// Like [`handle`](Self::handle), but if `consumed` is set, only forward
+    /// key and button releases
+    ///
+    /// `consumed` should be set when a UI library reports that it wants the
+    /// event, e.g. egui's `EventResponse::consumed`. See
+    /// [`enact::HandleInput::handle_consumed`].
+    pub fn handle_consumed<E: Event>(
+        &mut self,
+        event: &E,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+        consumed: bool,
+    ) {
+        event.handle(self, &Consumed { bindings, consumed }, seat);
+    }
+
+    /// Report mouse motion by `(x, y)` device counts, subject to policy
+    fn forward_mouse_motion(
+        &self,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+        x: f64,
+        y: f64,
+    ) {
+        if self.require_cursor_grab && !self.cursor_grabbed {
+            return;
+        }
+        let scale = self.mouse_counts_per_inch.map_or(1.0, |cpi| cpi.recip());
+        bindings
+            .handle(
+                &Input::MouseMotion,
+                mint::Vector2::<f64>::from([x * scale, y * scale]),
+                seat,
+            )
+            .unwrap();
+    }
+}
+
+/// Forwards inputs to `bindings` via [`enact::HandleInput::handle_consumed`]
+struct Consumed<'a, B> {
+    bindings: &'a B,
+    consumed: bool,
+}
+
+impl<B: enact::HandleInput> enact::HandleInput for Consumed<'_, B> {
+    fn handle<I: enact::Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut enact::Seat,
+    ) -> Result<(), enact::TypeError> {
+        self.bindings
+            .handle_consumed(input, data, seat, self.consumed)
+    }
 }
 
 /// Winit events that might contain supported inputs
 pub trait Event {
-    /// See [`handle`]
-    fn handle(&self, bindings: &enact::Bindings, seat: &mut enact::Seat);
+    /// See [`Handler::handle`]
+    fn handle(
+        &self,
+        handler: &mut Handler,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    );
 
     /// See [`Input::from_event`]
     fn to_inputs(&self) -> Vec<Input>;
 }
 
 impl Event for WindowEvent {
-    fn handle(&self, bindings: &enact::Bindings, seat: &mut enact::Seat) {
+    fn handle(
+        &self,
+        handler: &mut Handler,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        if handler.text_entry {
+            let text = match *self {
+                WindowEvent::KeyboardInput { ref event, .. } if event.state.is_pressed() => {
+                    event.text.as_deref()
+                }
+                WindowEvent::Ime(Ime::Commit(ref text)) => Some(&**text),
+                _ => None,
+            };
+            if let Some(text) = text {
+                bindings
+                    .handle(&Input::Text, text.to_owned(), seat)
+                    .unwrap();
+            }
+        }
         match *self {
-            WindowEvent::KeyboardInput { ref event, .. } if !event.repeat => {
+            WindowEvent::KeyboardInput { ref event, .. } if event.repeat => {
+                if !handler.key_repeat || !event.state.is_pressed() {
+                    return;
+                }
+                bindings
+                    .handle(&Input::PhysicalKeyRepeated(event.physical_key), (), seat)
+                    .unwrap();
+            }
+            WindowEvent::KeyboardInput {
+                ref event,
+                is_synthetic,
+                ..
+            } => {
+                if is_synthetic && handler.synthetic_keys == SyntheticKeyPolicy::Ignore {
+                    return;
+                }
                 bindings
                     .handle(
                         &Input::PhysicalKeyHeld(event.physical_key),
@@ -190,12 +533,28 @@ impl Event for WindowEvent {
                         seat,
                     )
                     .unwrap();
-                if event.state.is_pressed() {
+                if event.state.is_pressed()
+                    && !(is_synthetic && handler.synthetic_keys == SyntheticKeyPolicy::Resync)
+                {
                     bindings
                         .handle(&Input::PhysicalKeyPressed(event.physical_key), (), seat)
                         .unwrap();
                 }
             }
+            WindowEvent::CursorMoved { position, .. } => {
+                let last = handler
+                    .last_cursor_position
+                    .replace((position.x, position.y));
+                if !handler.cursor_motion_fallback || handler.raw_motion_seen {
+                    return;
+                }
+                if let Some((x, y)) = last {
+                    handler.forward_mouse_motion(bindings, seat, position.x - x, position.y - y);
+                }
+            }
+            WindowEvent::CursorLeft { .. } | WindowEvent::Focused(false) => {
+                handler.last_cursor_position = None;
+            }
             WindowEvent::MouseInput { state, button, .. } => {
                 bindings
                     .handle(&Input::MouseButtonHeld(button), state.is_pressed(), seat)
@@ -239,18 +598,15 @@ impl Event for WindowEvent {
 }
 
 impl Event for DeviceEvent {
-    fn handle(&self, bindings: &enact::Bindings, seat: &mut enact::Seat) {
-        match *self {
-            DeviceEvent::MouseMotion { delta: (x, y) } => {
-                bindings
-                    .handle(
-                        &Input::MouseMotion,
-                        mint::Vector2::<f64>::from([x, y]),
-                        seat,
-                    )
-                    .unwrap();
-            }
-            _ => {}
+    fn handle(
+        &self,
+        handler: &mut Handler,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
+        if let DeviceEvent::MouseMotion { delta: (x, y) } = *self {
+            handler.raw_motion_seen = true;
+            handler.forward_mouse_motion(bindings, seat, x, y);
         }
     }
 
@@ -263,11 +619,16 @@ impl Event for DeviceEvent {
 }
 
 impl<T> Event for winit::event::Event<T> {
-    fn handle(&self, bindings: &enact::Bindings, seat: &mut enact::Seat) {
+    fn handle(
+        &self,
+        handler: &mut Handler,
+        bindings: &impl enact::HandleInput,
+        seat: &mut enact::Seat,
+    ) {
         use winit::event::Event::*;
         match *self {
-            WindowEvent { ref event, .. } => handle(event, bindings, seat),
-            DeviceEvent { ref event, .. } => handle(event, bindings, seat),
+            WindowEvent { ref event, .. } => handler.handle(event, bindings, seat),
+            DeviceEvent { ref event, .. } => handler.handle(event, bindings, seat),
             _ => {}
         }
     }
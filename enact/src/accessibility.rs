@@ -0,0 +1,139 @@
+//! Presets rewriting configs for accessibility
+
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BindingsFactory, Config, Filter, FilterConfig, Session,
+    filter::{Debounce, Toggle},
+};
+
+/// Accessibility presets applied to every `bool` action bound directly to
+/// inputs
+///
+/// Enabled by [`Config::accessibility`] or
+/// [`BindingsFactory::set_accessibility`]. Presets are applied by
+/// [`BindingsFactory::load`] by inserting filters between inputs and actions,
+/// so that applications need no changes to offer them. Actions already read
+/// or written by a filter in the config are left alone, as are actions of
+/// other types; the former are recognized by the convention that filter
+/// sources are named by suffixing their targets' names. Since the filters are
+/// part of the loaded [`Bindings`], they also appear in its
+/// [`save`](crate::Bindings::save)d config, along with the presets, which
+/// leave them alone when it's loaded again.
+///
+/// [`Bindings`]: crate::Bindings
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct Accessibility {
+    /// Press once to hold and again to release, rather than holding, via a
+    /// [`Toggle`]
+    pub toggle_holds: bool,
+    /// Toggle only actions used as conditions of other bindings, e.g.
+    /// `aiming` in `"mouse left if aiming"`, so that such combinations can be
+    /// pressed one at a time
+    ///
+    /// Implied by [`toggle_holds`](Self::toggle_holds).
+    pub sticky_conditions: bool,
+    /// Ignore presses and releases within this many milliseconds of the
+    /// previous one, via a [`Debounce`]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub debounce_ms: Option<u32>,
+}
+
+impl Accessibility {
+    /// Whether no presets are enabled
+    pub fn is_off(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Every preset enabled by either `self` or `other`, with the longer
+    /// debounce
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            toggle_holds: self.toggle_holds || other.toggle_holds,
+            sticky_conditions: self.sticky_conditions || other.sticky_conditions,
+            debounce_ms: self.debounce_ms.max(other.debounce_ms),
+        }
+    }
+}
+
+impl BindingsFactory {
+    /// Apply `presets` to every config loaded, in addition to any enabled by
+    /// [`Config::accessibility`]
+    pub fn set_accessibility(&mut self, presets: Accessibility) {
+        self.accessibility = presets;
+    }
+}
+
+/// `config` with `presets` applied, if any are enabled
+pub(crate) fn apply(session: &Session, config: &Config, presets: Accessibility) -> Option<Config> {
+    if presets.is_off() {
+        return None;
+    }
+    // Filter sources are named by suffixing their targets' names
+    let is_filtered = |name: &str| {
+        config
+            .filters
+            .iter()
+            .flat_map(|f| &f.targets)
+            .any(|target| {
+                name.strip_prefix(&**target)
+                    .is_some_and(|suffix| suffix.is_empty() || suffix.starts_with('-'))
+            })
+    };
+    let conditions = config
+        .sources
+        .iter()
+        .flat_map(|source| &source.bindings)
+        .flat_map(|(_, inputs)| inputs)
+        .filter_map(|input| Some(input.rsplit_once(" if ")?.1.trim()))
+        .collect::<Vec<_>>();
+
+    let mut out = config.clone();
+    let mut wrapped = Vec::<(String, String)>::new();
+    for source in &mut out.sources {
+        for (action, _) in &mut source.bindings {
+            if let Some((_, outer)) = wrapped.iter().find(|(x, _)| x == action) {
+                action.clone_from(outer);
+                continue;
+            }
+            let is_bool = session
+                .action_id(action)
+                .is_some_and(|id| session.action::<bool>(id).is_ok());
+            if !is_bool || is_filtered(action) {
+                continue;
+            }
+            let mut outer = action.clone();
+            if presets.toggle_holds
+                || (presets.sticky_conditions && conditions.contains(&&**action))
+            {
+                out.filters.push(FilterConfig {
+                    ty: Toggle::NAME.to_owned(),
+                    targets: vec![outer.clone()],
+                    params: Vec::new(),
+                });
+                outer = format!("{outer}-press");
+            }
+            if let Some(ms) = presets.debounce_ms {
+                out.filters.push(FilterConfig {
+                    ty: Debounce::NAME.to_owned(),
+                    targets: vec![outer.clone()],
+                    params: vec![ms.to_string()],
+                });
+                outer = format!("{outer}-raw");
+            }
+            wrapped.push((action.clone(), outer.clone()));
+            *action = outer;
+        }
+    }
+    Some(out)
+}
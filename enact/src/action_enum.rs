@@ -0,0 +1,85 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{ActionId, DuplicateAction, Seat, Session};
+
+/// An enum whose variants each correspond to an action
+///
+/// Each variant's fields are the data associated with its action: unit
+/// variants correspond to `Action<()>`, and variants with a single field of
+/// type `T` to `Action<T>`. Usually implemented with `#[derive(ActionEnum)]`,
+/// available through the `derive` feature, which also generates a fieldless
+/// `Kind` enum identifying each variant. Action names are derived from
+/// variant names in `snake_case`, and can be overridden with
+/// `#[action(name = "...")]`.
+///
+/// Useful for applications which prefer matching on an enum over storing
+/// many [`Action`](crate::Action) handles. See [`ActionSet`].
+pub trait ActionEnum: Sized + 'static {
+    /// Identifies a variant without its data
+    type Kind: Copy;
+
+    /// Name of each variant's action, in order
+    const NAMES: &'static [&'static str];
+
+    /// The variant of `self`
+    fn kind(&self) -> Self::Kind;
+
+    /// Position of `kind` in [`NAMES`](Self::NAMES)
+    fn index(kind: Self::Kind) -> usize;
+
+    /// Create each variant's action, in order
+    fn create_actions(session: &mut Session) -> Result<Vec<ActionId>, DuplicateAction>;
+
+    /// Consume the next state change of the action for the variant at `index`,
+    /// given `actions` from [`create_actions`](Self::create_actions)
+    fn poll(seat: &Seat, actions: &[ActionId], index: usize) -> Option<Self>;
+}
+
+/// The actions corresponding to the variants of an [`ActionEnum`]
+pub struct ActionSet<E> {
+    actions: Vec<ActionId>,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E: ActionEnum> ActionSet<E> {
+    /// Create an action for each variant of `E` in `session`
+    pub fn new(session: &mut Session) -> Result<Self, DuplicateAction> {
+        Ok(Self {
+            actions: E::create_actions(session)?,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Look up the action for a variant
+    pub fn id(&self, kind: E::Kind) -> ActionId {
+        self.actions[E::index(kind)]
+    }
+
+    /// Consume the next state change of the action for a variant, if any
+    pub fn poll(&self, seat: &Seat, kind: E::Kind) -> Option<E> {
+        E::poll(seat, &self.actions, E::index(kind))
+    }
+
+    /// Consume all state changes of every variant's action
+    ///
+    /// Changes are grouped by variant, in declaration order.
+    pub fn poll_all(&self, seat: &Seat) -> Vec<E> {
+        let mut out = Vec::new();
+        for index in 0..self.actions.len() {
+            while let Some(x) = E::poll(seat, &self.actions, index) {
+                out.push(x);
+            }
+        }
+        out
+    }
+}
+
+impl<E> Clone for ActionSet<E> {
+    fn clone(&self) -> Self {
+        Self {
+            actions: self.actions.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Synthetic input workloads for measuring throughput
+//!
+//! Generates storms of key presses and mouse deltas against arbitrary
+//! bindings, so that applications and CI can track the cost of
+//! [`Bindings::handle`] and [`Seat::flush`] across releases and configs:
+//!
+//! ```ignore
+//! let mut harness = Harness::standard(64, 4);
+//! let report = harness.run(&Storm { keys: 32, deltas: 100, frames: 1000 });
+//! println!("{:.0} events/s", report.events_per_sec());
+//! ```
+
+use alloc::{borrow::ToOwned, format, string::String, vec, vec::Vec};
+use std::time::{Duration, Instant};
+
+use crate::{
+    Bindings, DeviceFamily, Input, InputTypeVisitor, ManualClock, Seat, Session, filter::DPad,
+};
+
+/// Inputs generated by a [`Harness`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SyntheticInput {
+    /// Whether a numbered key is held
+    Key(u32),
+    /// Pointer motion
+    Motion,
+}
+
+impl Input for SyntheticInput {
+    const NAME: &'static str = "synthetic";
+
+    fn visit_type<V: InputTypeVisitor>(&self) -> V::Output {
+        match *self {
+            SyntheticInput::Key(_) => V::visit::<bool>(),
+            SyntheticInput::Motion => V::visit::<mint::Vector2<f64>>(),
+        }
+    }
+
+    fn from_str(s: &str) -> Vec<Self> {
+        if s == "motion" {
+            return vec![SyntheticInput::Motion];
+        }
+        s.strip_prefix("key ")
+            .and_then(|n| n.parse().ok())
+            .map(SyntheticInput::Key)
+            .into_iter()
+            .collect()
+    }
+
+    fn to_string(&self) -> String {
+        match *self {
+            SyntheticInput::Key(n) => format!("key {n}"),
+            SyntheticInput::Motion => "motion".to_owned(),
+        }
+    }
+
+    fn device_family(&self) -> DeviceFamily {
+        DeviceFamily::KeyboardMouse
+    }
+}
+
+/// The shape of a synthetic workload, run by [`Harness::run`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Storm {
+    /// Number of keys toggled per frame
+    pub keys: u32,
+    /// Number of mouse deltas per frame
+    pub deltas: u32,
+    pub frames: u32,
+}
+
+/// Results of [`Harness::run`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Number of inputs handled
+    pub events: u64,
+    /// Total time spent in [`Bindings::handle`] and [`Bindings::update`]
+    pub handle: Duration,
+    /// Total time spent in [`Seat::flush`]
+    pub flush: Duration,
+}
+
+impl Report {
+    /// Inputs handled per second of [`handle`](Self::handle) time
+    pub fn events_per_sec(&self) -> f64 {
+        self.events as f64 / self.handle.as_secs_f64()
+    }
+}
+
+/// Drives synthetic input through bindings, measuring how long it takes
+///
+/// Bindings under test should bind [`SyntheticInput`]s. By default, the seat
+/// runs on virtual time, advancing 1ms per frame, so that time-aware filters
+/// behave deterministically. See [`realtime`](Self::realtime) for measuring
+/// on the system clock instead.
+pub struct Harness {
+    pub session: Session,
+    pub bindings: Bindings,
+    pub seat: Seat,
+    /// Virtual time, if not running on the system clock
+    clock: Option<ManualClock>,
+}
+
+impl Harness {
+    /// Measure `bindings`
+    pub fn new(session: Session, bindings: Bindings) -> Self {
+        let clock = ManualClock::new();
+        let mut seat = Seat::new();
+        seat.set_clock(clock.clone());
+        Self {
+            session,
+            bindings,
+            seat,
+            clock: Some(clock),
+        }
+    }
+
+    /// Measure `bindings` with the seat on the [`SystemClock`](crate::SystemClock)
+    ///
+    /// As in applications, every push is then made at a distinct time, which
+    /// exercises per-change bookkeeping that virtual time hides.
+    pub fn realtime(session: Session, bindings: Bindings) -> Self {
+        Self {
+            session,
+            bindings,
+            seat: Seat::new(),
+            clock: None,
+        }
+    }
+
+    /// A typical graph: `keys` keys each bound to their own action, and
+    /// `dpads` [`DPad`]s each fed by four more keys
+    ///
+    /// Mouse motion is bound to a vector action.
+    pub fn standard(keys: u32, dpads: u32) -> Self {
+        let mut session = Session::new();
+        let mut bindings = Bindings::new();
+        for key in 0..keys {
+            let action = session
+                .create_action::<bool>(&format!("key-{key}"))
+                .unwrap();
+            bindings
+                .bind(SyntheticInput::Key(key), action.id(), &session)
+                .unwrap();
+        }
+        for n in 0..dpads {
+            let target = session
+                .create_action::<mint::Vector2<f64>>(&format!("dpad-{n}"))
+                .unwrap();
+            let dpad = DPad::new(&mut session, target).unwrap();
+            let sources = [dpad.up(), dpad.left(), dpad.down(), dpad.right()];
+            for (i, source) in (0..).zip(sources) {
+                let key = SyntheticInput::Key(keys + n * 4 + i);
+                bindings.bind(key, source.id(), &session).unwrap();
+            }
+            bindings.add_filter(dpad, &session).unwrap();
+        }
+        let motion = session
+            .create_action::<mint::Vector2<f64>>("motion")
+            .unwrap();
+        bindings
+            .bind(SyntheticInput::Motion, motion.id(), &session)
+            .unwrap();
+        Self::new(session, bindings)
+    }
+
+    /// Handle `storm`, updating and flushing after each frame
+    ///
+    /// Keys are toggled in turn, cycling through every key bound in the
+    /// harness's bindings.
+    pub fn run(&mut self, storm: &Storm) -> Report {
+        let keys = self.bound_keys();
+        let mut report = Report {
+            events: 0,
+            handle: Duration::ZERO,
+            flush: Duration::ZERO,
+        };
+        let mut held = vec![false; keys.len()];
+        let mut next = 0;
+        for frame in 0..storm.frames {
+            let start = Instant::now();
+            for _ in 0..storm.keys {
+                if keys.is_empty() {
+                    break;
+                }
+                let i = next % keys.len();
+                next += 1;
+                held[i] = !held[i];
+                let _ = self.bindings.handle(&keys[i], held[i], &mut self.seat);
+            }
+            for i in 0..storm.deltas {
+                let delta =
+                    mint::Vector2::from([f64::from(i % 7) - 3.0, f64::from(frame % 5) - 2.0]);
+                let _ = self
+                    .bindings
+                    .handle(&SyntheticInput::Motion, delta, &mut self.seat);
+            }
+            self.bindings.update(&mut self.seat);
+            report.handle += start.elapsed();
+            report.events += u64::from(storm.deltas);
+            if !keys.is_empty() {
+                report.events += u64::from(storm.keys);
+            }
+
+            let start = Instant::now();
+            self.seat.flush();
+            report.flush += start.elapsed();
+            if let Some(ref clock) = self.clock {
+                clock.advance(Duration::from_millis(1));
+            }
+        }
+        report
+    }
+
+    /// Every [`SyntheticInput::Key`] bound in the bindings, in order
+    fn bound_keys(&self) -> Vec<SyntheticInput> {
+        let mut keys = self
+            .bindings
+            .bindings_for_any::<SyntheticInput>()
+            .into_iter()
+            .map(|(input, _)| input)
+            .filter(|x| matches!(x, SyntheticInput::Key(_)))
+            .collect::<Vec<_>>();
+        keys.sort_unstable_by_key(|x| match *x {
+            SyntheticInput::Key(n) => n,
+            SyntheticInput::Motion => u32::MAX,
+        });
+        keys
+    }
+}
@@ -0,0 +1,168 @@
+//! Compact binary encoding of [`Config`], for shipping inside asset packs
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{Accessibility, Config, DeviceAlias, FilterConfig};
+
+/// Identifies encoded configs
+const MAGIC: [u8; 4] = *b"ENCF";
+/// Incremented on incompatible changes to the encoding
+const VERSION: u8 = 2;
+/// Magic, version, and CRC-32 of the payload
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4;
+
+impl Config {
+    /// Encode in a compact binary form
+    ///
+    /// The payload is prefixed with a header identifying the format version
+    /// and a checksum, so corruption is detected by
+    /// [`from_bytes`](Self::from_bytes).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Encoded as tuples since `Config`'s own representation omits empty
+        // fields, which non-self-describing formats can't handle
+        let filters = self
+            .filters
+            .iter()
+            .map(|f| (&f.ty, &f.targets, &f.params))
+            .collect::<Vec<_>>();
+        let devices = self
+            .devices
+            .iter()
+            .map(|d| (&d.alias, d.vendor_id, d.product_id, &d.name, &d.serial))
+            .collect::<Vec<_>>();
+        let payload = postcard::to_allocvec(&(
+            &self.include,
+            devices,
+            &self.sources,
+            filters,
+            (
+                self.accessibility.toggle_holds,
+                self.accessibility.sticky_conditions,
+                self.accessibility.debounce_ms,
+            ),
+            &self.checksum,
+        ))
+        .expect("configs are always serializable");
+        let mut out = Vec::with_capacity(HEADER_LEN + payload.len());
+        out.extend_from_slice(&MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&crc32(&payload).to_le_bytes());
+        out.extend_from_slice(&payload);
+        out
+    }
+
+    /// Decode the output of [`to_bytes`](Self::to_bytes)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BinaryConfigError> {
+        if bytes.len() < HEADER_LEN || bytes[..MAGIC.len()] != MAGIC {
+            return Err(BinaryConfigError::NotAConfig);
+        }
+        let version = bytes[MAGIC.len()];
+        if version != VERSION {
+            return Err(BinaryConfigError::UnsupportedVersion(version));
+        }
+        let (checksum, payload) = bytes[MAGIC.len() + 1..].split_at(4);
+        if crc32(payload).to_le_bytes() != checksum {
+            return Err(BinaryConfigError::Corrupt);
+        }
+        let (include, devices, sources, filters, accessibility, checksum): (
+            _,
+            Vec<EncodedDevice>,
+            _,
+            Vec<EncodedFilter>,
+            EncodedAccessibility,
+            _,
+        ) = postcard::from_bytes(payload).map_err(BinaryConfigError::Decode)?;
+        Ok(Config {
+            include,
+            devices: devices
+                .into_iter()
+                .map(|(alias, vendor_id, product_id, name, serial)| DeviceAlias {
+                    alias,
+                    vendor_id,
+                    product_id,
+                    name,
+                    serial,
+                })
+                .collect(),
+            sources,
+            filters: filters
+                .into_iter()
+                .map(|(ty, targets, params)| FilterConfig {
+                    ty,
+                    targets,
+                    params,
+                })
+                .collect(),
+            accessibility: Accessibility {
+                toggle_holds: accessibility.0,
+                sticky_conditions: accessibility.1,
+                debounce_ms: accessibility.2,
+            },
+            checksum,
+        })
+    }
+}
+
+/// Type, targets, and parameters of a [`FilterConfig`]
+type EncodedFilter = (String, Vec<String>, Vec<String>);
+
+/// Whether holds are toggled, whether conditions are sticky, and the debounce
+/// window of an [`Accessibility`]
+type EncodedAccessibility = (bool, bool, Option<u32>);
+
+/// Label, vendor ID, product ID, name pattern, and serial of a [`DeviceAlias`]
+type EncodedDevice = (
+    String,
+    Option<u16>,
+    Option<u16>,
+    Option<String>,
+    Option<String>,
+);
+
+/// Reasons why [`Config::from_bytes`] might fail
+#[derive(Debug)]
+pub enum BinaryConfigError {
+    /// The data does not begin with an encoded config's header
+    NotAConfig,
+    /// The data was encoded by an incompatible version of this library
+    UnsupportedVersion(u8),
+    /// The payload does not match its checksum
+    Corrupt,
+    /// The payload could not be decoded
+    Decode(postcard::Error),
+}
+
+impl fmt::Display for BinaryConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BinaryConfigError::NotAConfig => write!(f, "not an encoded config"),
+            BinaryConfigError::UnsupportedVersion(v) => {
+                write!(f, "unsupported config encoding version {v}")
+            }
+            BinaryConfigError::Corrupt => write!(f, "config checksum mismatch"),
+            BinaryConfigError::Decode(ref e) => write!(f, "malformed config: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for BinaryConfigError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match *self {
+            BinaryConfigError::Decode(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// CRC-32 (IEEE), computed bitwise since configs are small
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
@@ -0,0 +1,112 @@
+use alloc::{format, vec::Vec};
+
+use crate::{
+    BindingsFactory, Config, DuplicateAction, FilterConfig, NAMESPACE_SEPARATOR, Session,
+    SourceConfig,
+};
+
+/// A reusable set of actions with default bindings, e.g. from a gameplay
+/// library
+///
+/// Register with [`BindingsFactory::register_bundle`]. The bundle's actions
+/// live in their own namespace (see [`Session::create_action_in`]), and its
+/// defaults are merged into every config loaded by the factory, so the
+/// bundle's controls work out of the box yet remain rebindable.
+pub trait Bundle {
+    /// Namespace containing the bundle's actions
+    fn namespace(&self) -> &str;
+
+    /// Create the bundle's actions in `session`
+    ///
+    /// Actions should be created with [`Session::create_action_in`] using
+    /// [`namespace`](Self::namespace).
+    fn create_actions(&self, session: &mut Session) -> Result<(), DuplicateAction>;
+
+    /// Default bindings and filters, with action names relative to
+    /// [`namespace`](Self::namespace)
+    ///
+    /// Filter parameters are used as-is, so any action names they contain
+    /// must include the namespace.
+    fn defaults(&self) -> Config;
+
+    /// Register any input sources and filters the bundle relies on
+    fn register(&self, factory: &mut BindingsFactory) {
+        let _ = factory;
+    }
+}
+
+impl BindingsFactory {
+    /// Create `bundle`'s actions in `session` and merge its defaults into
+    /// configs loaded by [`load`](Self::load)
+    ///
+    /// Loaded configs take precedence: each action's inputs from a given
+    /// source replace the bundle's defaults, as in
+    /// [`Config::apply_overrides`].
+    pub fn register_bundle(
+        &mut self,
+        session: &mut Session,
+        bundle: &impl Bundle,
+    ) -> Result<(), DuplicateAction> {
+        bundle.register(self);
+        bundle.create_actions(session)?;
+        self.bundles
+            .push(namespaced(&bundle.defaults(), bundle.namespace()));
+        Ok(())
+    }
+
+    /// `config` with bundle defaults applied beneath it
+    pub(crate) fn with_bundles(&self, config: &Config) -> Option<Config> {
+        if self.bundles.is_empty() {
+            return None;
+        }
+        let mut out = Config::default();
+        for defaults in &self.bundles {
+            out.apply_overrides(defaults);
+        }
+        out.apply_overrides(config);
+        Some(out)
+    }
+}
+
+/// Qualify the action names in `config` with `namespace`
+fn namespaced(config: &Config, namespace: &str) -> Config {
+    let qualify = |name: &str| format!("{namespace}{NAMESPACE_SEPARATOR}{name}");
+    Config {
+        include: Vec::new(),
+        devices: config.devices.clone(),
+        sources: config
+            .sources
+            .iter()
+            .map(|source| SourceConfig {
+                ty: source.ty.clone(),
+                bindings: source
+                    .bindings
+                    .iter()
+                    .map(|(action, inputs)| {
+                        let inputs = inputs
+                            .iter()
+                            .map(|input| match input.rsplit_once(" if ") {
+                                Some((input, condition)) => {
+                                    format!("{input} if {}", qualify(condition.trim()))
+                                }
+                                None => input.clone(),
+                            })
+                            .collect();
+                        (qualify(action), inputs)
+                    })
+                    .collect(),
+            })
+            .collect(),
+        filters: config
+            .filters
+            .iter()
+            .map(|filter| FilterConfig {
+                ty: filter.ty.clone(),
+                targets: filter.targets.iter().map(|t| qualify(t)).collect(),
+                params: filter.params.clone(),
+            })
+            .collect(),
+        accessibility: config.accessibility,
+        checksum: None,
+    }
+}
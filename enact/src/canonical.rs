@@ -0,0 +1,54 @@
+//! Canonical spellings of inputs, so that a config reads the same everywhere
+
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+use crate::{Config, Input, lenient};
+
+/// The canonical spelling of the `I` input which `s` refers to, tolerating
+/// trivial formatting differences
+pub(crate) fn input<I: Input>(s: &str) -> Option<String> {
+    let s = s.trim();
+    let mut inputs = I::from_str(s);
+    if inputs.is_empty() {
+        inputs = lenient::parse_input(s);
+    }
+    Some(inputs.first()?.to_string())
+}
+
+/// The canonical spellings of [`Input::names`], sorted and deduplicated
+pub(crate) fn names<I: Input>() -> Vec<String> {
+    let mut out = I::names()
+        .iter()
+        .filter_map(|name| input::<I>(name))
+        .collect::<Vec<_>>();
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// Replace each input in `config` with `canonical(source, input)`, where
+/// available
+pub(crate) fn canonicalize(
+    config: &Config,
+    canonical: impl Fn(&str, &str) -> Option<String>,
+) -> Config {
+    let mut out = config.clone();
+    for source in &mut out.sources {
+        for (_, inputs) in &mut source.bindings {
+            for input in inputs {
+                let (name, condition) = match input.rsplit_once(" if ") {
+                    Some((name, condition)) => (name, Some(condition)),
+                    None => (&**input, None),
+                };
+                let Some(name) = canonical(&source.ty, name) else {
+                    continue;
+                };
+                *input = match condition {
+                    Some(condition) => format!("{name} if {}", condition.trim()),
+                    None => name.to_owned(),
+                };
+            }
+        }
+    }
+    out
+}
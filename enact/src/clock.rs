@@ -0,0 +1,204 @@
+use alloc::sync::Arc;
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+#[cfg(feature = "std")]
+use std::{sync::OnceLock, time::Instant};
+
+#[cfg(feature = "std")]
+use crate::sync::Mutex;
+
+/// A source of time for a [`Seat`](crate::Seat)
+///
+/// Used to timestamp state changes and by time-aware [`Filter`](crate::Filter)s.
+/// Replacing the default [`SystemClock`] with a [`ManualClock`] allows tests
+/// and replays to run on virtual time, for reproducible results, and wrapping
+/// it in a [`ScaledClock`] lets game pauses and slow motion apply to input
+/// timing too. Without the `std` feature there is no default, and time stands
+/// still until a clock is set.
+pub trait Clock: Send + Sync + 'static {
+    /// Time elapsed since an arbitrary fixed epoch
+    ///
+    /// Must never decrease.
+    fn now(&self) -> Duration;
+}
+
+/// Monotonic wall-clock time
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        static EPOCH: OnceLock<Instant> = OnceLock::new();
+        EPOCH.get_or_init(Instant::now).elapsed()
+    }
+}
+
+/// A clock which only advances when told to
+///
+/// Clones share the same time, so one can be given to a
+/// [`Seat`](crate::Seat) while another is used to drive it.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    /// Nanoseconds since the epoch
+    nanos: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Create a clock reading zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the clock forward by `duration`
+    ///
+    /// Saturates at roughly 584 years past the epoch.
+    pub fn advance(&self, duration: Duration) {
+        let duration = nanos(duration);
+        let _ = self
+            .nanos
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |now| {
+                Some(now.saturating_add(duration))
+            });
+    }
+
+    /// Set the current time
+    ///
+    /// Must not move the clock backwards. Saturates like
+    /// [`advance`](Self::advance).
+    pub fn set(&self, now: Duration) {
+        self.nanos.store(nanos(now), Ordering::Relaxed);
+    }
+}
+
+/// `duration` in nanoseconds, saturating at `u64::MAX`
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+}
+
+/// A clock which can be paused or slowed down, for measuring input timing in
+/// game time
+///
+/// Wraps another clock, e.g. [`SystemClock`]. While paused, time stands
+/// still, so that hold timers, auto-repeat, and buffering windows freeze
+/// during a game pause instead of firing as soon as it ends. Clones share
+/// the same state, so one can be given to a [`Seat`](crate::Seat) while
+/// another is used to control it.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct ScaledClock<C = SystemClock> {
+    inner: C,
+    state: Arc<Mutex<ScaledState>>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct ScaledState {
+    /// Scaled time as of the last change in rate
+    scaled: Duration,
+    /// Time according to the inner clock as of the last change in rate
+    anchor: Duration,
+    scale: f64,
+    paused: bool,
+}
+
+#[cfg(feature = "std")]
+impl Default for ScaledState {
+    fn default() -> Self {
+        Self {
+            scaled: Duration::ZERO,
+            anchor: Duration::ZERO,
+            scale: 1.0,
+            paused: false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ScaledState {
+    fn now(&self, inner: Duration) -> Duration {
+        if self.paused {
+            return self.scaled;
+        }
+        let elapsed = inner.saturating_sub(self.anchor).as_secs_f64() * self.scale;
+        // Saturate rather than panic if a large scale overflows `Duration`
+        let elapsed = Duration::try_from_secs_f64(elapsed).unwrap_or(Duration::MAX);
+        self.scaled.saturating_add(elapsed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Clock> ScaledClock<C> {
+    /// Run at the same rate as `inner`, from the current time
+    pub fn new(inner: C) -> Self {
+        let anchor = inner.now();
+        Self {
+            inner,
+            state: Arc::new(Mutex::new(ScaledState {
+                scaled: anchor,
+                anchor,
+                ..ScaledState::default()
+            })),
+        }
+    }
+
+    /// Stop or restart time
+    ///
+    /// The rate set by [`set_scale`](Self::set_scale) is kept while paused.
+    pub fn set_paused(&self, paused: bool) {
+        self.update(|state| state.paused = paused);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().paused
+    }
+
+    /// Run at `scale` times the rate of the inner clock, e.g. 0.5 for slow
+    /// motion
+    ///
+    /// Panics if `scale` is negative or not finite
+    pub fn set_scale(&self, scale: f64) {
+        assert!(
+            scale.is_finite() && scale >= 0.0,
+            "invalid clock scale: {scale}"
+        );
+        self.update(|state| state.scale = scale);
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.state.lock().scale
+    }
+
+    /// Apply `f` to the state, measuring time from now at the new rate
+    fn update(&self, f: impl FnOnce(&mut ScaledState)) {
+        let mut state = self.state.lock();
+        let inner = self.inner.now();
+        state.scaled = state.now(inner);
+        state.anchor = inner;
+        f(&mut state);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Clock + Default> Default for ScaledClock<C> {
+    fn default() -> Self {
+        Self::new(C::default())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Clock> Clock for ScaledClock<C> {
+    fn now(&self) -> Duration {
+        let state = self.state.lock();
+        state.now(self.inner.now())
+    }
+}
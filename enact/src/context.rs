@@ -0,0 +1,204 @@
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+use core::{any::TypeId, marker::PhantomData};
+
+use crate::{Action, ActionId, Seat, Session, vector::VectorType};
+
+/// Mutually exclusive sets of actions, such as gameplay and menu controls
+///
+/// At most one context is active at a time. Switching contexts with
+/// [`enter`](Self::enter) denies the actions of every other context in a
+/// [`Seat`] (see [`Seat::set_denied`]) and neutralizes those of the context
+/// being left: `bool` actions become `false`, and `f64` and
+/// [`Vector2`](crate::Vector2) actions zero, so that e.g. opening a menu
+/// while running doesn't leave movement stuck. Actions shared with the
+/// context being entered, and actions in no context, are unaffected. Hooks
+/// added with [`on_enter`](Self::on_enter) and [`on_exit`](Self::on_exit)
+/// run on each transition, for any further cleanup.
+///
+/// Tracks the active context of a single seat; use one per seat.
+#[derive(Default)]
+pub struct Contexts {
+    contexts: Vec<Context>,
+    active: Option<usize>,
+}
+
+struct Context {
+    name: String,
+    /// Each action and how to neutralize it, if possible
+    actions: Vec<(ActionId, Option<Resting>)>,
+    on_enter: Vec<Hook>,
+    on_exit: Vec<Hook>,
+}
+
+impl Context {
+    fn contains(&self, id: ActionId) -> bool {
+        self.actions.iter().any(|&(x, _)| x == id)
+    }
+}
+
+type Hook = Box<dyn FnMut(&mut Seat) + Send>;
+
+impl Contexts {
+    /// Create an empty set of contexts, none of which is active
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a context named `name` containing `actions`
+    ///
+    /// Replaces any existing context with the same name. Actions may belong
+    /// to several contexts.
+    ///
+    /// Panics if any of `actions` was not defined in `session`
+    pub fn add(
+        &mut self,
+        session: &Session,
+        name: &str,
+        actions: impl IntoIterator<Item = ActionId>,
+    ) {
+        let actions = actions
+            .into_iter()
+            .map(|id| (id, Resting::of(session.definition(id).ty)))
+            .collect();
+        match self.contexts.iter_mut().find(|x| x.name == name) {
+            Some(context) => context.actions = actions,
+            None => self.contexts.push(Context {
+                name: name.to_owned(),
+                actions,
+                on_enter: Vec::new(),
+                on_exit: Vec::new(),
+            }),
+        }
+    }
+
+    /// Declare a context containing the actions in `namespace`, named after
+    /// it
+    ///
+    /// See [`Session::create_action_in`].
+    pub fn add_namespace(&mut self, session: &Session, namespace: &str) {
+        self.add(session, namespace, session.actions_in(namespace));
+    }
+
+    /// Run `hook` after the context named `name` is entered
+    ///
+    /// Returns `false` if there is no such context.
+    pub fn on_enter(&mut self, name: &str, hook: impl FnMut(&mut Seat) + Send + 'static) -> bool {
+        let Some(context) = self.contexts.iter_mut().find(|x| x.name == name) else {
+            return false;
+        };
+        context.on_enter.push(Box::new(hook));
+        true
+    }
+
+    /// Run `hook` after the context named `name` is left and its actions
+    /// neutralized
+    ///
+    /// Returns `false` if there is no such context.
+    pub fn on_exit(&mut self, name: &str, hook: impl FnMut(&mut Seat) + Send + 'static) -> bool {
+        let Some(context) = self.contexts.iter_mut().find(|x| x.name == name) else {
+            return false;
+        };
+        context.on_exit.push(Box::new(hook));
+        true
+    }
+
+    /// Name of the active context, if any
+    pub fn active(&self) -> Option<&str> {
+        Some(&self.contexts[self.active?].name)
+    }
+
+    /// Make the context named `name` active in `seat`
+    ///
+    /// Also used to apply the initial context to a new seat. Entering the
+    /// active context again has no effect. Returns `false`, changing nothing,
+    /// if there is no such context.
+    pub fn enter(&mut self, name: &str, seat: &mut Seat) -> bool {
+        let Some(next) = self.contexts.iter().position(|x| x.name == name) else {
+            return false;
+        };
+        if self.active == Some(next) {
+            return true;
+        }
+        if let Some(prev) = self.active {
+            // Neutralize while still permitted
+            let (leaving, entering) = (&self.contexts[prev], &self.contexts[next]);
+            for &(id, resting) in &leaving.actions {
+                if let Some(resting) = resting
+                    && !entering.contains(id)
+                {
+                    resting.apply(seat, id);
+                }
+            }
+            for hook in &mut self.contexts[prev].on_exit {
+                hook(seat);
+            }
+        }
+        for (i, context) in self.contexts.iter().enumerate() {
+            if i != next {
+                for &(id, _) in &context.actions {
+                    seat.set_denied(id, true);
+                }
+            }
+        }
+        for &(id, _) in &self.contexts[next].actions {
+            seat.set_denied(id, false);
+        }
+        self.active = Some(next);
+        for hook in &mut self.contexts[next].on_enter {
+            hook(seat);
+        }
+        true
+    }
+}
+
+/// The resting state of an action
+#[derive(Copy, Clone)]
+enum Resting {
+    /// `false`
+    Bool,
+    /// Zero
+    F64,
+    /// Zero
+    Vector(VectorType),
+}
+
+impl Resting {
+    fn of(ty: TypeId) -> Option<Self> {
+        if ty == TypeId::of::<bool>() {
+            Some(Resting::Bool)
+        } else if ty == TypeId::of::<f64>() {
+            Some(Resting::F64)
+        } else {
+            VectorType::find(ty).map(Resting::Vector)
+        }
+    }
+
+    /// Return `id` to rest in `seat`, if it isn't already
+    fn apply(self, seat: &mut Seat, id: ActionId) {
+        match self {
+            Resting::Bool => {
+                let action = Action::<bool> {
+                    id,
+                    _marker: PhantomData,
+                };
+                if seat.get(action) == Some(true) {
+                    _ = seat.push(id, false);
+                }
+            }
+            Resting::F64 => {
+                let action = Action::<f64> {
+                    id,
+                    _marker: PhantomData,
+                };
+                if seat.get(action).is_some_and(|x| x != 0.0) {
+                    _ = seat.push(id, 0.0);
+                }
+            }
+            Resting::Vector(ty) => {
+                if ty.get(seat, id).is_some_and(|x| x != [0.0; 2]) {
+                    _ = ty.push(seat, id, [0.0; 2]);
+                }
+            }
+        }
+    }
+}
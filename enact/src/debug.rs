@@ -0,0 +1,111 @@
+//! Inspection of live action state, for debug overlays
+
+use alloc::{format, string::String, sync::Arc, vec::Vec};
+use core::{
+    any::{Any, TypeId},
+    fmt::Debug,
+};
+
+use crate::{ActionId, Seat, Session};
+
+/// Formats a value of an action's type
+pub(crate) type Formatter = fn(&dyn Any) -> String;
+
+/// State of a single action, as reported by [`Seat::debug_snapshot`]
+#[derive(Debug, Clone)]
+pub struct ActionSnapshot {
+    pub id: ActionId,
+    pub name: String,
+    /// Name of the action's Rust type
+    pub type_name: &'static str,
+    /// The latest value, formatted with [`Debug`], or `None` if the type has
+    /// no formatter
+    ///
+    /// See [`Session::register_debug`].
+    pub value: Option<String>,
+    /// Number of changes waiting to be [`poll`](Seat::poll)ed
+    pub queued: usize,
+}
+
+impl Session {
+    /// Allow [`Seat::debug_snapshot`] to format values of type `T`
+    ///
+    /// Primitive types, `String`, and `mint` vectors are supported by default.
+    pub fn register_debug<T: Debug + 'static>(&mut self) {
+        Arc::make_mut(&mut self.formatters).insert(TypeId::of::<T>(), format::<T>);
+        self.modified();
+    }
+
+    fn formatter(&self, ty: TypeId) -> Option<Formatter> {
+        if let Some(&f) = self.formatters.get(&ty) {
+            return Some(f);
+        }
+        macro_rules! builtin {
+            ($($ty:ty),*) => {
+                $(if ty == TypeId::of::<$ty>() {
+                    return Some(format::<$ty>);
+                })*
+            };
+        }
+        builtin!(
+            (),
+            bool,
+            f32,
+            f64,
+            i8,
+            i16,
+            i32,
+            i64,
+            u8,
+            u16,
+            u32,
+            u64,
+            usize,
+            isize,
+            char,
+            String,
+            mint::Vector2<f32>,
+            mint::Vector2<f64>,
+            mint::Vector3<f32>,
+            mint::Vector3<f64>
+        );
+        None
+    }
+}
+
+fn format<T: Debug + 'static>(value: &dyn Any) -> String {
+    format!("{:?}", value.downcast_ref::<T>().expect("type mismatch"))
+}
+
+impl Seat {
+    /// Describe the state of every action in `session` that has any, in order
+    /// of [`ActionId`]
+    ///
+    /// Lets debug overlays show live input state without knowing every action's
+    /// type.
+    pub fn debug_snapshot(&self, session: &Session) -> Vec<ActionSnapshot> {
+        self.state
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let slot = slot.as_ref()?;
+                let id = ActionId {
+                    index: index as u32,
+                    generation: slot.generation,
+                };
+                if !session.contains(id) {
+                    return None;
+                }
+                let definition = session.definition(id);
+                let state = slot.state.get().read();
+                Some(ActionSnapshot {
+                    id,
+                    name: definition.name.clone(),
+                    type_name: definition.ty_name,
+                    value: session.formatter(definition.ty).map(|f| f(state.latest())),
+                    queued: state.queued(),
+                })
+            })
+            .collect()
+    }
+}
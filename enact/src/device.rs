@@ -0,0 +1,143 @@
+//! Names for specific physical devices, so that bindings stay attached to the
+//! right hardware in multi-device setups
+
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Bindings;
+
+/// A name for devices matching certain criteria, from [`Config::devices`]
+///
+/// Backends which distinguish between devices, like `enact-hid`'s joysticks,
+/// accept a label for each device; look one up with
+/// [`Bindings::device_alias`] so that bindings can refer to e.g. `js
+/// left_stick axis 0` regardless of the order in which devices were connected.
+///
+/// Every criterion that is set must match. An alias with no criteria matches
+/// nothing.
+///
+/// [`Config::devices`]: crate::Config::devices
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceAlias {
+    /// Label referenced in bindings, e.g. `left_joycon`
+    pub alias: String,
+    /// USB vendor ID
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub vendor_id: Option<u16>,
+    /// USB product ID
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub product_id: Option<u16>,
+    /// Pattern matched against the device's name, ignoring ASCII case, where
+    /// `*` matches any sequence of characters
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub name: Option<String>,
+    /// Serial number, to tell apart several devices of the same model
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub serial: Option<String>,
+}
+
+impl DeviceAlias {
+    /// Whether `device` satisfies every criterion of this alias
+    pub fn matches(&self, device: &DeviceInfo) -> bool {
+        if self.vendor_id.is_none()
+            && self.product_id.is_none()
+            && self.name.is_none()
+            && self.serial.is_none()
+        {
+            return false;
+        }
+        self.vendor_id.is_none_or(|x| device.vendor_id == Some(x))
+            && self.product_id.is_none_or(|x| device.product_id == Some(x))
+            && self
+                .name
+                .as_ref()
+                .is_none_or(|pattern| wildcard_match(pattern, &device.name))
+            && self
+                .serial
+                .as_ref()
+                .is_none_or(|x| device.serial.as_ref() == Some(x))
+    }
+}
+
+/// Identifying details of a connected device, as reported by the platform
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceInfo {
+    pub name: String,
+    pub vendor_id: Option<u16>,
+    pub product_id: Option<u16>,
+    pub serial: Option<String>,
+}
+
+impl Bindings {
+    /// Label for `device`, from the first alias it matches
+    ///
+    /// Pass the result to a backend's per-device handler when the device is
+    /// connected.
+    pub fn device_alias(&self, device: &DeviceInfo) -> Option<&str> {
+        self.devices
+            .iter()
+            .find(|alias| alias.matches(device))
+            .map(|alias| &*alias.alias)
+    }
+
+    /// Device aliases, in the order they're matched
+    pub fn device_aliases(&self) -> &[DeviceAlias] {
+        &self.devices
+    }
+
+    /// Define a device alias, replacing any existing alias with the same label
+    ///
+    /// Saved by [`save`](Self::save), so e.g. assignments made in a device
+    /// setup menu persist across sessions.
+    pub fn set_device_alias(&mut self, alias: DeviceAlias) {
+        match self.devices.iter_mut().find(|x| x.alias == alias.alias) {
+            Some(existing) => *existing = alias,
+            None => self.devices.push(alias),
+        }
+    }
+
+    /// Remove the device alias labeled `alias`, if any
+    pub fn remove_device_alias(&mut self, alias: &str) -> Option<DeviceAlias> {
+        let i = self.devices.iter().position(|x| x.alias == alias)?;
+        Some(self.devices.remove(i))
+    }
+}
+
+/// Whether `s` matches `pattern`, ignoring ASCII case, where `*` in `pattern`
+/// matches any sequence of characters
+fn wildcard_match(pattern: &str, s: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let s = s.to_ascii_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = s.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((&last, middle)) = parts.split_last() else {
+        // No wildcards
+        return rest.is_empty();
+    };
+    for part in middle {
+        let Some(i) = rest.find(part) else {
+            return false;
+        };
+        rest = &rest[i + part.len()..];
+    }
+    rest.ends_with(last)
+}
@@ -0,0 +1,57 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{ActionId, FxHashMap, Seat};
+
+type Hook = Box<dyn FnMut(ActionId)>;
+
+/// Calls hooks for actions whose state changed, as a [`Seat`] is flushed
+///
+/// An alternative to polling every action individually, for applications
+/// which prefer to react to input through callbacks. Hooks are only told
+/// which action changed; they may look up its latest state with
+/// [`Seat::get`] after [`flush`](Self::flush) returns.
+#[derive(Default)]
+pub struct Dispatcher {
+    hooks: FxHashMap<ActionId, Vec<Hook>>,
+    any: Vec<Hook>,
+}
+
+impl Dispatcher {
+    /// Create a dispatcher with no hooks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `hook` whenever `action` had unconsumed state changes when
+    /// flushed
+    pub fn on(&mut self, action: ActionId, hook: impl FnMut(ActionId) + 'static) {
+        self.hooks.entry(action).or_default().push(Box::new(hook));
+    }
+
+    /// Call `hook` for every action which had unconsumed state changes when
+    /// flushed
+    pub fn on_any(&mut self, hook: impl FnMut(ActionId) + 'static) {
+        self.any.push(Box::new(hook));
+    }
+
+    /// Remove all hooks registered for `action` with [`on`](Self::on)
+    pub fn remove(&mut self, action: ActionId) {
+        self.hooks.remove(&action);
+    }
+
+    /// Flush `seat`, calling hooks for each action which had unconsumed
+    /// state changes
+    ///
+    /// Replaces [`Seat::flush`]. Hooks for a specific action run before those
+    /// registered with [`on_any`](Self::on_any), each in registration order.
+    pub fn flush(&mut self, seat: &mut Seat) {
+        seat.flush_with(|action| {
+            for hook in self.hooks.get_mut(&action).into_iter().flatten() {
+                hook(action);
+            }
+            for hook in &mut self.any {
+                hook(action);
+            }
+        });
+    }
+}
@@ -0,0 +1,213 @@
+//! Undoable, transactional changes to [`Bindings`]
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    any::{Any, TypeId},
+    ops::Deref,
+};
+
+use crate::{
+    Action, ActionId, AnyFilter, BindError, Binding, Bindings, Filter, FilterCycle, FilterId,
+    Input, InputBindings, Session,
+};
+
+/// A transaction over [`Bindings`], from [`Bindings::edit`]
+///
+/// Changes take effect immediately, so the bindings can be inspected through
+/// [`Deref`] as they're edited, and can be undone and redone one at a time.
+/// [`commit`](Self::commit) keeps them, while [`rollback`](Self::rollback), or
+/// dropping the edit, reverts every change. Only the bindings of affected
+/// inputs are recorded, so large bindings aren't copied per change. Useful for
+/// settings screens with Cancel and Apply buttons.
+pub struct BindingsEdit<'a> {
+    bindings: &'a mut Bindings,
+    done: Vec<Change>,
+    undone: Vec<Change>,
+}
+
+/// A reversible change
+struct Change {
+    undo: Box<dyn Fn(&mut Bindings)>,
+    redo: Box<dyn Fn(&mut Bindings)>,
+}
+
+impl Bindings {
+    /// Begin a transaction
+    pub fn edit(&mut self) -> BindingsEdit<'_> {
+        BindingsEdit {
+            bindings: self,
+            done: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    /// The bindings of `input`
+    fn input_bindings<I: Input>(&self, input: &I) -> Vec<Binding> {
+        self.actions
+            .get(&TypeId::of::<I>())
+            .and_then(|x| (&**x as &dyn Any).downcast_ref::<InputBindings<I>>())
+            .and_then(|x| x.bindings.get(input))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replace the bindings of `input`
+    fn set_input_bindings<I: Input>(&mut self, input: &I, bindings: &[Binding]) {
+        let Some(x) = self.actions.get_mut(&TypeId::of::<I>()) else {
+            for &binding in bindings {
+                self.insert_binding(input.clone(), binding);
+            }
+            return;
+        };
+        let x = (&mut **x as &mut dyn Any)
+            .downcast_mut::<InputBindings<I>>()
+            .unwrap();
+        if bindings.is_empty() {
+            x.bindings.remove(input);
+        } else {
+            x.bindings.insert(input.clone(), bindings.to_vec());
+        }
+    }
+}
+
+impl BindingsEdit<'_> {
+    /// See [`Bindings::bind`]
+    pub fn bind<I: Input>(
+        &mut self,
+        input: I,
+        action: ActionId,
+        session: &Session,
+    ) -> Result<(), BindError> {
+        self.change_input(&input.clone(), |b| b.bind(input, action, session))
+    }
+
+    /// See [`Bindings::bind_if`]
+    pub fn bind_if<I: Input>(
+        &mut self,
+        input: I,
+        action: ActionId,
+        condition: Action<bool>,
+        session: &Session,
+    ) -> Result<(), BindError> {
+        self.change_input(&input.clone(), |b| {
+            b.bind_if(input, action, condition, session)
+        })
+    }
+
+    /// See [`Bindings::unbind`]
+    pub fn unbind<I: Input>(&mut self, input: &I, action: ActionId) -> bool {
+        self.change_input(input, |b| b.unbind(input, action))
+    }
+
+    /// See [`Bindings::unbind_all`]
+    pub fn unbind_all<I: Input>(&mut self, input: &I) -> bool {
+        self.change_input(input, |b| b.unbind_all(input))
+    }
+
+    /// See [`Bindings::add_filter`]
+    pub fn add_filter<F: Filter>(
+        &mut self,
+        filter: F,
+        session: &Session,
+    ) -> Result<FilterId, FilterCycle> {
+        let id = self.bindings.add_filter(filter.clone(), session)?;
+        self.record(Change {
+            undo: Box::new(move |b| b.remove_filter(id)),
+            redo: Box::new(move |b| {
+                b.add_any_filter(Box::new(filter.clone())).unwrap();
+            }),
+        });
+        Ok(id)
+    }
+
+    /// See [`Bindings::remove_filter`]
+    pub fn remove_filter(&mut self, filter: FilterId) {
+        let removed = AnyFilter::clone(&**self.bindings.filters.get(filter.0 as usize).unwrap());
+        self.bindings.remove_filter(filter);
+        self.record(Change {
+            // Slab reuses the most recently freed index, so undoing in order
+            // restores the original ID
+            undo: Box::new(move |b| {
+                b.add_any_filter(AnyFilter::clone(&*removed)).unwrap();
+            }),
+            redo: Box::new(move |b| b.remove_filter(filter)),
+        });
+    }
+
+    /// Revert the most recent change, returning whether there was one
+    pub fn undo(&mut self) -> bool {
+        let Some(change) = self.done.pop() else {
+            return false;
+        };
+        (change.undo)(self.bindings);
+        self.undone.push(change);
+        true
+    }
+
+    /// Reapply the most recently undone change, returning whether there was
+    /// one
+    ///
+    /// Making a new change discards any undone changes.
+    pub fn redo(&mut self) -> bool {
+        let Some(change) = self.undone.pop() else {
+            return false;
+        };
+        (change.redo)(self.bindings);
+        self.done.push(change);
+        true
+    }
+
+    /// Whether [`undo`](Self::undo) would have an effect
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    /// Whether [`redo`](Self::redo) would have an effect
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Keep all changes
+    pub fn commit(mut self) {
+        self.done.clear();
+    }
+
+    /// Revert all changes
+    pub fn rollback(self) {}
+
+    /// Perform `f`, recording its effect on the bindings of `input`
+    fn change_input<I: Input, R>(&mut self, input: &I, f: impl FnOnce(&mut Bindings) -> R) -> R {
+        let before = self.bindings.input_bindings(input);
+        let result = f(self.bindings);
+        let after = self.bindings.input_bindings(input);
+        if before == after {
+            return result;
+        }
+        let input2 = input.clone();
+        let input = input.clone();
+        self.record(Change {
+            undo: Box::new(move |b| b.set_input_bindings(&input, &before)),
+            redo: Box::new(move |b| b.set_input_bindings(&input2, &after)),
+        });
+        result
+    }
+
+    fn record(&mut self, change: Change) {
+        self.undone.clear();
+        self.done.push(change);
+    }
+}
+
+impl Deref for BindingsEdit<'_> {
+    type Target = Bindings;
+
+    fn deref(&self) -> &Bindings {
+        self.bindings
+    }
+}
+
+impl Drop for BindingsEdit<'_> {
+    fn drop(&mut self) {
+        while self.undo() {}
+    }
+}
@@ -0,0 +1,153 @@
+//! Detection of configs which synthesize inputs, for fair-play policies
+
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use core::time::Duration;
+
+use crate::{
+    Config, Filter,
+    filter::{self, Macro, MacroValue},
+};
+
+/// A filter in a [`Config`] which synthesizes inputs, from
+/// [`Config::synthesized_inputs`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SynthesisFlag {
+    /// Index into [`Config::filters`]
+    pub filter: usize,
+    pub kind: SynthesisKind,
+}
+
+/// How a [`SynthesisFlag`]'s filter synthesizes inputs
+#[derive(Debug, Clone, PartialEq)]
+pub enum SynthesisKind {
+    /// A [`Macro`] plays back a sequence of inputs from a single trigger
+    Macro {
+        trigger: String,
+        steps: usize,
+        /// Time from the trigger to the last step
+        duration: Duration,
+    },
+    /// A [`Macro`] presses the same action repeatedly
+    ///
+    /// Presses are pushes of `true` to an `Action<bool>` not already `true`
+    /// within the macro, and every push to an `Action<()>`.
+    Turbo {
+        action: String,
+        presses: usize,
+        /// Shortest time between consecutive presses
+        interval: Duration,
+    },
+    /// A [`Macro`] fires the trigger of a macro, possibly its own, so that
+    /// playback can chain or loop indefinitely
+    Chained { trigger: String },
+}
+
+impl Config {
+    /// Find filters which synthesize rapid or repeated inputs, such as macro
+    /// playback and turbo
+    ///
+    /// Lets multiplayer titles enforce fair-play policies on user configs,
+    /// e.g. by rejecting configs with any [`SynthesisKind::Turbo`] faster than
+    /// a human could press. Only filters registered with [`Macro::NAME`] are
+    /// recognized. Inspects the config as written, without resolving
+    /// [`include`](Self::include)s, so it should be applied to each included
+    /// config too.
+    pub fn synthesized_inputs(&self) -> Vec<SynthesisFlag> {
+        let macros = self
+            .filters
+            .iter()
+            .enumerate()
+            .filter(|(_, filter)| filter.ty == Macro::NAME)
+            .filter_map(|(index, filter)| {
+                let (trigger, steps) = filter.params.split_first()?;
+                Some((index, trigger.trim(), parse_steps(steps)))
+            })
+            .collect::<Vec<(usize, &str, Vec<Step<'_>>)>>();
+
+        let mut out = Vec::new();
+        for &(filter, trigger, ref steps) in &macros {
+            out.push(SynthesisFlag {
+                filter,
+                kind: SynthesisKind::Macro {
+                    trigger: trigger.to_owned(),
+                    steps: steps.len(),
+                    duration: steps.last().map_or(Duration::ZERO, |x| x.at),
+                },
+            });
+
+            // Time of each press of each action, in order of first press
+            let mut presses = Vec::<(&str, Vec<Duration>)>::new();
+            let mut held = Vec::new();
+            for step in steps {
+                let pressed = match step.value {
+                    MacroValue::Unit => true,
+                    MacroValue::Bool(true) => !held.contains(&step.action),
+                    _ => false,
+                };
+                match step.value {
+                    MacroValue::Bool(true) => held.push(step.action),
+                    MacroValue::Unit => {}
+                    _ => held.retain(|&x| x != step.action),
+                }
+                if !pressed {
+                    continue;
+                }
+                match presses.iter_mut().find(|(x, _)| *x == step.action) {
+                    Some((_, times)) => times.push(step.at),
+                    None => presses.push((step.action, vec![step.at])),
+                }
+            }
+            for (action, times) in presses {
+                let Some(interval) = times.windows(2).map(|x| x[1] - x[0]).min() else {
+                    continue;
+                };
+                out.push(SynthesisFlag {
+                    filter,
+                    kind: SynthesisKind::Turbo {
+                        action: action.to_owned(),
+                        presses: times.len(),
+                        interval,
+                    },
+                });
+            }
+
+            let mut fired = Vec::new();
+            for step in steps {
+                if !fired.contains(&step.action)
+                    && macros.iter().any(|&(_, trigger, _)| trigger == step.action)
+                {
+                    fired.push(step.action);
+                    out.push(SynthesisFlag {
+                        filter,
+                        kind: SynthesisKind::Chained {
+                            trigger: step.action.to_owned(),
+                        },
+                    });
+                }
+            }
+        }
+        out
+    }
+}
+
+/// A step of a [`Macro`]'s config
+struct Step<'a> {
+    /// Time since the trigger
+    at: Duration,
+    action: &'a str,
+    value: MacroValue,
+}
+
+/// Parse steps as [`Macro`] does, accumulating their times
+fn parse_steps(params: &[String]) -> Vec<Step<'_>> {
+    let mut at = Duration::ZERO;
+    params
+        .iter()
+        .filter_map(|param| {
+            let (delay, rest) = filter::split_step(param)?;
+            at = at.checked_add(delay)?;
+            let (action, value) = filter::split_value(rest);
+            Some(Step { at, action, value })
+        })
+        .collect()
+}
@@ -1,14 +1,29 @@
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::{ops::RangeInclusive, time::Duration};
+
+// Test builds link std, whose inherent methods take precedence
+#[cfg(not(any(feature = "std", test)))]
+use crate::float::F64Ext as _;
+
 use crate::{
     Action, ActionId, DuplicateAction, Filter, FilterConfig, FilterLoadError, Seat, Session,
+    Vector2, float, vector::VectorType,
 };
 
-/// Converts four directional inputs into a single [`mint::Vector2<f64>`]
+/// Converts four directional inputs into a single vector
 ///
 /// Source action names are derived by suffixing `-up`/`-left`/`-down`/`-right`
-/// to the target action name
+/// to the target action name. The target may have any [`Vector2`] type.
 #[derive(Clone)]
 pub struct DPad {
-    target: Action<mint::Vector2<f64>>,
+    target: VectorTarget,
 
     up: Action<bool>,
     left: Action<bool>,
@@ -17,9 +32,9 @@ pub struct DPad {
 }
 
 impl DPad {
-    pub fn new(
+    pub fn new<V: Vector2>(
         session: &mut Session,
-        target: Action<mint::Vector2<f64>>,
+        target: Action<V>,
     ) -> Result<Self, DuplicateAction> {
         let [up, left, down, right] = DPAD_DIRS.map(|dir| {
             let o = session.action_name(target.id());
@@ -27,7 +42,7 @@ impl DPad {
         });
 
         Ok(Self {
-            target,
+            target: VectorTarget::new(target),
             up: up?,
             left: left?,
             down: down?,
@@ -68,29 +83,14 @@ impl Filter for DPad {
 
     fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
         let o = &*cfg.targets[0];
-        let [up, left, down, right] = DPAD_DIRS.map(|dir| {
-            session
-                .action::<bool>(session.action_id(&format!("{o}-{dir}")).unwrap())
-                .unwrap()
-        });
+        let [up, left, down, right] =
+            DPAD_DIRS.map(|dir| filter_action(session, Self::NAME, &format!("{o}-{dir}")));
         Ok(Self {
-            target: session
-                .action(
-                    session
-                        .action_id(o)
-                        .ok_or_else(|| FilterLoadError::UnknownTarget {
-                            output: o.to_owned(),
-                        })?,
-                )
-                .map_err(|e| FilterLoadError::TypeError {
-                    filter_ty: Self::NAME.to_owned(),
-                    action: o.to_owned(),
-                    error: e,
-                })?,
-            up,
-            left,
-            down,
-            right,
+            target: VectorTarget::load(session, Self::NAME, o)?,
+            up: up?,
+            left: left?,
+            down: down?,
+            right: right?,
         })
     }
 
@@ -98,6 +98,7 @@ impl Filter for DPad {
         FilterConfig {
             ty: Self::NAME.to_owned(),
             targets: vec![session.action_name(self.target.id()).to_owned()],
+            params: Vec::new(),
         }
     }
 
@@ -117,9 +118,2051 @@ impl Filter for DPad {
             - seat.get(self.left).unwrap_or_default() as u64 as f64;
         let y = seat.get(self.up).unwrap_or_default() as u64 as f64
             - seat.get(self.down).unwrap_or_default() as u64 as f64;
-        seat.push(self.target.id(), mint::Vector2::<f64>::from([x, y]))
-            .unwrap();
+        self.target.push(seat, [x, y]);
     }
 }
 
 const DPAD_DIRS: [&str; 4] = ["up", "left", "down", "right"];
+
+/// A target action of any [`Vector2`] type
+#[derive(Copy, Clone)]
+struct VectorTarget {
+    id: ActionId,
+    ty: VectorType,
+}
+
+impl VectorTarget {
+    fn new<V: Vector2>(action: Action<V>) -> Self {
+        Self {
+            id: action.id(),
+            ty: VectorType::of::<V>(),
+        }
+    }
+
+    /// Look up the target action `name` of a filter of type `filter_ty`
+    fn load(session: &Session, filter_ty: &str, name: &str) -> Result<Self, FilterLoadError> {
+        let id = session
+            .action_id(name)
+            .ok_or_else(|| FilterLoadError::UnknownTarget {
+                output: name.to_owned(),
+            })?;
+        match VectorType::find(session.definition(id).ty) {
+            Some(ty) => Ok(Self { id, ty }),
+            None => Err(FilterLoadError::TypeError {
+                filter_ty: filter_ty.to_owned(),
+                action: name.to_owned(),
+                error: session
+                    .action::<mint::Vector2<f64>>(id)
+                    .map(drop)
+                    .unwrap_err(),
+            }),
+        }
+    }
+
+    fn id(&self) -> ActionId {
+        self.id
+    }
+
+    fn get(&self, seat: &Seat) -> Option<[f64; 2]> {
+        self.ty.get(seat, self.id)
+    }
+
+    fn push(&self, seat: &mut Seat, xy: [f64; 2]) {
+        let _ = self.ty.push(seat, self.id, xy);
+    }
+}
+
+/// Produces mouse-drag-style motion, active only while a button is held
+///
+/// Combines a `bool` source, suffixed `-button`, with a motion source,
+/// suffixed `-motion`. While the button is held, the target receives the sum
+/// of motion since the filter was last applied; otherwise it's zero. Useful
+/// for camera orbiting and map panning. The target may have any [`Vector2`]
+/// type.
+///
+/// The motion source is consumed with [`Seat::poll`], so it must remain polled.
+#[derive(Clone)]
+pub struct Drag {
+    target: VectorTarget,
+
+    button: Action<bool>,
+    motion: Action<mint::Vector2<f64>>,
+}
+
+impl Drag {
+    pub fn new<V: Vector2>(
+        session: &mut Session,
+        target: Action<V>,
+    ) -> Result<Self, DuplicateAction> {
+        let o = session.action_name(target.id()).to_owned();
+        Ok(Self {
+            target: VectorTarget::new(target),
+            button: session.create_action(&format!("{o}-button"))?,
+            motion: session.create_action(&format!("{o}-motion"))?,
+        })
+    }
+
+    pub fn button(&self) -> Action<bool> {
+        self.button
+    }
+    pub fn motion(&self) -> Action<mint::Vector2<f64>> {
+        self.motion
+    }
+}
+
+impl Filter for Drag {
+    const NAME: &str = "drag";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 1 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<bool>(&format!("{o}-button"))?;
+        session.create_action::<mint::Vector2<f64>>(&format!("{o}-motion"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        Ok(Self {
+            target: VectorTarget::load(session, Self::NAME, o)?,
+            button: filter_action(session, Self::NAME, &format!("{o}-button"))?,
+            motion: filter_action(session, Self::NAME, &format!("{o}-motion"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![session.action_name(self.target.id()).to_owned()],
+            params: Vec::new(),
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.button.id(), self.motion.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let mut delta = None::<[f64; 2]>;
+        while let Some(motion) = seat.poll(self.motion) {
+            let [x, y] = delta.unwrap_or_default();
+            delta = Some([x + motion.x, y + motion.y]);
+        }
+        match delta {
+            Some(delta) if seat.get(self.button).unwrap_or_default() => {
+                self.target.push(seat, delta);
+            }
+            // Avoid flooding the target with zeroes while the button is released
+            _ if self.target.get(seat).is_some_and(|x| x != [0.0; 2]) => {
+                self.target.push(seat, [0.0; 2]);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Eases an axis toward the direction of two `bool` sources over time
+///
+/// Emulates analog acceleration for digital inputs, e.g. so that keyboard
+/// movement doesn't snap instantly between 0 and 1. Source action names are
+/// derived by suffixing `-positive`/`-negative` to the target action name. The
+/// target, an `f64`, moves toward 1 while only the positive source is held, -1
+/// while only the negative source is held, and 0 otherwise. Moving away from 0
+/// takes `attack` for a full unit, and moving toward 0 takes `release`.
+///
+/// In a [`FilterConfig`], the parameters are the attack and release times in
+/// milliseconds. Ramping proceeds in [`Bindings::update`](crate::Bindings::update),
+/// so it's only as smooth as the rate at which that's called.
+#[derive(Clone)]
+pub struct Ramp {
+    target: Action<f64>,
+    attack: Duration,
+    release: Duration,
+
+    positive: Action<bool>,
+    negative: Action<bool>,
+}
+
+impl Ramp {
+    pub fn new(
+        session: &mut Session,
+        target: Action<f64>,
+        attack: Duration,
+        release: Duration,
+    ) -> Result<Self, DuplicateAction> {
+        let o = session.action_name(target.id()).to_owned();
+        Ok(Self {
+            target,
+            attack,
+            release,
+            positive: session.create_action(&format!("{o}-positive"))?,
+            negative: session.create_action(&format!("{o}-negative"))?,
+        })
+    }
+
+    pub fn positive(&self) -> Action<bool> {
+        self.positive
+    }
+    pub fn negative(&self) -> Action<bool> {
+        self.negative
+    }
+
+    pub fn attack(&self) -> Duration {
+        self.attack
+    }
+    pub fn release(&self) -> Duration {
+        self.release
+    }
+
+    /// Advance `value` toward `goal` over `elapsed`
+    fn step(&self, mut value: f64, goal: f64, mut elapsed: Duration) -> f64 {
+        while value != goal {
+            // Reversing direction passes through 0, at the release rate
+            let (to, period) = if value != 0.0 && (goal - value).signum() != value.signum() {
+                let to = if goal.signum() == value.signum() {
+                    goal
+                } else {
+                    0.0
+                };
+                (to, self.release)
+            } else {
+                (goal, self.attack)
+            };
+            let needed = period.mul_f64((to - value).abs());
+            if needed > elapsed {
+                let progress = elapsed.as_secs_f64() / period.as_secs_f64();
+                return value + progress * (to - value).signum();
+            }
+            elapsed -= needed;
+            value = to;
+        }
+        value
+    }
+}
+
+impl Filter for Ramp {
+    const NAME: &str = "ramp";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 1 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<bool>(&format!("{o}-positive"))?;
+        session.create_action::<bool>(&format!("{o}-negative"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        let target = filter_action::<f64>(session, Self::NAME, o)?;
+        let time = |i: usize| {
+            let param = cfg.params.get(i).map_or("", |x| &**x);
+            param
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|x| Duration::try_from_secs_f64(x / 1000.0).ok())
+                .ok_or_else(|| FilterLoadError::InvalidParam {
+                    filter_ty: Self::NAME.to_owned(),
+                    param: param.to_owned(),
+                })
+        };
+        Ok(Self {
+            target,
+            attack: time(0)?,
+            release: time(1)?,
+            positive: filter_action(session, Self::NAME, &format!("{o}-positive"))?,
+            negative: filter_action(session, Self::NAME, &format!("{o}-negative"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![session.action_name(self.target.id()).to_owned()],
+            params: vec![
+                (self.attack.as_secs_f64() * 1000.0).to_string(),
+                (self.release.as_secs_f64() * 1000.0).to_string(),
+            ],
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.positive.id(), self.negative.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let positive = seat.get(self.positive).unwrap_or_default();
+        let negative = seat.get(self.negative).unwrap_or_default();
+        let goal = positive as u64 as f64 - negative as u64 as f64;
+        let value = seat.get(self.target).unwrap_or_default();
+        if value == goal {
+            return;
+        }
+        // The target is changed at every step, and sources at every change of
+        // goal, so the latest of those is when the ramp last progressed
+        let now = seat.now();
+        let since = [self.target.id(), self.positive.id(), self.negative.id()]
+            .into_iter()
+            .filter_map(|id| seat.last_change(id))
+            .max()
+            .unwrap_or(now);
+        let next = self.step(value, goal, now.saturating_sub(since));
+        if next != value {
+            let _ = seat.push(self.target.id(), next);
+        }
+        if next != goal {
+            seat.wake_at(now, self.positive.id());
+        }
+    }
+}
+
+/// Pulls the direction of a vector toward the nearest of evenly spaced angles
+///
+/// Useful for classic 8-way movement and menu navigation from analog sticks.
+/// The source action name is derived by suffixing `-raw` to the target action
+/// name. `directions` angles are spaced evenly around the circle, starting
+/// along +X. The direction is rotated toward the nearest by `strength`, from 0
+/// for no effect to 1 for snapping exactly, while the magnitude is preserved.
+/// The target may have any [`Vector2`] type.
+///
+/// In a [`FilterConfig`], the parameters are the number of directions and the
+/// strength.
+#[derive(Clone)]
+pub struct Snap {
+    target: VectorTarget,
+    directions: u32,
+    strength: f64,
+
+    raw: Action<mint::Vector2<f64>>,
+}
+
+impl Snap {
+    /// Panics if `directions` is 0 or `strength` isn't between 0 and 1
+    pub fn new<V: Vector2>(
+        session: &mut Session,
+        target: Action<V>,
+        directions: u32,
+        strength: f64,
+    ) -> Result<Self, DuplicateAction> {
+        assert!(directions > 0, "no directions to snap to");
+        assert!((0.0..=1.0).contains(&strength), "strength out of range");
+        let o = session.action_name(target.id()).to_owned();
+        Ok(Self {
+            target: VectorTarget::new(target),
+            directions,
+            strength,
+            raw: session.create_action(&format!("{o}-raw"))?,
+        })
+    }
+
+    pub fn raw(&self) -> Action<mint::Vector2<f64>> {
+        self.raw
+    }
+
+    pub fn directions(&self) -> u32 {
+        self.directions
+    }
+    pub fn strength(&self) -> f64 {
+        self.strength
+    }
+}
+
+impl Filter for Snap {
+    const NAME: &str = "snap";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 1 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<mint::Vector2<f64>>(&format!("{o}-raw"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        let invalid = |i: usize| FilterLoadError::InvalidParam {
+            filter_ty: Self::NAME.to_owned(),
+            param: cfg.params.get(i).cloned().unwrap_or_default(),
+        };
+        let param = |i: usize| cfg.params.get(i).map(|x| x.trim());
+        let directions = param(0)
+            .and_then(|x| x.parse::<u32>().ok())
+            .filter(|&x| x > 0)
+            .ok_or_else(|| invalid(0))?;
+        let strength = param(1)
+            .and_then(|x| x.parse::<f64>().ok())
+            .filter(|x| (0.0..=1.0).contains(x))
+            .ok_or_else(|| invalid(1))?;
+        Ok(Self {
+            target: VectorTarget::load(session, Self::NAME, o)?,
+            directions,
+            strength,
+            raw: filter_action(session, Self::NAME, &format!("{o}-raw"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![session.action_name(self.target.id()).to_owned()],
+            params: vec![self.directions.to_string(), self.strength.to_string()],
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.raw.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let Some(raw) = seat.get(self.raw) else {
+            return;
+        };
+        let length = float::hypot(raw.x, raw.y);
+        if length == 0.0 {
+            self.target.push(seat, [0.0; 2]);
+            return;
+        }
+        let angle = float::atan2(raw.y, raw.x);
+        let spacing = core::f64::consts::TAU / f64::from(self.directions);
+        let nearest = (angle / spacing).round() * spacing;
+        let angle = angle + (nearest - angle) * self.strength;
+        let (y, x) = float::sin_cos(angle);
+        self.target.push(seat, [x * length, y * length]);
+    }
+}
+
+/// Selects one of several sectors of a circle by the direction of a vector
+///
+/// Drives radial menus, e.g. weapon or emote wheels, from a stick or mouse.
+/// The source action name is derived by suffixing `-raw` to the target action
+/// name. The target, a `u8`, receives the index of the sector the source
+/// points into. Sectors are equal in size and numbered clockwise, with sector
+/// 0 centered on +Y. Vectors shorter than `deadzone` leave the selection
+/// unchanged, so that releasing a stick keeps the last sector selected. The
+/// selection only changes once the direction passes more than `hysteresis`
+/// beyond the edge of the selected sector, avoiding flicker along edges.
+///
+/// In a [`FilterConfig`], the parameters are the number of sectors, the
+/// deadzone, and the hysteresis in degrees.
+#[derive(Clone)]
+pub struct Sector {
+    target: Action<u8>,
+    sectors: u16,
+    deadzone: f64,
+    hysteresis: f64,
+
+    raw: Action<mint::Vector2<f64>>,
+}
+
+impl Sector {
+    /// `hysteresis` is in radians
+    ///
+    /// Panics if `sectors` isn't between 1 and 256.
+    pub fn new(
+        session: &mut Session,
+        target: Action<u8>,
+        sectors: u16,
+        deadzone: f64,
+        hysteresis: f64,
+    ) -> Result<Self, DuplicateAction> {
+        assert!((1..=256).contains(&sectors), "sector count out of range");
+        let o = session.action_name(target.id()).to_owned();
+        Ok(Self {
+            target,
+            sectors,
+            deadzone,
+            hysteresis,
+            raw: session.create_action(&format!("{o}-raw"))?,
+        })
+    }
+
+    pub fn raw(&self) -> Action<mint::Vector2<f64>> {
+        self.raw
+    }
+
+    pub fn sectors(&self) -> u16 {
+        self.sectors
+    }
+    pub fn deadzone(&self) -> f64 {
+        self.deadzone
+    }
+    pub fn hysteresis(&self) -> f64 {
+        self.hysteresis
+    }
+}
+
+impl Filter for Sector {
+    const NAME: &str = "sector";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 1 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<mint::Vector2<f64>>(&format!("{o}-raw"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        let target = filter_action::<u8>(session, Self::NAME, o)?;
+        let invalid = |i: usize| FilterLoadError::InvalidParam {
+            filter_ty: Self::NAME.to_owned(),
+            param: cfg.params.get(i).cloned().unwrap_or_default(),
+        };
+        let param = |i: usize| cfg.params.get(i).map(|x| x.trim());
+        let sectors = param(0)
+            .and_then(|x| x.parse::<u16>().ok())
+            .filter(|x| (1..=256).contains(x))
+            .ok_or_else(|| invalid(0))?;
+        let non_negative = |i: usize| {
+            param(i)
+                .and_then(|x| x.parse::<f64>().ok())
+                .filter(|&x| x >= 0.0 && x.is_finite())
+                .ok_or_else(|| invalid(i))
+        };
+        Ok(Self {
+            target,
+            sectors,
+            deadzone: non_negative(1)?,
+            hysteresis: non_negative(2)?.to_radians(),
+            raw: filter_action(session, Self::NAME, &format!("{o}-raw"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![session.action_name(self.target.id()).to_owned()],
+            params: vec![
+                self.sectors.to_string(),
+                self.deadzone.to_string(),
+                self.hysteresis.to_degrees().to_string(),
+            ],
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.raw.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        use core::f64::consts::TAU;
+
+        let Some(raw) = seat.get(self.raw) else {
+            return;
+        };
+        if float::hypot(raw.x, raw.y) < self.deadzone || (raw.x == 0.0 && raw.y == 0.0) {
+            return;
+        }
+        // Clockwise from +Y
+        let angle = float::atan2(raw.x, raw.y).rem_euclid(TAU);
+        let width = TAU / f64::from(self.sectors);
+        let current = seat.get(self.target);
+        if let Some(current) = current.filter(|&x| u16::from(x) < self.sectors) {
+            let offset = (angle - f64::from(current) * width).rem_euclid(TAU);
+            if offset.min(TAU - offset) <= width / 2.0 + self.hysteresis {
+                return;
+            }
+        }
+        let sector = ((angle / width).round() as u16 % self.sectors) as u8;
+        if current != Some(sector) {
+            let _ = seat.push(self.target.id(), sector);
+        }
+    }
+}
+
+/// Accumulates an axis, e.g. a mouse wheel, into a stepped integer
+///
+/// Suits zoom levels and scrolling through weapons or list entries. The source
+/// action name is derived by suffixing `-delta` to the target action name.
+/// Every `step` of accumulated delta moves the target, an `i64`, by 1, with a
+/// negative `step` reversing direction. Partial steps carry over between
+/// deltas, so smooth scrolling works as well as wheel notches. By default the
+/// target is unbounded and starts at 0; see [`set_range`](Self::set_range).
+///
+/// In a [`FilterConfig`], the parameters are the step, then optionally the
+/// minimum and maximum, then optionally `wrap`.
+///
+/// The source is consumed with [`Seat::poll`], so it must remain polled.
+#[derive(Clone)]
+pub struct Scroll {
+    target: Action<i64>,
+    step: f64,
+    range: Option<RangeInclusive<i64>>,
+    wrap: bool,
+
+    delta: Action<f64>,
+}
+
+/// Partial steps of a [`Scroll`], kept in its [`Seat::filter_state`]
+#[derive(Default)]
+struct ScrollCarry(f64);
+
+impl Scroll {
+    /// Panics if `step` is zero or not finite
+    pub fn new(
+        session: &mut Session,
+        target: Action<i64>,
+        step: f64,
+    ) -> Result<Self, DuplicateAction> {
+        assert!(step != 0.0 && step.is_finite(), "invalid step");
+        let o = session.action_name(target.id()).to_owned();
+        Ok(Self {
+            target,
+            step,
+            range: None,
+            wrap: false,
+            delta: session.create_action(&format!("{o}-delta"))?,
+        })
+    }
+
+    /// Keep the target within `range`, starting at its start
+    ///
+    /// Scrolling past either end stops there, or if `wrap` is set, continues
+    /// from the other end. Panics if `range` is empty.
+    pub fn set_range(&mut self, range: RangeInclusive<i64>, wrap: bool) {
+        assert!(!range.is_empty(), "empty range");
+        self.range = Some(range);
+        self.wrap = wrap;
+    }
+
+    pub fn delta(&self) -> Action<f64> {
+        self.delta
+    }
+
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+    pub fn range(&self) -> Option<RangeInclusive<i64>> {
+        self.range.clone()
+    }
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    /// Move `value` by `steps`, respecting the range
+    fn offset(&self, value: i64, steps: i64) -> i64 {
+        let Some(ref range) = self.range else {
+            return value.saturating_add(steps);
+        };
+        let (min, max) = (*range.start(), *range.end());
+        if !self.wrap {
+            return value.saturating_add(steps).clamp(min, max);
+        }
+        let len = i128::from(max) - i128::from(min) + 1;
+        let offset = (i128::from(value) - i128::from(min) + i128::from(steps)).rem_euclid(len);
+        (i128::from(min) + offset) as i64
+    }
+}
+
+impl Filter for Scroll {
+    const NAME: &str = "scroll";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 1 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<f64>(&format!("{o}-delta"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        let target = filter_action::<i64>(session, Self::NAME, o)?;
+        let invalid = |i: usize| FilterLoadError::InvalidParam {
+            filter_ty: Self::NAME.to_owned(),
+            param: cfg.params.get(i).cloned().unwrap_or_default(),
+        };
+        let param = |i: usize| cfg.params.get(i).map(|x| x.trim());
+        let step = param(0)
+            .and_then(|x| x.parse::<f64>().ok())
+            .filter(|&x| x != 0.0 && x.is_finite())
+            .ok_or_else(|| invalid(0))?;
+        let range = match cfg.params.len() {
+            1 => None,
+            3 | 4 => {
+                let bound = |i: usize| {
+                    param(i)
+                        .and_then(|x| x.parse::<i64>().ok())
+                        .ok_or_else(|| invalid(i))
+                };
+                let range = bound(1)?..=bound(2)?;
+                if range.is_empty() {
+                    return Err(invalid(2));
+                }
+                Some(range)
+            }
+            _ => return Err(invalid(cfg.params.len() - 1)),
+        };
+        let wrap = match param(3) {
+            None => false,
+            Some("wrap") => true,
+            Some(_) => return Err(invalid(3)),
+        };
+        Ok(Self {
+            target,
+            step,
+            range,
+            wrap,
+            delta: filter_action(session, Self::NAME, &format!("{o}-delta"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        let mut params = vec![self.step.to_string()];
+        if let Some(ref range) = self.range {
+            params.push(range.start().to_string());
+            params.push(range.end().to_string());
+            if self.wrap {
+                params.push("wrap".to_owned());
+            }
+        }
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![session.action_name(self.target.id()).to_owned()],
+            params,
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.delta.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let mut delta = None::<f64>;
+        while let Some(x) = seat.poll(self.delta) {
+            delta = Some(delta.unwrap_or_default() + x);
+        }
+        let Some(delta) = delta else {
+            return;
+        };
+        let ScrollCarry(carry) = seat.filter_state(self.delta.id());
+        *carry += delta / self.step;
+        let steps = carry.trunc();
+        *carry -= steps;
+        let current = seat.get(self.target);
+        let start = current.unwrap_or_else(|| self.range.as_ref().map_or(0, |r| *r.start()));
+        let value = self.offset(start, steps as i64);
+        if current != Some(value) {
+            let _ = seat.push(self.target.id(), value);
+        }
+    }
+}
+
+/// Measures how long a `bool` source has been held, e.g. to draw a bow or
+/// charge a shot
+///
+/// The source action name is derived by suffixing `-held` to the name of the
+/// first target. The first target, an `f64`, rises from 0 to 1 over `time`
+/// while the source is held, and keeps its final value after release until
+/// the next press. The optional second target, a `()`, fires on each release,
+/// at which point the first holds the charge reached.
+///
+/// In a [`FilterConfig`], the parameter is the charge time in milliseconds.
+/// Charging proceeds in [`Bindings::update`](crate::Bindings::update), so it's
+/// only as smooth as the rate at which that's called.
+#[derive(Clone)]
+pub struct Charge {
+    target: Action<f64>,
+    release: Option<Action<()>>,
+    time: Duration,
+
+    held: Action<bool>,
+}
+
+impl Charge {
+    pub fn new(
+        session: &mut Session,
+        target: Action<f64>,
+        release: Option<Action<()>>,
+        time: Duration,
+    ) -> Result<Self, DuplicateAction> {
+        let o = session.action_name(target.id()).to_owned();
+        Ok(Self {
+            target,
+            release,
+            time,
+            held: session.create_action(&format!("{o}-held"))?,
+        })
+    }
+
+    pub fn held(&self) -> Action<bool> {
+        self.held
+    }
+
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+impl Filter for Charge {
+    const NAME: &str = "charge";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if !(1..=2).contains(&cfg.targets.len()) {
+            return Err(FilterLoadError::OutputCountOutOfRange { min: 1, max: 2 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<bool>(&format!("{o}-held"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        let param = cfg.params.first().map_or("", |x| &**x);
+        let time = param
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(|x| Duration::try_from_secs_f64(x / 1000.0).ok())
+            .ok_or_else(|| FilterLoadError::InvalidParam {
+                filter_ty: Self::NAME.to_owned(),
+                param: param.to_owned(),
+            })?;
+        Ok(Self {
+            target: filter_action(session, Self::NAME, o)?,
+            release: cfg
+                .targets
+                .get(1)
+                .map(|name| filter_action(session, Self::NAME, name))
+                .transpose()?,
+            time,
+            held: filter_action(session, Self::NAME, &format!("{o}-held"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: self
+                .target_actions()
+                .into_iter()
+                .map(|id| session.action_name(id).to_owned())
+                .collect(),
+            params: vec![(self.time.as_secs_f64() * 1000.0).to_string()],
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.held.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        core::iter::once(self.target.id())
+            .chain(self.release.map(|x| x.id()))
+            .collect()
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let Some(pressed) = seat.last_activation(self.held.id()) else {
+            return;
+        };
+        let held = seat.get(self.held).unwrap_or_default();
+        let now = seat.now();
+        // Charging stops at release
+        let end = match seat.last_change(self.held.id()) {
+            Some(released) if !held => released,
+            _ => now,
+        };
+        let elapsed = end.saturating_sub(pressed);
+        let charge = if elapsed >= self.time {
+            1.0
+        } else {
+            elapsed.as_secs_f64() / self.time.as_secs_f64()
+        };
+        if seat.get(self.target) != Some(charge) {
+            let _ = seat.push(self.target.id(), charge);
+        }
+        if held {
+            if charge < 1.0 {
+                seat.wake_at(now, self.held.id());
+            }
+            return;
+        }
+        if let Some(release) = self.release {
+            // Fire once per press, ignoring repeated releases
+            if seat
+                .last_change(release.id())
+                .is_none_or(|fired| fired < pressed)
+            {
+                let _ = seat.push(release.id(), ());
+            }
+        }
+    }
+}
+
+/// Splits an analog trigger into two digital stages, like a GameCube trigger
+///
+/// The source action name is derived by suffixing `-pull` to the name of the
+/// first target. The first target, a `bool`, is held while the `f64` source is
+/// at least `soft`, e.g. for a half-pull; the second while it's at least
+/// `hard`, for a full pull.
+///
+/// In a [`FilterConfig`], the parameters are the two thresholds.
+#[derive(Clone)]
+pub struct Stages {
+    soft_target: Action<bool>,
+    hard_target: Action<bool>,
+    soft: f64,
+    hard: f64,
+
+    pull: Action<f64>,
+}
+
+impl Stages {
+    pub fn new(
+        session: &mut Session,
+        soft_target: Action<bool>,
+        hard_target: Action<bool>,
+        soft: f64,
+        hard: f64,
+    ) -> Result<Self, DuplicateAction> {
+        let o = session.action_name(soft_target.id()).to_owned();
+        Ok(Self {
+            soft_target,
+            hard_target,
+            soft,
+            hard,
+            pull: session.create_action(&format!("{o}-pull"))?,
+        })
+    }
+
+    pub fn pull(&self) -> Action<f64> {
+        self.pull
+    }
+
+    pub fn soft(&self) -> f64 {
+        self.soft
+    }
+    pub fn hard(&self) -> f64 {
+        self.hard
+    }
+}
+
+impl Filter for Stages {
+    const NAME: &str = "stages";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 2 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 2 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<f64>(&format!("{o}-pull"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let target = |name: &str| filter_action::<bool>(session, Self::NAME, name);
+        let threshold = |i: usize| {
+            let param = cfg.params.get(i).map_or("", |x| &**x);
+            param
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|x| x.is_finite())
+                .ok_or_else(|| FilterLoadError::InvalidParam {
+                    filter_ty: Self::NAME.to_owned(),
+                    param: param.to_owned(),
+                })
+        };
+        let o = &*cfg.targets[0];
+        Ok(Self {
+            soft_target: target(o)?,
+            hard_target: target(&cfg.targets[1])?,
+            soft: threshold(0)?,
+            hard: threshold(1)?,
+            pull: filter_action(session, Self::NAME, &format!("{o}-pull"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![
+                session.action_name(self.soft_target.id()).to_owned(),
+                session.action_name(self.hard_target.id()).to_owned(),
+            ],
+            params: vec![self.soft.to_string(), self.hard.to_string()],
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.pull.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.soft_target.id(), self.hard_target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let pull = seat.get(self.pull).unwrap_or_default();
+        for (target, threshold) in [(self.soft_target, self.soft), (self.hard_target, self.hard)] {
+            let held = pull >= threshold;
+            // Avoid flooding targets with repeated states as the trigger moves
+            if seat.get(target) != Some(held) {
+                let _ = seat.push(target.id(), held);
+            }
+        }
+    }
+}
+
+/// Resolves simultaneous opposing directions, i.e. SOCD cleaning
+///
+/// Each of the two `bool` targets, e.g. `left` and `right`, has a source
+/// derived by suffixing `-raw` to its name. Each target follows its source,
+/// except while both sources are held, when the [`SocdPolicy`] decides.
+/// Targets may feed a [`DPad`] so that movement is cleaned before it's
+/// combined.
+///
+/// In a [`FilterConfig`], the parameter is the policy: `neutral`, `last`, or
+/// `first`.
+#[derive(Clone)]
+pub struct Socd {
+    targets: [Action<bool>; 2],
+    policy: SocdPolicy,
+
+    raw: [Action<bool>; 2],
+}
+
+/// How a [`Socd`] filter resolves opposing inputs held together
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum SocdPolicy {
+    /// Neither direction is held
+    #[default]
+    Neutral,
+    /// The most recently pressed direction is held
+    LastWins,
+    /// The direction pressed first is held
+    FirstWins,
+}
+
+impl SocdPolicy {
+    /// Name in a [`FilterConfig`]
+    pub fn name(self) -> &'static str {
+        match self {
+            SocdPolicy::Neutral => "neutral",
+            SocdPolicy::LastWins => "last",
+            SocdPolicy::FirstWins => "first",
+        }
+    }
+
+    /// Look up a policy by [`name`](Self::name)
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Neutral, Self::LastWins, Self::FirstWins]
+            .into_iter()
+            .find(|x| x.name() == name)
+    }
+}
+
+impl Socd {
+    pub fn new(
+        session: &mut Session,
+        targets: [Action<bool>; 2],
+        policy: SocdPolicy,
+    ) -> Result<Self, DuplicateAction> {
+        let [a, b] = targets.map(|target| {
+            let o = session.action_name(target.id()).to_owned();
+            session.create_action(&format!("{o}-raw"))
+        });
+        Ok(Self {
+            targets,
+            policy,
+            raw: [a?, b?],
+        })
+    }
+
+    /// Sources of each target, in order
+    pub fn raw(&self) -> [Action<bool>; 2] {
+        self.raw
+    }
+
+    pub fn policy(&self) -> SocdPolicy {
+        self.policy
+    }
+}
+
+impl Filter for Socd {
+    const NAME: &str = "socd";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 2 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 2 });
+        }
+        for o in &cfg.targets {
+            session.create_action::<bool>(&format!("{o}-raw"))?;
+        }
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let target = |name: &str| filter_action::<bool>(session, Self::NAME, name);
+        let raw = |name: &str| filter_action(session, Self::NAME, &format!("{name}-raw"));
+        let param = cfg.params.first().map_or("", |x| &**x);
+        let policy =
+            SocdPolicy::from_name(param.trim()).ok_or_else(|| FilterLoadError::InvalidParam {
+                filter_ty: Self::NAME.to_owned(),
+                param: param.to_owned(),
+            })?;
+        let (a, b) = (&*cfg.targets[0], &*cfg.targets[1]);
+        Ok(Self {
+            targets: [target(a)?, target(b)?],
+            policy,
+            raw: [raw(a)?, raw(b)?],
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: self
+                .targets
+                .iter()
+                .map(|x| session.action_name(x.id()).to_owned())
+                .collect(),
+            params: vec![self.policy.name().to_owned()],
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        self.raw.iter().map(|x| x.id()).collect()
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        self.targets.iter().map(|x| x.id()).collect()
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let held = self.raw.map(|x| seat.get(x).unwrap_or_default());
+        let held = if held == [true; 2] {
+            let [a, b] = self.raw.map(|x| seat.last_activation(x.id()));
+            match self.policy {
+                SocdPolicy::Neutral => [false; 2],
+                SocdPolicy::LastWins => [a > b, a <= b],
+                SocdPolicy::FirstWins => [a <= b, a > b],
+            }
+        } else {
+            held
+        };
+        for (target, held) in self.targets.into_iter().zip(held) {
+            if seat.get(target) != Some(held) {
+                let _ = seat.push(target.id(), held);
+            }
+        }
+    }
+}
+
+/// Multiplies a vector by a scalar supplied at runtime, e.g. to reduce look
+/// sensitivity while zoomed in
+///
+/// Combines a `mint::Vector2<f64>` source, suffixed `-raw`, with an `f64`
+/// source, suffixed `-scale`, which the application pushes to directly, e.g.
+/// with the current zoom factor. Each change to the vector is forwarded to the
+/// target multiplied by the latest scale, or 1 if none has been pushed.
+/// Changes to the scale take effect from the next change to the vector. The
+/// target may have any [`Vector2`] type.
+///
+/// The vector source is consumed with [`Seat::poll`], so it must remain
+/// polled.
+#[derive(Clone)]
+pub struct Scale {
+    target: VectorTarget,
+
+    raw: Action<mint::Vector2<f64>>,
+    scale: Action<f64>,
+}
+
+impl Scale {
+    pub fn new<V: Vector2>(
+        session: &mut Session,
+        target: Action<V>,
+    ) -> Result<Self, DuplicateAction> {
+        let o = session.action_name(target.id()).to_owned();
+        Ok(Self {
+            target: VectorTarget::new(target),
+            raw: session.create_action(&format!("{o}-raw"))?,
+            scale: session.create_action(&format!("{o}-scale"))?,
+        })
+    }
+
+    pub fn raw(&self) -> Action<mint::Vector2<f64>> {
+        self.raw
+    }
+    pub fn scale(&self) -> Action<f64> {
+        self.scale
+    }
+}
+
+impl Filter for Scale {
+    const NAME: &str = "scale";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 1 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<mint::Vector2<f64>>(&format!("{o}-raw"))?;
+        session.create_action::<f64>(&format!("{o}-scale"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        Ok(Self {
+            target: VectorTarget::load(session, Self::NAME, o)?,
+            raw: filter_action(session, Self::NAME, &format!("{o}-raw"))?,
+            scale: filter_action(session, Self::NAME, &format!("{o}-scale"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![session.action_name(self.target.id()).to_owned()],
+            params: Vec::new(),
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.raw.id(), self.scale.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let scale = seat.get(self.scale).unwrap_or(1.0);
+        while let Some(raw) = seat.poll(self.raw) {
+            self.target.push(seat, [raw.x * scale, raw.y * scale]);
+        }
+    }
+}
+
+/// Latches a `bool` target, flipping it on each press of a `bool` source
+///
+/// The source action name is derived by suffixing `-press` to the target
+/// action name. Useful for accessibility, letting actions which would
+/// otherwise need to be held be pressed once to engage and again to release.
+/// Presses are detected with [`Seat::last_activation`], so several presses
+/// between calls to [`Bindings::update`](crate::Bindings::update) count as
+/// one.
+#[derive(Clone)]
+pub struct Toggle {
+    target: Action<bool>,
+
+    press: Action<bool>,
+}
+
+impl Toggle {
+    pub fn new(session: &mut Session, target: Action<bool>) -> Result<Self, DuplicateAction> {
+        let o = session.action_name(target.id()).to_owned();
+        Ok(Self {
+            target,
+            press: session.create_action(&format!("{o}-press"))?,
+        })
+    }
+
+    pub fn press(&self) -> Action<bool> {
+        self.press
+    }
+}
+
+impl Filter for Toggle {
+    const NAME: &str = "toggle";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 1 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<bool>(&format!("{o}-press"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        Ok(Self {
+            target: filter_action(session, Self::NAME, o)?,
+            press: filter_action(session, Self::NAME, &format!("{o}-press"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![session.action_name(self.target.id()).to_owned()],
+            params: Vec::new(),
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.press.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let Some(pressed) = seat.last_activation(self.press.id()) else {
+            return;
+        };
+        if seat
+            .last_change(self.target.id())
+            .is_some_and(|flipped| flipped >= pressed)
+        {
+            return;
+        }
+        let on = !seat.get(self.target).unwrap_or_default();
+        let _ = seat.push(self.target.id(), on);
+    }
+}
+
+/// Ignores changes to a `bool` source made too soon after the last change
+/// forwarded to a `bool` target
+///
+/// The source action name is derived by suffixing `-raw` to the target action
+/// name. Filters out contact bounce and, for accessibility, unintended
+/// repeated presses from tremors. A change to the source made during the
+/// window is forwarded when the window closes if the source still differs from
+/// the target then.
+///
+/// In a [`FilterConfig`], the parameter is the window in milliseconds.
+#[derive(Clone)]
+pub struct Debounce {
+    target: Action<bool>,
+    window: Duration,
+
+    raw: Action<bool>,
+}
+
+impl Debounce {
+    pub fn new(
+        session: &mut Session,
+        target: Action<bool>,
+        window: Duration,
+    ) -> Result<Self, DuplicateAction> {
+        let o = session.action_name(target.id()).to_owned();
+        Ok(Self {
+            target,
+            window,
+            raw: session.create_action(&format!("{o}-raw"))?,
+        })
+    }
+
+    pub fn raw(&self) -> Action<bool> {
+        self.raw
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+}
+
+impl Filter for Debounce {
+    const NAME: &str = "debounce";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 1 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<bool>(&format!("{o}-raw"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        let param = cfg.params.first().map_or("", |x| &**x);
+        let window = param
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(|x| Duration::try_from_secs_f64(x / 1000.0).ok())
+            .ok_or_else(|| FilterLoadError::InvalidParam {
+                filter_ty: Self::NAME.to_owned(),
+                param: param.to_owned(),
+            })?;
+        Ok(Self {
+            target: filter_action(session, Self::NAME, o)?,
+            window,
+            raw: filter_action(session, Self::NAME, &format!("{o}-raw"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![session.action_name(self.target.id()).to_owned()],
+            params: vec![(self.window.as_secs_f64() * 1000.0).to_string()],
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.raw.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        let Some(raw) = seat.get(self.raw) else {
+            return;
+        };
+        if seat.get(self.target) == Some(raw) {
+            return;
+        }
+        if let Some(changed) = seat.last_change(self.target.id()) {
+            // A window too long to represent never closes
+            let Some(open) = changed.checked_add(self.window) else {
+                return;
+            };
+            if seat.now() < open {
+                seat.wake_at(open, self.raw.id());
+                return;
+            }
+        }
+        let _ = seat.push(self.target.id(), raw);
+    }
+}
+
+/// Look up the action `name` read or written by a filter of type `filter_ty`
+///
+/// Since types are checked here, pushes to the action can only fail if it's
+/// since been removed from the session, so filters ignore such failures.
+fn filter_action<T: 'static>(
+    session: &Session,
+    filter_ty: &str,
+    name: &str,
+) -> Result<Action<T>, FilterLoadError> {
+    let id = session
+        .action_id(name)
+        .ok_or_else(|| FilterLoadError::UnknownTarget {
+            output: name.to_owned(),
+        })?;
+    session
+        .action::<T>(id)
+        .map_err(|error| FilterLoadError::TypeError {
+            filter_ty: filter_ty.to_owned(),
+            action: name.to_owned(),
+            error,
+        })
+}
+
+/// Single-switch scanning, cycling focus through `()` actions and firing the
+/// focused one when a switch is pressed
+///
+/// Enables play with a single button. The first target, a `u8`, holds the
+/// index of the focused action among the rest, e.g. for highlighting it. The
+/// source action name is derived by suffixing `-switch` to the name of the
+/// first target. The first press of the switch starts scanning; each later
+/// press fires the action focused at the time of the press and restarts
+/// scanning from the first. Focus advances every `period`, in
+/// [`Bindings::update`](crate::Bindings::update), which must be called
+/// regularly.
+///
+/// In a [`FilterConfig`], the parameter is the period in milliseconds. The
+/// switch is consumed with [`Seat::poll`], so it must remain polled.
+#[derive(Clone)]
+pub struct Scan {
+    focus: Action<u8>,
+    items: Arc<[Action<()>]>,
+    period: Duration,
+
+    switch: Action<()>,
+}
+
+/// When a [`Scan`] last (re)started scanning, if it has, kept in its
+/// [`Seat::filter_state`]
+#[derive(Default)]
+struct ScanStart(Option<Duration>);
+
+impl Scan {
+    /// Scan through `items`, of which there must be between 1 and 256
+    pub fn new(
+        session: &mut Session,
+        focus: Action<u8>,
+        items: Vec<Action<()>>,
+        period: Duration,
+    ) -> Result<Self, DuplicateAction> {
+        assert!(
+            (1..=256).contains(&items.len()),
+            "scanning requires 1 to 256 items"
+        );
+        assert!(!period.is_zero(), "scan period must be nonzero");
+        let o = session.action_name(focus.id()).to_owned();
+        Ok(Self {
+            focus,
+            items: items.into(),
+            period,
+            switch: session.create_action(&format!("{o}-switch"))?,
+        })
+    }
+
+    pub fn switch(&self) -> Action<()> {
+        self.switch
+    }
+
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Index of the item focused `elapsed` after scanning started
+    fn index(&self, elapsed: Duration) -> usize {
+        (elapsed.as_nanos() / self.period.as_nanos()) as usize % self.items.len()
+    }
+}
+
+impl Filter for Scan {
+    const NAME: &str = "scan";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if !(2..=257).contains(&cfg.targets.len()) {
+            return Err(FilterLoadError::OutputCountOutOfRange { min: 2, max: 257 });
+        }
+        let o = &*cfg.targets[0];
+        session.create_action::<()>(&format!("{o}-switch"))?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let o = &*cfg.targets[0];
+        let param = cfg.params.first().map_or("", |x| &**x);
+        let period = param
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(|x| Duration::try_from_secs_f64(x / 1000.0).ok())
+            .filter(|x| !x.is_zero())
+            .ok_or_else(|| FilterLoadError::InvalidParam {
+                filter_ty: Self::NAME.to_owned(),
+                param: param.to_owned(),
+            })?;
+        Ok(Self {
+            focus: filter_action(session, Self::NAME, o)?,
+            items: cfg.targets[1..]
+                .iter()
+                .map(|name| filter_action(session, Self::NAME, name))
+                .collect::<Result<_, _>>()?,
+            period,
+            switch: filter_action(session, Self::NAME, &format!("{o}-switch"))?,
+        })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: self
+                .target_actions()
+                .into_iter()
+                .map(|id| session.action_name(id).to_owned())
+                .collect(),
+            params: vec![(self.period.as_secs_f64() * 1000.0).to_string()],
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.switch.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        core::iter::once(self.focus.id())
+            .chain(self.items.iter().map(|x| x.id()))
+            .collect()
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        // When scanning last (re)started, if it has
+        let mut start = seat.filter_state::<ScanStart>(self.switch.id()).0;
+        while let Some(pressed) = seat.next_time(self.switch.id()) {
+            seat.poll(self.switch);
+            if let Some(start) = start {
+                let item = self.items[self.index(pressed.saturating_sub(start))];
+                let _ = seat.push(item.id(), ());
+            }
+            start = Some(pressed);
+        }
+        let Some(start) = start else {
+            return;
+        };
+        seat.filter_state::<ScanStart>(self.switch.id()).0 = Some(start);
+        let elapsed = seat.now().saturating_sub(start);
+        let index = self.index(elapsed) as u8;
+        if seat.get(self.focus) != Some(index) {
+            let _ = seat.push(self.focus.id(), index);
+        }
+        // Focus stays put once the next step is too far off to represent
+        let periods = elapsed.as_nanos() / self.period.as_nanos() + 1;
+        let next = u32::try_from(periods)
+            .ok()
+            .and_then(|periods| self.period.checked_mul(periods))
+            .and_then(|offset| start.checked_add(offset));
+        if let Some(next) = next {
+            seat.wake_at(next, self.switch.id());
+        }
+    }
+}
+
+/// Plays back a sequence of pushes each time its trigger fires
+///
+/// Useful for accessibility, e.g. performing a combo with a single button, and
+/// for scripted tutorials. Each [`MacroStep`] is pushed after a delay relative
+/// to the previous step. Delayed steps are performed by
+/// [`Bindings::update`](crate::Bindings::update), so their timing is only as
+/// precise as the rate at which it's called. Firing the trigger again during
+/// playback starts an overlapping playback.
+///
+/// In a [`FilterConfig`], the first parameter names the trigger, and each
+/// subsequent parameter is a step written as `DELAY_MS ACTION [VALUE]`, e.g.
+/// `"50 jump true"`. The value is omitted for `()` actions. Targets list every
+/// action the steps push to.
+///
+/// The trigger is consumed with [`Seat::poll`], so it must remain polled.
+#[derive(Clone)]
+pub struct Macro {
+    trigger: Action<()>,
+    steps: Arc<[MacroStep]>,
+    targets: Vec<ActionId>,
+}
+
+/// A single push performed by a [`Macro`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MacroStep {
+    /// Time after the previous step, or the trigger for the first step
+    pub delay: Duration,
+    pub action: ActionId,
+    pub value: MacroValue,
+}
+
+/// A value pushed by a [`MacroStep`], which must match its action's type
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MacroValue {
+    Unit,
+    Bool(bool),
+    F64(f64),
+}
+
+impl From<()> for MacroValue {
+    fn from((): ()) -> Self {
+        MacroValue::Unit
+    }
+}
+
+impl From<bool> for MacroValue {
+    fn from(value: bool) -> Self {
+        MacroValue::Bool(value)
+    }
+}
+
+impl From<f64> for MacroValue {
+    fn from(value: f64) -> Self {
+        MacroValue::F64(value)
+    }
+}
+
+impl Macro {
+    /// Create a macro triggered by a new action named `trigger`
+    pub fn new(
+        session: &mut Session,
+        trigger: &str,
+        steps: Vec<MacroStep>,
+    ) -> Result<Self, FilterLoadError> {
+        for step in &steps {
+            check_step(session, step)?;
+        }
+        let trigger = session.create_action(trigger)?;
+        Ok(Self::from_steps(trigger, steps))
+    }
+
+    fn from_steps(trigger: Action<()>, steps: Vec<MacroStep>) -> Self {
+        let mut targets = Vec::new();
+        for step in &steps {
+            if !targets.contains(&step.action) {
+                targets.push(step.action);
+            }
+        }
+        Self {
+            trigger,
+            steps: steps.into(),
+            targets,
+        }
+    }
+
+    pub fn trigger(&self) -> Action<()> {
+        self.trigger
+    }
+
+    pub fn steps(&self) -> &[MacroStep] {
+        &self.steps
+    }
+}
+
+/// Ensure `step`'s value has the type of its action
+fn check_step(session: &Session, step: &MacroStep) -> Result<(), FilterLoadError> {
+    let result = match step.value {
+        MacroValue::Unit => session.action::<()>(step.action).map(drop),
+        MacroValue::Bool(_) => session.action::<bool>(step.action).map(drop),
+        MacroValue::F64(_) => session.action::<f64>(step.action).map(drop),
+    };
+    result.map_err(|error| FilterLoadError::TypeError {
+        filter_ty: Macro::NAME.to_owned(),
+        action: session.action_name(step.action).to_owned(),
+        error,
+    })
+}
+
+/// Parse a step written as `DELAY_MS ACTION [VALUE]`
+fn parse_step(session: &Session, param: &str) -> Option<MacroStep> {
+    let (delay, rest) = split_step(param)?;
+    let (action, value) = match session.action_id(rest) {
+        Some(action) => (action, MacroValue::Unit),
+        None => {
+            let (name, value) = split_value(rest);
+            if value == MacroValue::Unit {
+                return None;
+            }
+            (session.action_id(name)?, value)
+        }
+    };
+    Some(MacroStep {
+        delay,
+        action,
+        value,
+    })
+}
+
+/// Split a step written as `DELAY_MS ACTION [VALUE]` into its delay and the
+/// rest
+pub(crate) fn split_step(param: &str) -> Option<(Duration, &str)> {
+    let (delay, rest) = param.trim().split_once(' ')?;
+    let delay = Duration::try_from_secs_f64(delay.parse::<f64>().ok()? / 1000.0).ok()?;
+    Some((delay, rest.trim()))
+}
+
+/// Split `ACTION [VALUE]` into the action name and value, taken to be
+/// [`MacroValue::Unit`] if the last word isn't a value
+pub(crate) fn split_value(rest: &str) -> (&str, MacroValue) {
+    let Some((name, value)) = rest.rsplit_once(' ') else {
+        return (rest, MacroValue::Unit);
+    };
+    let value = match value {
+        "true" => MacroValue::Bool(true),
+        "false" => MacroValue::Bool(false),
+        _ => match value.parse() {
+            Ok(x) => MacroValue::F64(x),
+            Err(_) => return (rest, MacroValue::Unit),
+        },
+    };
+    (name.trim(), value)
+}
+
+impl Filter for Macro {
+    const NAME: &str = "macro";
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        let Some(trigger) = cfg.params.first() else {
+            return Err(FilterLoadError::InvalidParam {
+                filter_ty: Self::NAME.to_owned(),
+                param: String::new(),
+            });
+        };
+        session.create_action::<()>(trigger)?;
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let invalid = |param: &str| FilterLoadError::InvalidParam {
+            filter_ty: Self::NAME.to_owned(),
+            param: param.to_owned(),
+        };
+        let (trigger, steps) = cfg.params.split_first().ok_or_else(|| invalid(""))?;
+        let trigger = session
+            .action_id(trigger)
+            .and_then(|id| session.action(id).ok())
+            .ok_or_else(|| invalid(trigger))?;
+        let steps = steps
+            .iter()
+            .map(|param| {
+                let step = parse_step(session, param).ok_or_else(|| invalid(param))?;
+                if !cfg
+                    .targets
+                    .iter()
+                    .any(|t| **t == *session.action_name(step.action))
+                {
+                    return Err(invalid(param));
+                }
+                check_step(session, &step)?;
+                Ok(step)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_steps(trigger, steps))
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        let steps = self.steps.iter().map(|step| {
+            let delay = step.delay.as_secs_f64() * 1000.0;
+            let action = session.action_name(step.action);
+            match step.value {
+                MacroValue::Unit => format!("{delay} {action}"),
+                MacroValue::Bool(x) => format!("{delay} {action} {x}"),
+                MacroValue::F64(x) => format!("{delay} {action} {x}"),
+            }
+        });
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: self
+                .targets
+                .iter()
+                .map(|&id| session.action_name(id).to_owned())
+                .collect(),
+            params: core::iter::once(session.action_name(self.trigger.id()).to_owned())
+                .chain(steps)
+                .collect(),
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        vec![self.trigger.id()]
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        self.targets.clone()
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        while seat.poll(self.trigger).is_some() {
+            let now = seat.now();
+            let mut at = now;
+            for step in &*self.steps {
+                at = at.saturating_add(step.delay);
+                if at <= now {
+                    push_value(seat, step.action, step.value);
+                } else {
+                    match step.value {
+                        MacroValue::Unit => seat.push_at(at, step.action, ()),
+                        MacroValue::Bool(x) => seat.push_at(at, step.action, x),
+                        MacroValue::F64(x) => seat.push_at(at, step.action, x),
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn push_value(seat: &mut Seat, action: ActionId, value: MacroValue) {
+    let _ = match value {
+        MacroValue::Unit => seat.push(action, ()),
+        MacroValue::Bool(x) => seat.push(action, x),
+        MacroValue::F64(x) => seat.push(action, x),
+    };
+}
+
+/// Pushes a constant value to its target whenever one of its sources fires
+///
+/// Maps several `()` source actions, one per value, to a single target, e.g.
+/// weapon slots. Source action names are derived by suffixing `-VALUE` to the
+/// target action name, e.g. `select_weapon-2`. In a [`FilterConfig`], the
+/// parameters list the values. Loaded from configs as
+/// [`SelectValue::FILTER_NAME`], so a [`BindingsFactory`](crate::BindingsFactory)
+/// must register `Select<E>` for each desired value type `E`; `i64` and
+/// `String` are registered by default.
+///
+/// Sources are consumed with [`Seat::poll`], so they must remain polled.
+#[derive(Clone)]
+pub struct Select<E> {
+    target: Action<E>,
+    options: Vec<(Action<()>, E)>,
+}
+
+/// A type of value pushed by a [`Select`] filter
+pub trait SelectValue: Clone + 'static {
+    /// [`Filter::NAME`] of `Select<Self>`, e.g. `"select-weapon"`
+    const FILTER_NAME: &'static str;
+
+    /// Interpret a value from a [`FilterConfig`]
+    fn parse(s: &str) -> Option<Self>;
+
+    /// Inverse of [`parse`](Self::parse)
+    fn format(&self) -> String;
+}
+
+impl SelectValue for i64 {
+    const FILTER_NAME: &'static str = "select";
+
+    fn parse(s: &str) -> Option<Self> {
+        s.trim().parse().ok()
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl SelectValue for String {
+    const FILTER_NAME: &'static str = "select-string";
+
+    fn parse(s: &str) -> Option<Self> {
+        Some(s.to_owned())
+    }
+
+    fn format(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<E: SelectValue> Select<E> {
+    pub fn new(
+        session: &mut Session,
+        target: Action<E>,
+        values: Vec<E>,
+    ) -> Result<Self, DuplicateAction> {
+        let o = session.action_name(target.id()).to_owned();
+        let options = values
+            .into_iter()
+            .map(|value| {
+                let source = session.create_action(&format!("{o}-{}", value.format()))?;
+                Ok((source, value))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { target, options })
+    }
+
+    /// The source action which selects each value
+    pub fn options(&self) -> &[(Action<()>, E)] {
+        &self.options
+    }
+}
+
+impl<E: SelectValue> Filter for Select<E> {
+    const NAME: &str = E::FILTER_NAME;
+
+    fn create_source_actions(
+        session: &mut Session,
+        cfg: &FilterConfig,
+    ) -> Result<(), FilterLoadError> {
+        if cfg.targets.len() != 1 {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        }
+        let o = &*cfg.targets[0];
+        for param in &cfg.params {
+            let value = E::parse(param).ok_or_else(|| FilterLoadError::InvalidParam {
+                filter_ty: Self::NAME.to_owned(),
+                param: param.clone(),
+            })?;
+            session.create_action::<()>(&format!("{o}-{}", value.format()))?;
+        }
+        Ok(())
+    }
+
+    fn load(session: &Session, cfg: &FilterConfig) -> Result<Self, FilterLoadError> {
+        let [o] = &*cfg.targets else {
+            return Err(FilterLoadError::WrongOutputCount { expected: 1 });
+        };
+        let target = filter_action(session, Self::NAME, o)?;
+        let options = cfg
+            .params
+            .iter()
+            .filter_map(|param| {
+                let value = E::parse(param)?;
+                let source = session.action_id(&format!("{o}-{}", value.format()))?;
+                Some((session.action(source).ok()?, value))
+            })
+            .collect();
+        Ok(Self { target, options })
+    }
+
+    fn save(&self, session: &Session) -> FilterConfig {
+        FilterConfig {
+            ty: Self::NAME.to_owned(),
+            targets: vec![session.action_name(self.target.id()).to_owned()],
+            params: self
+                .options
+                .iter()
+                .map(|(_, value)| value.format())
+                .collect(),
+        }
+    }
+
+    fn source_actions(&self) -> Vec<ActionId> {
+        self.options.iter().map(|(source, _)| source.id()).collect()
+    }
+
+    fn target_actions(&self) -> Vec<ActionId> {
+        vec![self.target.id()]
+    }
+
+    fn apply(&self, seat: &mut Seat) {
+        for (source, value) in &self.options {
+            while seat.poll(*source).is_some() {
+                let _ = seat.push(self.target.id(), value.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Floating-point functions which are only inherent methods with std, backed
+//! by libm otherwise
+//!
+//! Transcendental functions are also backed by libm with the `deterministic`
+//! feature, since platform implementations may round differently.
+
+// Test builds link std, whose inherent methods take precedence
+#[cfg(not(any(feature = "std", test)))]
+pub(crate) trait F64Ext: Sized {
+    fn round(self) -> Self;
+    fn trunc(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+#[cfg(not(any(feature = "std", test)))]
+impl F64Ext for f64 {
+    fn round(self) -> Self {
+        libm::round(self)
+    }
+
+    fn trunc(self) -> Self {
+        libm::trunc(self)
+    }
+
+    fn rem_euclid(self, rhs: Self) -> Self {
+        let r = self % rhs;
+        if r < 0.0 { r + rhs.abs() } else { r }
+    }
+}
+
+pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+    #[cfg(any(feature = "deterministic", not(feature = "std")))]
+    return libm::hypot(x, y);
+    #[cfg(not(any(feature = "deterministic", not(feature = "std"))))]
+    return x.hypot(y);
+}
+
+pub(crate) fn atan2(y: f64, x: f64) -> f64 {
+    #[cfg(any(feature = "deterministic", not(feature = "std")))]
+    return libm::atan2(y, x);
+    #[cfg(not(any(feature = "deterministic", not(feature = "std"))))]
+    return y.atan2(x);
+}
+
+pub(crate) fn sin_cos(x: f64) -> (f64, f64) {
+    #[cfg(any(feature = "deterministic", not(feature = "std")))]
+    return libm::sincos(x);
+    #[cfg(not(any(feature = "deterministic", not(feature = "std"))))]
+    return x.sin_cos();
+}
+
+/// Stands in for the missing dependency, so that the `compile_error!` in the
+/// crate root is the only error reported
+#[cfg(not(any(feature = "std", feature = "libm")))]
+mod libm {
+    pub(super) fn round(_: f64) -> f64 {
+        unreachable!()
+    }
+
+    pub(super) fn trunc(_: f64) -> f64 {
+        unreachable!()
+    }
+
+    pub(super) fn hypot(_: f64, _: f64) -> f64 {
+        unreachable!()
+    }
+
+    pub(super) fn atan2(_: f64, _: f64) -> f64 {
+        unreachable!()
+    }
+
+    pub(super) fn sincos(_: f64) -> (f64, f64) {
+        unreachable!()
+    }
+}
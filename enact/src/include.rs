@@ -0,0 +1,66 @@
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::error::Error;
+
+use crate::{BindingsFactory, Config, LoadError};
+
+/// Looks up a config named in [`Config::include`]
+pub(crate) type IncludeResolver =
+    Arc<dyn Fn(&str) -> Result<Config, Box<dyn Error + Send + Sync>> + Send + Sync>;
+
+impl BindingsFactory {
+    /// Use `resolver` to look up configs named in [`Config::include`]
+    ///
+    /// Names are passed through verbatim, so they may be file paths, asset
+    /// IDs, or keys in an in-memory map, as `resolver` sees fit. Without a
+    /// resolver, every include is reported as
+    /// [`LoadError::UnresolvedInclude`].
+    pub fn set_include_resolver(
+        &mut self,
+        resolver: impl Fn(&str) -> Result<Config, Box<dyn Error + Send + Sync>> + Send + Sync + 'static,
+    ) {
+        self.include_resolver = Some(Arc::new(resolver));
+    }
+
+    /// `config` with its includes, and theirs, applied beneath it, if it has
+    /// any
+    ///
+    /// `stack` holds the names of the configs being resolved, to detect
+    /// cycles.
+    pub(crate) fn resolve_includes(
+        &self,
+        config: &Config,
+        stack: &mut Vec<String>,
+        errors: &mut Vec<LoadError>,
+    ) -> Option<Config> {
+        if config.include.is_empty() {
+            return None;
+        }
+        let mut out = Config::default();
+        for name in &config.include {
+            if stack.contains(name) {
+                errors.push(LoadError::IncludeCycle { name: name.clone() });
+                continue;
+            }
+            let included = match self.include_resolver {
+                Some(ref resolver) => resolver(name),
+                None => Err("no include resolver".into()),
+            };
+            let included = match included {
+                Ok(x) => x,
+                Err(error) => {
+                    errors.push(LoadError::UnresolvedInclude {
+                        name: name.clone(),
+                        error: error.into(),
+                    });
+                    continue;
+                }
+            };
+            stack.push(name.clone());
+            let resolved = self.resolve_includes(&included, stack, errors);
+            stack.pop();
+            out.apply_overrides(resolved.as_ref().unwrap_or(&included));
+        }
+        out.apply_overrides(config);
+        Some(out)
+    }
+}
@@ -0,0 +1,194 @@
+//! Detection of tampering with configs
+
+use alloc::{format, string::String, sync::Arc};
+
+use crate::{BindingsFactory, Config};
+
+/// Secret key for [`Config::sign`] and [`Config::verify`]
+pub type ConfigKey = [u8; 16];
+
+impl Config {
+    /// Authenticate the current contents with `key`, setting
+    /// [`checksum`](Self::checksum)
+    ///
+    /// The checksum is a SipHash-2-4 MAC of everything but itself, so it can't
+    /// be recomputed after an edit without the key. Shipping the key inside an
+    /// application only deters casual tampering, since determined players can
+    /// extract it; servers holding the key can verify configs uploaded by
+    /// clients with confidence.
+    pub fn sign(&mut self, key: &ConfigKey) {
+        self.checksum = Some(format!("{:016x}", self.mac(key)));
+    }
+
+    /// Whether [`checksum`](Self::checksum) was produced by [`sign`](Self::sign)
+    /// with `key` for the current contents
+    pub fn verify(&self, key: &ConfigKey) -> bool {
+        self.checksum
+            .as_deref()
+            .and_then(|x| u64::from_str_radix(x, 16).ok())
+            == Some(self.mac(key))
+    }
+
+    fn mac(&self, key: &ConfigKey) -> u64 {
+        let mut h = SipHasher::new(key);
+        h.strings(&self.include);
+        h.len(self.devices.len());
+        for device in &self.devices {
+            h.field(device.alias.as_bytes());
+            h.option(
+                device
+                    .vendor_id
+                    .map(u16::to_le_bytes)
+                    .as_ref()
+                    .map(|x| &x[..]),
+            );
+            h.option(
+                device
+                    .product_id
+                    .map(u16::to_le_bytes)
+                    .as_ref()
+                    .map(|x| &x[..]),
+            );
+            h.option(device.name.as_deref().map(str::as_bytes));
+            h.option(device.serial.as_deref().map(str::as_bytes));
+        }
+        h.len(self.sources.len());
+        for source in &self.sources {
+            h.field(source.ty.as_bytes());
+            h.len(source.bindings.len());
+            for (action, inputs) in &source.bindings {
+                h.field(action.as_bytes());
+                h.strings(inputs);
+            }
+        }
+        h.len(self.filters.len());
+        for filter in &self.filters {
+            h.field(filter.ty.as_bytes());
+            h.strings(&filter.targets);
+            h.strings(&filter.params);
+        }
+        let presets = &self.accessibility;
+        h.write(&[
+            u8::from(presets.toggle_holds),
+            u8::from(presets.sticky_conditions),
+        ]);
+        h.option(
+            presets
+                .debounce_ms
+                .map(u32::to_le_bytes)
+                .as_ref()
+                .map(|x| &x[..]),
+        );
+        h.finish()
+    }
+}
+
+impl BindingsFactory {
+    /// Require configs passed to [`load`](Self::load) to pass
+    /// [`Config::verify`] with `key`, or allow any config if `None`
+    ///
+    /// Configs failing verification are reported as
+    /// [`LoadError::Tampered`](crate::LoadError::Tampered) and ignored, as if
+    /// empty, so that defaults from [`Bundle`](crate::Bundle)s and fallbacks
+    /// still apply. Included configs aren't verified, so should come from
+    /// trusted storage. Off by default, so that users may edit configs freely.
+    pub fn set_verify_key(&mut self, key: Option<ConfigKey>) {
+        self.verify_key = key.map(Arc::new);
+    }
+}
+
+/// SipHash-2-4, per Aumasson and Bernstein
+struct SipHasher {
+    v: [u64; 4],
+    /// Bytes not yet compressed, in the low bits
+    tail: u64,
+    /// Total bytes written
+    written: usize,
+}
+
+impl SipHasher {
+    fn new(key: &ConfigKey) -> Self {
+        let k0 = u64::from_le_bytes(key[..8].try_into().unwrap());
+        let k1 = u64::from_le_bytes(key[8..].try_into().unwrap());
+        Self {
+            v: [
+                k0 ^ 0x736f_6d65_7073_6575,
+                k1 ^ 0x646f_7261_6e64_6f6d,
+                k0 ^ 0x6c79_6765_6e65_7261,
+                k1 ^ 0x7465_6462_7974_6573,
+            ],
+            tail: 0,
+            written: 0,
+        }
+    }
+
+    fn round(&mut self) {
+        let [v0, v1, v2, v3] = &mut self.v;
+        *v0 = v0.wrapping_add(*v1);
+        *v1 = v1.rotate_left(13) ^ *v0;
+        *v0 = v0.rotate_left(32);
+        *v2 = v2.wrapping_add(*v3);
+        *v3 = v3.rotate_left(16) ^ *v2;
+        *v0 = v0.wrapping_add(*v3);
+        *v3 = v3.rotate_left(21) ^ *v0;
+        *v2 = v2.wrapping_add(*v1);
+        *v1 = v1.rotate_left(17) ^ *v2;
+        *v2 = v2.rotate_left(32);
+    }
+
+    fn compress(&mut self, m: u64) {
+        self.v[3] ^= m;
+        self.round();
+        self.round();
+        self.v[0] ^= m;
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.tail |= u64::from(byte) << (8 * (self.written % 8));
+            self.written += 1;
+            if self.written.is_multiple_of(8) {
+                let m = self.tail;
+                self.tail = 0;
+                self.compress(m);
+            }
+        }
+    }
+
+    /// Write `bytes` unambiguously, prefixed by their length
+    fn field(&mut self, bytes: &[u8]) {
+        self.len(bytes.len());
+        self.write(bytes);
+    }
+
+    fn len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    fn option(&mut self, bytes: Option<&[u8]>) {
+        match bytes {
+            Some(x) => {
+                self.write(&[1]);
+                self.field(x);
+            }
+            None => self.write(&[0]),
+        }
+    }
+
+    fn strings(&mut self, strings: &[String]) {
+        self.len(strings.len());
+        for x in strings {
+            self.field(x.as_bytes());
+        }
+    }
+
+    fn finish(mut self) -> u64 {
+        let m = self.tail | ((self.written as u64) << 56);
+        self.compress(m);
+        self.v[2] ^= 0xff;
+        for _ in 0..4 {
+            self.round();
+        }
+        self.v.iter().fold(0, |acc, x| acc ^ x)
+    }
+}
@@ -0,0 +1,103 @@
+//! Measurement of delays between handling inputs and consuming their effects
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::time::Duration;
+
+use crate::{Seat, sync::Mutex};
+
+/// Number of most recent samples retained
+const CAPACITY: usize = 4096;
+
+/// Distribution of input latency, from [`Seat::latency`]
+///
+/// Latency is the time from a change to an action's state, e.g. when
+/// [`Bindings::handle`](crate::Bindings::handle) receives an input, until the
+/// application consumes it with [`Seat::poll`] or first observes it with
+/// [`Seat::get`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LatencySummary {
+    /// Number of changes measured
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+/// Recent latency samples
+#[derive(Debug, Default)]
+pub(crate) struct LatencyLog {
+    samples: Mutex<VecDeque<Duration>>,
+}
+
+impl LatencyLog {
+    pub(crate) fn record(&self, latency: Duration) {
+        let mut samples = self.samples.lock();
+        if samples.len() == CAPACITY {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+    }
+}
+
+impl Seat {
+    /// Declare whether to measure input latency
+    ///
+    /// Off by default. Once enabled, consumption of every action's state is
+    /// timed, except by [`Filter`](crate::Filter)s. Only the most recent
+    /// samples are retained. See [`latency`](Self::latency).
+    pub fn set_measure_latency(&mut self, enabled: bool) {
+        if !enabled {
+            self.latency = None;
+        } else if self.latency.is_none() {
+            self.latency = Some(LatencyLog::default());
+        }
+    }
+
+    /// Summarize latency measured since [`set_measure_latency`] was enabled
+    /// or [`reset_latency`](Self::reset_latency) was called, if any changes
+    /// were consumed
+    ///
+    /// [`set_measure_latency`]: Self::set_measure_latency
+    pub fn latency(&self) -> Option<LatencySummary> {
+        let mut samples = self
+            .latency
+            .as_ref()?
+            .samples
+            .lock()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let percentile = |p: usize| samples[(samples.len() - 1) * p / 100];
+        Some(LatencySummary {
+            samples: samples.len(),
+            min: samples[0],
+            median: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: samples[samples.len() - 1],
+        })
+    }
+
+    /// Discard latency samples measured so far
+    pub fn reset_latency(&mut self) {
+        if let Some(ref mut log) = self.latency {
+            log.samples.get_mut().clear();
+        }
+    }
+
+    /// Record that a change made at `at` was consumed
+    pub(crate) fn consumed(&self, at: Duration) {
+        let Some(ref log) = self.latency else {
+            return;
+        };
+        if !self.filtering {
+            log.record(self.now().saturating_sub(at));
+        }
+    }
+}
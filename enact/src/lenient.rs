@@ -0,0 +1,77 @@
+//! Tolerance for trivial formatting differences in hand-edited configs
+
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+use crate::{Config, Input, Session};
+
+/// `s` in lowercase, without whitespace, `_`, or `-`
+///
+/// Namespace separators are kept, so that e.g. `ui.back` and `uib.ack` remain
+/// distinct.
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|&c| !c.is_whitespace() && c != '_' && c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// The canonical name of the action which `name` refers to, if it only
+/// differs trivially
+fn action_name<'a>(session: &'a Session, name: &str) -> Option<&'a str> {
+    if session.action_id(name).is_some() || session.alias_target(name).is_some() {
+        return None;
+    }
+    let normalized = normalize(name);
+    let mut matches = session
+        .actions
+        .iter()
+        .map(|def| &*def.name)
+        .filter(|x| normalize(x) == normalized);
+    let first = matches.next()?;
+    // Ambiguous matches are left to be reported as unknown
+    matches.next().is_none().then_some(first)
+}
+
+/// Replace action names in `config` which differ trivially from those in
+/// `session` with their canonical forms, and trim inputs
+pub(crate) fn canonicalize(session: &Session, config: &Config) -> Config {
+    let canonical = |name: &str| {
+        let name = name.trim();
+        action_name(session, name).unwrap_or(name).to_owned()
+    };
+    let mut out = config.clone();
+    for source in &mut out.sources {
+        for (action, inputs) in &mut source.bindings {
+            *action = canonical(action);
+            for input in inputs {
+                *input = match input.rsplit_once(" if ") {
+                    Some((input, condition)) => {
+                        format!("{} if {}", input.trim(), canonical(condition))
+                    }
+                    None => input.trim().to_owned(),
+                };
+            }
+        }
+    }
+    for filter in &mut out.filters {
+        for target in &mut filter.targets {
+            *target = canonical(target);
+        }
+    }
+    out
+}
+
+/// Like [`Input::from_str`], but tolerating differences in case and
+/// separators from the names in [`Input::names`]
+pub(crate) fn parse_input<I: Input>(s: &str) -> Vec<I> {
+    let lower = s.to_ascii_lowercase();
+    let inputs = I::from_str(&lower);
+    if !inputs.is_empty() {
+        return inputs;
+    }
+    let normalized = normalize(s);
+    I::names()
+        .into_iter()
+        .find(|name| normalize(name) == normalized)
+        .map_or_else(Vec::new, |name| I::from_str(&name))
+}
@@ -6,31 +6,204 @@
 //! Different input sources can be added without modifying the core library, and
 //! bindings covering any number of input sources can be saved to and loaded
 //! from config files and updated on the fly.
+//!
+//! Only `alloc` is required, for use on embedded and other `no_std` targets:
+//! disable default features and enable `libm`. Without the `std` feature,
+//! [`Seat`]s can't be shared between threads, there's no default [`Clock`], and
+//! the `ron`, `rayon`, and `bench` features are unavailable.
+//!
+//! Lockstep multiplayer games, which must run the same bindings on every peer
+//! and get the same results, should enable the `deterministic` feature. Built-in
+//! filters then use portable implementations of functions such as `atan2` in
+//! place of the platform's, and otherwise rely only on exactly-rounded
+//! arithmetic. Peers must also agree on time, e.g. by advancing a
+//! [`ManualClock`] once per simulation tick.
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-use std::{
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("the `libm` feature is required without `std`");
+
+use alloc::{
+    borrow::ToOwned, boxed::Box, collections::VecDeque, format, string::String, sync::Arc, vec,
+    vec::Vec,
+};
+use core::{
     any::{Any, TypeId, type_name},
-    collections::VecDeque,
     fmt,
     hash::Hash,
     marker::PhantomData,
-    sync::RwLock,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Duration,
 };
 
+mod accessibility;
+mod action_enum;
+#[cfg(feature = "bench")]
+pub mod bench;
+#[cfg(feature = "postcard")]
+mod binary;
+mod bundle;
+mod canonical;
+mod clock;
+mod context;
+mod debug;
+mod device;
+mod dispatcher;
+mod edit;
+mod fairplay;
 pub mod filter;
+mod float;
+mod include;
+mod integrity;
+mod latency;
+mod lenient;
+mod macros;
+mod memory;
+mod pool;
+mod queue;
+mod replay;
+#[cfg(feature = "ron")]
+mod ron_format;
+#[cfg(feature = "serde")]
+pub mod serialize;
+mod stack;
+mod steam;
+mod sync;
+mod timestep;
 mod type_id_map;
+mod vector;
+mod view;
 
 use iddqd::BiHashMap;
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::FxBuildHasher;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use slab::Slab;
-
+use sync::{Mutex, RwLock};
+
+pub use accessibility::Accessibility;
+pub use action_enum::{ActionEnum, ActionSet};
+#[cfg(feature = "postcard")]
+pub use binary::BinaryConfigError;
+pub use bundle::Bundle;
+pub use clock::{Clock, ManualClock};
+#[cfg(feature = "std")]
+pub use clock::{ScaledClock, SystemClock};
+pub use context::Contexts;
+pub use debug::ActionSnapshot;
+pub use device::{DeviceAlias, DeviceInfo};
+pub use dispatcher::Dispatcher;
+pub use edit::BindingsEdit;
+#[cfg(feature = "derive")]
+pub use enact_derive::ActionEnum;
+pub use fairplay::{SynthesisFlag, SynthesisKind};
+pub use integrity::ConfigKey;
+use latency::LatencyLog;
+pub use latency::LatencySummary;
+#[doc(hidden)]
+pub use macros::__private;
+pub use memory::ActionMemory;
+use pool::AnyColumn;
+pub use pool::{PooledSeat, SeatPool};
+use queue::{BitQueue, Queue, UnitQueue};
+pub use replay::{Divergence, InputValue, RecordedEvent, RecordedInput, Recorder, Recording};
+#[cfg(feature = "ron")]
+pub use ron_format::RonError;
+pub use stack::BindingStack;
+pub use steam::{SteamInput, SteamManifest};
+pub use timestep::{FixedTimestep, Step, Steps};
 use type_id_map::TypeIdMap;
+pub use vector::Vector2;
+use vector::VectorType;
+pub use view::{ActionView, BindingEntry, BindingView, GroupView};
+
+/// Hash map using rustc-hash's fast hasher, from hashbrown so as not to require
+/// std
+pub(crate) type FxHashMap<K, V> = hashbrown::HashMap<K, V, FxBuildHasher>;
+pub(crate) type FxHashSet<T> = hashbrown::HashSet<T, FxBuildHasher>;
 
 /// A collection of [`Action`] definitions
-#[derive(Default, Clone)]
+///
+/// Any number of [`Bindings`] (e.g. one per player) may be used with the same
+/// [`Session`]. Using [`Bindings`] or [`Seat`]s with a [`Session`] other than
+/// the one they were created with panics.
+///
+/// Sessions are immutable once shared: they may be wrapped in an [`Arc`] to
+/// perform lookups from multiple threads. Cloning is cheap, with storage
+/// shared until either clone is modified, so a clone can also serve as a
+/// snapshot to hand to e.g. an asset loading thread, and [`Bindings`] built
+/// with it may be used with the original. A clone becomes a distinct session
+/// once it's modified, since it may then define different actions.
 pub struct Session {
-    actions: BiHashMap<ActionDefinition, rustc_hash::FxBuildHasher>,
+    /// Identifies this session and its unmodified clones, to detect mixing of
+    /// sessions
+    id: SessionId,
+    /// Whether this is a clone that takes a fresh `id` when first modified
+    borrowed_id: bool,
+    actions: Arc<BiHashMap<ActionDefinition, FxBuildHasher>>,
+    /// Former names of actions, accepted when loading configs
+    aliases: Arc<FxHashMap<String, ActionId>>,
+    /// Actions which every config is expected to bind
+    required: Arc<FxHashSet<ActionId>>,
+    /// Current generation of each action index
+    generations: Vec<u32>,
+    /// Indices of removed actions available for reuse
+    free: Vec<u32>,
+    /// Formatters registered with [`Session::register_debug`]
+    formatters: Arc<FxHashMap<TypeId, debug::Formatter>>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            id: SessionId::new(),
+            borrowed_id: false,
+            actions: Default::default(),
+            aliases: Default::default(),
+            required: Default::default(),
+            generations: Default::default(),
+            free: Default::default(),
+            formatters: Default::default(),
+        }
+    }
+}
+
+impl Clone for Session {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            borrowed_id: true,
+            actions: self.actions.clone(),
+            aliases: self.aliases.clone(),
+            required: self.required.clone(),
+            generations: self.generations.clone(),
+            free: self.free.clone(),
+            formatters: self.formatters.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct SessionId(u64);
+
+impl SessionId {
+    fn new() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Separates an action's namespace from the rest of its name
+const NAMESPACE_SEPARATOR: char = '.';
+
+/// The namespace of the action named `name`, if any
+fn namespace_of(name: &str) -> Option<&str> {
+    Some(name.split_once(NAMESPACE_SEPARATOR)?.0)
 }
 
 impl Session {
@@ -39,6 +212,14 @@ impl Session {
         Self::default()
     }
 
+    /// Stop sharing an id with the session this was cloned from, if any
+    fn modified(&mut self) {
+        if self.borrowed_id {
+            self.id = SessionId::new();
+            self.borrowed_id = false;
+        }
+    }
+
     /// Create an action with the unique identifier `name`
     ///
     /// `name` will be used to identify the action in config files and
@@ -47,35 +228,166 @@ impl Session {
     ///
     /// See [`Action`] for discussion of action design.
     pub fn create_action<T: 'static>(&mut self, name: &str) -> Result<Action<T>, DuplicateAction> {
-        let id = ActionId(u32::try_from(self.actions.len()).expect("too many actions"));
-        if self
-            .actions
-            .insert_unique(ActionDefinition {
-                id,
-                name: name.into(),
-                ty: TypeId::of::<T>(),
-                ty_name: std::any::type_name::<T>(),
-            })
-            .is_err()
+        let index = match self.free.last() {
+            Some(&index) => index,
+            None => u32::try_from(self.generations.len()).expect("too many actions"),
+        };
+        let id = ActionId {
+            index,
+            generation: self.generations.get(index as usize).copied().unwrap_or(0),
+        };
+        if self.aliases.contains_key(name)
+            || Arc::make_mut(&mut self.actions)
+                .insert_unique(ActionDefinition {
+                    id,
+                    name: name.into(),
+                    ty: TypeId::of::<T>(),
+                    ty_name: type_name::<T>(),
+                })
+                .is_err()
         {
             return Err(DuplicateAction {
                 name: name.to_owned(),
             });
         }
+        if self.free.pop().is_none() {
+            self.generations.push(0);
+        }
+        self.modified();
         Ok(Action {
             id,
             _marker: PhantomData,
         })
     }
 
+    /// Create an action named `name` within `namespace`
+    ///
+    /// The action's full name is `namespace.name`, e.g. `ui.confirm`, so that
+    /// libraries and plugins can define actions without clashing with each
+    /// other or with the host application, provided the host avoids `.` in its
+    /// own names. Actions can be enumerated by namespace with
+    /// [`actions_in`](Self::actions_in).
+    ///
+    /// Panics if `namespace` is empty or contains `.`
+    pub fn create_action_in<T: 'static>(
+        &mut self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<Action<T>, DuplicateAction> {
+        assert!(
+            !namespace.is_empty() && !namespace.contains(NAMESPACE_SEPARATOR),
+            "invalid namespace: {namespace:?}"
+        );
+        self.create_action(&format!("{namespace}{NAMESPACE_SEPARATOR}{name}"))
+    }
+
+    /// Remove the action associated with an [`ActionId`], along with any
+    /// aliases for it
+    ///
+    /// The action's name becomes available for reuse. Outstanding handles to
+    /// the removed action become stale: [`Session`] methods will panic if given
+    /// them, and [`Seat`]s will refuse to store state for them. Bindings and
+    /// filters referring to the removed action are left dangling.
+    ///
+    /// Returns whether `id` referred to a live action.
+    pub fn remove_action(&mut self, id: ActionId) -> bool {
+        if Arc::make_mut(&mut self.actions).remove1(&id).is_none() {
+            return false;
+        }
+        Arc::make_mut(&mut self.aliases).retain(|_, &mut target| target != id);
+        if self.required.contains(&id) {
+            Arc::make_mut(&mut self.required).remove(&id);
+        }
+        self.generations[id.index as usize] += 1;
+        self.free.push(id.index);
+        self.modified();
+        true
+    }
+
+    /// Whether `id` refers to an action which currently exists in this
+    /// [`Session`]
+    ///
+    /// False for actions which have been removed.
+    pub fn contains(&self, id: ActionId) -> bool {
+        self.actions.get1(&id).is_some()
+    }
+
+    fn definition(&self, id: ActionId) -> &ActionDefinition {
+        if let Some(act) = self.actions.get1(&id) {
+            return act;
+        }
+        match self.generations.get(id.index as usize) {
+            Some(&generation) if generation > id.generation => {
+                panic!("stale action handle: action was removed from the session")
+            }
+            _ => panic!("no such action"),
+        }
+    }
+
+    /// Register `alias` as a former name of the action associated with an
+    /// [`ActionId`]
+    ///
+    /// Configs which refer to `alias` will be loaded as if they referred to the
+    /// action's current name, reporting [`LoadError::DeprecatedAction`], so
+    /// that renaming an action doesn't break existing configs. Saved configs
+    /// always use the current name.
+    ///
+    /// Panics if `id` was not defined in this [`Session`] or has been removed
+    pub fn create_alias(&mut self, id: ActionId, alias: &str) -> Result<(), DuplicateAction> {
+        self.definition(id);
+        if self.actions.get2(alias).is_some() || self.aliases.contains_key(alias) {
+            return Err(DuplicateAction {
+                name: alias.to_owned(),
+            });
+        }
+        Arc::make_mut(&mut self.aliases).insert(alias.to_owned(), id);
+        self.modified();
+        Ok(())
+    }
+
+    /// Set whether the action associated with an [`ActionId`] must be bound
+    ///
+    /// [`BindingsFactory::load`] reports [`LoadError::UnboundRequiredAction`]
+    /// for each required action which is neither bound to an input nor
+    /// produced by a filter, so that a config can't silently leave the player
+    /// unable to e.g. open the pause menu.
+    ///
+    /// Panics if `id` was not defined in this [`Session`] or has been removed
+    pub fn set_required(&mut self, id: ActionId, required: bool) {
+        self.definition(id);
+        if self.required.contains(&id) != required {
+            let set = Arc::make_mut(&mut self.required);
+            if required {
+                set.insert(id);
+            } else {
+                set.remove(&id);
+            }
+            self.modified();
+        }
+    }
+
+    /// Whether the action associated with an [`ActionId`] is required
+    ///
+    /// See [`set_required`](Self::set_required).
+    pub fn is_required(&self, id: ActionId) -> bool {
+        self.required.contains(&id)
+    }
+
+    /// Get the [`ActionId`] formerly identified by `alias`, if any
+    ///
+    /// See [`create_alias`](Self::create_alias).
+    pub fn alias_target(&self, alias: &str) -> Option<ActionId> {
+        self.aliases.get(alias).copied()
+    }
+
     /// Get the a typed [`Action`] handle associated with an [`ActionId`]
     ///
-    /// Panics if `id` was not defined in this [`Session`]
+    /// Panics if `id` was not defined in this [`Session`] or has been removed
     pub fn action<T: 'static>(&self, id: ActionId) -> Result<Action<T>, TypeError> {
-        let act = self.actions.get1(&id).expect("no such action");
+        let act = self.definition(id);
         if act.ty != TypeId::of::<T>() {
             return Err(TypeError {
-                expected: std::any::type_name::<T>(),
+                expected: type_name::<T>(),
                 actual: act.ty_name,
             });
         }
@@ -85,6 +397,28 @@ impl Session {
         })
     }
 
+    /// Enumerate all actions, in no particular order
+    pub fn actions(&self) -> impl Iterator<Item = ActionId> + '_ {
+        self.actions.iter().map(|act| act.id)
+    }
+
+    /// Enumerate actions in `namespace`, in no particular order
+    ///
+    /// See [`create_action_in`](Self::create_action_in).
+    pub fn actions_in<'a>(&'a self, namespace: &'a str) -> impl Iterator<Item = ActionId> + 'a {
+        self.actions
+            .iter()
+            .filter(move |act| namespace_of(&act.name) == Some(namespace))
+            .map(|act| act.id)
+    }
+
+    /// Get the namespace of the action associated with an [`ActionId`], if any
+    ///
+    /// Panics if `id` was not defined in this [`Session`] or has been removed
+    pub fn action_namespace(&self, id: ActionId) -> Option<&str> {
+        namespace_of(&self.definition(id).name)
+    }
+
     /// Get the [`ActionId`] identified by `name`, if any
     pub fn action_id(&self, name: &str) -> Option<ActionId> {
         Some(self.actions.get2(name)?.id)
@@ -92,9 +426,9 @@ impl Session {
 
     /// Get the name of the action associated with an [`ActionId`]
     ///
-    /// Panics if `id` was not defined in this [`Session`]
+    /// Panics if `id` was not defined in this [`Session`] or has been removed
     pub fn action_name(&self, id: ActionId) -> &str {
-        &self.actions.get1(&id).unwrap().name
+        &self.definition(id).name
     }
 
     /// Check whether an [`Input`] can be bound to the action associated with an
@@ -103,16 +437,16 @@ impl Session {
     /// Inputs can only be bound to actions if they produce events of the same
     /// Rust type that the action was created with.
     ///
-    /// Panics if `id` was not defiend in this [`Session`]
+    /// Panics if `id` was not defiend in this [`Session`] or has been removed
     pub fn check_type<I: Input>(&self, id: ActionId, input: &I) -> Result<(), TypeError> {
-        let act = self.actions.get1(&id).expect("no such action");
+        let act = self.definition(id);
         if act.ty == input.visit_type::<GetTypeId>() {
             return Ok(());
         }
-        return Err(TypeError {
+        Err(TypeError {
             expected: input.visit_type::<GetTypeName>(),
             actual: act.ty_name,
-        });
+        })
     }
 }
 
@@ -128,7 +462,7 @@ impl fmt::Display for DuplicateAction {
     }
 }
 
-impl std::error::Error for DuplicateAction {}
+impl core::error::Error for DuplicateAction {}
 
 /// A mismatch between the type of an input and an action, or between the type
 /// of some data and the type described by an input.
@@ -144,7 +478,7 @@ impl fmt::Display for TypeError {
     }
 }
 
-impl std::error::Error for TypeError {}
+impl core::error::Error for TypeError {}
 
 #[derive(Clone)]
 struct ActionDefinition {
@@ -171,7 +505,10 @@ impl iddqd::BiHashItem for ActionDefinition {
 }
 
 /// Identifies a unique bindable input, such as a specific button
-pub trait Input: Hash + Eq + Clone + 'static {
+///
+/// With the `rayon` feature, inputs must also be `Send + Sync`; see
+/// [`MaybeSendSync`].
+pub trait Input: Hash + Eq + Clone + MaybeSendSync + 'static {
     /// A globally unique human-readable identifier for this type of input
     ///
     /// Used in [`Config`] to identify each input type. A single
@@ -184,7 +521,9 @@ pub trait Input: Hash + Eq + Clone + 'static {
 
     /// Enumerate all inputs that `s` could represent
     ///
-    /// Must return at most one input of any given type
+    /// Must return at most one input of any given type, and must not depend
+    /// on the platform, so that configs behave the same everywhere. See
+    /// [`BindingsFactory::canonical_names`].
     fn from_str(s: &str) -> Vec<Self>;
 
     /// Generate a human-readable string identifying this input
@@ -192,6 +531,117 @@ pub trait Input: Hash + Eq + Clone + 'static {
     /// [`from_str`](Self::from_str) on the resulting string must include a
     /// value equivalent to `self` in its result
     fn to_string(&self) -> String;
+
+    /// Enumerate the canonical strings accepted by [`from_str`](Self::from_str)
+    ///
+    /// Useful for autocompletion in binding editors, and used to suggest
+    /// corrections for unrecognized inputs in [`LoadError::UnknownInput`].
+    /// Sources whose inputs can't be listed exhaustively (e.g. arbitrary
+    /// numbered buttons) should list only the common ones.
+    fn names() -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The kind of device that produces this input
+    ///
+    /// Reported by [`Seat::last_source`] so that applications can display
+    /// button glyphs matching the device the user is currently using.
+    fn device_family(&self) -> DeviceFamily {
+        DeviceFamily::Generic
+    }
+
+    /// Stable name of the glyph depicting this input, if any
+    ///
+    /// Names are `/`-separated paths beginning with
+    /// [`DeviceFamily::glyph_prefix`], e.g. `kbm/space`, `kbm/mouse-left`, or
+    /// `pad/xbox/a`, and otherwise contain only lowercase ASCII letters,
+    /// digits, and `-`. They don't change between releases, so asset
+    /// pipelines can pre-bake icon atlases keyed by them. Usually built with
+    /// [`DeviceFamily::glyph`].
+    fn glyph(&self) -> Option<String> {
+        None
+    }
+}
+
+/// `Send + Sync` with the `rayon` feature, which builds the bindings for each
+/// input source on its own thread, and no bound otherwise
+#[cfg(feature = "rayon")]
+pub trait MaybeSendSync: Send + Sync {}
+#[cfg(feature = "rayon")]
+impl<T: Send + Sync + ?Sized> MaybeSendSync for T {}
+
+/// `Send + Sync` with the `rayon` feature, which builds the bindings for each
+/// input source on its own thread, and no bound otherwise
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSendSync {}
+#[cfg(not(feature = "rayon"))]
+impl<T: ?Sized> MaybeSendSync for T {}
+
+/// Broad categories of input device, for selecting button glyphs
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum DeviceFamily {
+    /// Unknown or unspecified hardware
+    #[default]
+    Generic,
+    KeyboardMouse,
+    Xbox,
+    PlayStation,
+    Switch,
+}
+
+impl DeviceFamily {
+    /// Leading component of glyph names for inputs from this family
+    ///
+    /// One of `generic`, `kbm`, `pad/xbox`, `pad/playstation`, or
+    /// `pad/switch`. See [`Input::glyph`].
+    pub fn glyph_prefix(self) -> &'static str {
+        match self {
+            DeviceFamily::Generic => "generic",
+            DeviceFamily::KeyboardMouse => "kbm",
+            DeviceFamily::Xbox => "pad/xbox",
+            DeviceFamily::PlayStation => "pad/playstation",
+            DeviceFamily::Switch => "pad/switch",
+        }
+    }
+
+    /// Glyph name for the control called `name` on this family of device
+    ///
+    /// `name` is lowercased, and spaces become `-`, so input names like
+    /// `"mouse left"` can be used directly. Returns `None` if `name` contains
+    /// other characters that aren't ASCII letters, digits, or `-`.
+    pub fn glyph(self, name: &str) -> Option<String> {
+        let name = name.trim();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == ' ')
+        {
+            return None;
+        }
+        let name = name.to_ascii_lowercase().replace(' ', "-");
+        Some(format!("{}/{name}", self.glyph_prefix()))
+    }
+}
+
+/// The most recent source of input to a [`Seat`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LastSource {
+    /// [`Input::NAME`] of the input source
+    pub source: &'static str,
+    pub family: DeviceFamily,
+}
+
+/// The input which caused a change to an action's state
+///
+/// See [`Seat::poll_with_source`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSource {
+    /// [`Input::NAME`] of the input source
+    pub source: &'static str,
+    pub family: DeviceFamily,
+    /// The input, from [`Input::to_string`]
+    pub input: String,
 }
 
 /// Returns `Some` iff `input` produces events of type `T`
@@ -199,6 +649,43 @@ pub fn has_type<T: 'static, I: Input>(input: &I) -> bool {
     input.visit_type::<GetTypeId>() == TypeId::of::<T>()
 }
 
+/// Something which routes input events to actions, i.e. [`Bindings`] or a
+/// [`BindingStack`]
+///
+/// Input handlers provided by backend crates accept any implementation.
+pub trait HandleInput {
+    /// Change the state of `input` to `data` in `seat`
+    ///
+    /// See [`Bindings::handle`].
+    fn handle<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+    ) -> Result<(), TypeError>;
+
+    /// Like [`handle`](Self::handle), but if `consumed` is set, only deliver
+    /// releases
+    ///
+    /// `consumed` should be set when an immediate-mode UI reports that it
+    /// wants the event, e.g. while a text field has keyboard focus. Button
+    /// presses and other input are then suppressed, while releases (`false`
+    /// data) still go through so that a button held when the UI took focus
+    /// doesn't get stuck.
+    fn handle_consumed<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+        consumed: bool,
+    ) -> Result<(), TypeError> {
+        if consumed && (&data as &dyn Any).downcast_ref::<bool>() != Some(&false) {
+            return Ok(());
+        }
+        self.handle(input, data, seat)
+    }
+}
+
 /// Helper to inspect the type of data associated with an [`Input`] via
 /// [`Input::visit_type`]
 pub trait InputTypeVisitor {
@@ -227,16 +714,38 @@ impl InputTypeVisitor for GetTypeName {
 /// Parses bindings for arbitrary input types from serialized form
 #[derive(Clone)]
 pub struct BindingsFactory {
-    input_binding_builders: FxHashMap<
-        &'static str,
-        (
-            TypeId,
-            fn(&Session, &SourceConfig) -> (Box<dyn AnyInputBindings>, Vec<LoadError>),
-        ),
-    >,
+    input_binding_builders: FxHashMap<&'static str, SourceBuilder>,
     filter_builders: FxHashMap<&'static str, FilterBuilder>,
+    /// Bindings applied to actions left unbound by a config, in registration
+    /// order
+    fallbacks: Vec<(ActionId, Fallback)>,
+    /// Defaults from registered [`Bundle`]s, with namespaced action names
+    bundles: Vec<Config>,
+    include_resolver: Option<include::IncludeResolver>,
+    /// See [`BindingsFactory::set_lenient`]
+    lenient: bool,
+    /// See [`BindingsFactory::set_verify_key`]
+    verify_key: Option<Arc<ConfigKey>>,
+    /// See [`BindingsFactory::set_accessibility`]
+    accessibility: Accessibility,
+}
+
+#[derive(Copy, Clone)]
+struct SourceBuilder {
+    ty: TypeId,
+    build: InputBindingsBuilder,
+    /// See [`canonical::input`]
+    canonical_input: fn(&str) -> Option<String>,
+    /// See [`canonical::names`]
+    canonical_names: fn() -> Vec<String>,
 }
 
+/// Loads a [`SourceConfig`], leniently if set
+type InputBindingsBuilder =
+    fn(&Session, &SourceConfig, bool) -> (Box<dyn AnyInputBindings>, Vec<LoadError>);
+
+type Fallback = Arc<dyn Fn(&mut Bindings, &Session) -> Result<(), BindError> + Send + Sync>;
+
 /// A mechanism to compute virtual inputs
 pub trait Filter: Sized + 'static + Clone {
     /// A globally unique human-readable identifier for this type of filter
@@ -275,6 +784,21 @@ impl BindingsFactory {
     pub fn new() -> Self {
         let mut out = Self::empty();
         out.register_filter::<filter::DPad>();
+        out.register_filter::<filter::Drag>();
+        out.register_filter::<filter::Macro>();
+        out.register_filter::<filter::Ramp>();
+        out.register_filter::<filter::Snap>();
+        out.register_filter::<filter::Sector>();
+        out.register_filter::<filter::Scroll>();
+        out.register_filter::<filter::Charge>();
+        out.register_filter::<filter::Stages>();
+        out.register_filter::<filter::Socd>();
+        out.register_filter::<filter::Scale>();
+        out.register_filter::<filter::Toggle>();
+        out.register_filter::<filter::Debounce>();
+        out.register_filter::<filter::Scan>();
+        out.register_filter::<filter::Select<i64>>();
+        out.register_filter::<filter::Select<String>>();
         out
     }
 
@@ -283,55 +807,100 @@ impl BindingsFactory {
         Self {
             input_binding_builders: Default::default(),
             filter_builders: Default::default(),
+            fallbacks: Vec::new(),
+            bundles: Vec::new(),
+            include_resolver: None,
+            lenient: false,
+            verify_key: None,
+            accessibility: Accessibility::default(),
         }
     }
 
+    /// Tolerate trivial formatting differences in loaded configs
+    ///
+    /// When set, action names and inputs which aren't recognized exactly are
+    /// matched ignoring ASCII case, surrounding whitespace, and separators
+    /// (spaces, `_`, and `-`), so e.g. `"LeftShift"` is accepted for `"left
+    /// shift"`. Inputs must differ from one of their source's
+    /// [`Input::names`] only in these ways. Ambiguous names are still
+    /// rejected. Since [`Bindings::save`] always writes canonical names,
+    /// saving a leniently loaded config fixes its formatting. Off by default.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+
+    /// Bind `input` to `action` in loaded [`Bindings`] if the config leaves
+    /// `action` unbound
+    ///
+    /// Fallbacks apply only to actions which no source binds and no filter
+    /// produces, ensuring that a corrupted or partial config can't leave
+    /// essential controls dead without overriding the user's choices. Any
+    /// number of fallbacks may be registered for the same action.
+    pub fn add_fallback<I: Input + Send + Sync>(
+        &mut self,
+        session: &Session,
+        action: ActionId,
+        input: I,
+    ) -> Result<(), TypeError> {
+        session.check_type(action, &input)?;
+        self.fallbacks.push((
+            action,
+            Arc::new(move |bindings, session| bindings.bind(input.clone(), action, session)),
+        ));
+        Ok(())
+    }
+
     /// Enable loading configurations that include inputs of type `I`
     pub fn register_source<I: Input>(&mut self) {
         self.input_binding_builders.insert(
             I::NAME,
-            (TypeId::of::<I>(), |session, cfg| {
-                let mut bindings = FxHashMap::<I, Vec<ActionId>>::default();
-                let mut errors = Vec::new();
-                for (name, inputs) in &cfg.bindings {
-                    let Some(action) = session.action_id(name) else {
-                        errors.push(LoadError::UnknownAction { name: name.clone() });
-                        continue;
-                    };
-                    for input_str in inputs {
-                        let inputs = I::from_str(input_str);
-                        if inputs.is_empty() {
-                            errors.push(LoadError::UnknownInput {
-                                input: input_str.clone(),
-                            });
-                            continue;
-                        }
-                        let mut expected = Vec::new();
-                        let mut success = false;
-                        for input in inputs {
-                            if let Err(error) = session.check_type(action, &input) {
-                                expected.push(error.expected);
-                            } else {
-                                bindings.entry(input).or_default().push(action);
-                                success = true;
-                                break;
-                            }
-                        }
-                        if !success {
-                            errors.push(LoadError::InputTypeError {
-                                action_name: name.clone(),
-                                input: input_str.clone(),
-                                actual: session.actions.get1(&action).unwrap().ty_name,
-                                expected,
-                            })
-                        }
-                    }
-                }
-                (Box::new(InputBindings { bindings }), errors)
-            }),
+            SourceBuilder {
+                ty: TypeId::of::<I>(),
+                build: build_source::<I>,
+                canonical_input: canonical::input::<I>,
+                canonical_names: canonical::names::<I>,
+            },
         );
     }
 
+    /// The canonical spelling of the input named `input` from the source
+    /// named `source`
+    ///
+    /// Trivial formatting differences are tolerated as by
+    /// [`set_lenient`](Self::set_lenient). Returns `None` if no such source
+    /// is registered or it doesn't recognize `input`.
+    pub fn canonical_input(&self, source: &str, input: &str) -> Option<String> {
+        (self.input_binding_builders.get(source)?.canonical_input)(input)
+    }
+
+    /// Rewrite every input in `config` in its canonical spelling
+    ///
+    /// Backends parse their canonical spellings identically on every
+    /// platform, so a config canonicalized before shipping behaves the same
+    /// on Windows, macOS, Linux, and the web, whichever backend serves each.
+    /// Inputs which aren't recognized are left unchanged, to be reported by
+    /// [`load`](Self::load).
+    pub fn canonicalize(&self, config: &Config) -> Config {
+        canonical::canonicalize(config, |source, input| self.canonical_input(source, input))
+    }
+
+    /// The canonical spellings of the common inputs of each registered
+    /// source, sorted by [`Input::NAME`]
+    ///
+    /// Derived from [`Input::names`]. Intended to be checked in as a golden
+    /// file and compared against in tests on each target platform, so that
+    /// any backend which parses differently somewhere is caught before a
+    /// config relying on it ships.
+    pub fn canonical_names(&self) -> Vec<(&'static str, Vec<String>)> {
+        let mut out = self
+            .input_binding_builders
+            .iter()
+            .map(|(&name, builder)| (name, (builder.canonical_names)()))
+            .collect::<Vec<_>>();
+        out.sort_unstable_by_key(|&(name, _)| name);
+        out
+    }
+
     /// Enable loading filters of type `F`
     pub fn register_filter<F: Filter>(&mut self) {
         self.filter_builders.insert(
@@ -353,10 +922,33 @@ impl BindingsFactory {
     ///
     /// Malformed inputs will be recorded in the returned [`LoadError`]s, but
     /// will not terminate parsing: all well-formed bindings will be included in
-    /// the resulting [`Bindings`].
+    /// the resulting [`Bindings`]. Configs named in [`Config::include`] are
+    /// resolved with the resolver passed to
+    /// [`set_include_resolver`](Self::set_include_resolver), and included
+    /// beneath `config`, as are defaults from registered [`Bundle`]s.
     pub fn load(&self, session: &mut Session, config: &Config) -> (Bindings, Vec<LoadError>) {
-        let mut bindings = Bindings::new();
         let mut errors = Vec::new();
+        let empty = Config::default();
+        let config = match self.verify_key {
+            Some(ref key) if !config.verify(key) => {
+                errors.push(LoadError::Tampered);
+                &empty
+            }
+            _ => config,
+        };
+        let resolved = self.resolve_includes(config, &mut Vec::new(), &mut errors);
+        let config = resolved.as_ref().unwrap_or(config);
+        let merged = self.with_bundles(config);
+        let config = merged.as_ref().unwrap_or(config);
+        let canonical = self.lenient.then(|| lenient::canonicalize(session, config));
+        let config = canonical.as_ref().unwrap_or(config);
+        let presets = self.accessibility.union(&config.accessibility);
+        let accessible = accessibility::apply(session, config, presets);
+        let config = accessible.as_ref().unwrap_or(config);
+        let mut bindings = Bindings::new();
+        bindings.session = Some(session.id);
+        bindings.devices = config.devices.clone();
+        bindings.accessibility = config.accessibility;
 
         // Create all filter source actions first so that filters can be chained arbitrarily
         let mut filter_builders = Vec::with_capacity(config.filters.len());
@@ -388,17 +980,69 @@ impl BindingsFactory {
             }
         }
 
-        for source in &config.sources {
-            let Some((ty, builder)) = self.input_binding_builders.get(&*source.ty) else {
+        // Source builders are independent, so large configs can be built in
+        // parallel
+        let build = |source: &SourceConfig| {
+            let builder = self.input_binding_builders.get(&*source.ty)?;
+            Some((builder.ty, (builder.build)(session, source, self.lenient)))
+        };
+        #[cfg(feature = "rayon")]
+        let built = {
+            use rayon::prelude::*;
+            config.sources.par_iter().map(build).collect::<Vec<_>>()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let built = config.sources.iter().map(build).collect::<Vec<_>>();
+        for (source, built) in config.sources.iter().zip(built) {
+            let Some((ty, (built, source_errors))) = built else {
                 errors.push(LoadError::UnknownSource {
                     name: source.ty.clone(),
                 });
                 continue;
             };
-            let (built, source_errors) = builder(session, source);
             // Future work: Merge duplicates?
-            bindings.actions.insert(*ty, built);
-            errors.extend(source_errors.into_iter());
+            bindings.actions.insert(ty, built);
+            errors.extend(source_errors);
+        }
+
+        let bound = bindings
+            .actions
+            .values()
+            .flat_map(|source| source.actions())
+            .collect::<FxHashSet<_>>();
+        for &action in &bound {
+            if let Some(filter) = bindings.producer(action) {
+                errors.push(LoadError::BoundFilterTarget {
+                    action: session.action_name(action).to_owned(),
+                    filter_ty: bindings.filter_ty(filter).to_owned(),
+                });
+            }
+        }
+        let unbound = self
+            .fallbacks
+            .iter()
+            .map(|&(action, _)| action)
+            .filter(|&action| {
+                session.contains(action)
+                    && !bound.contains(&action)
+                    && bindings.producer(action).is_none()
+            })
+            .collect::<FxHashSet<_>>();
+        for (action, fallback) in &self.fallbacks {
+            if !unbound.contains(action) {
+                continue;
+            }
+            if let Err(error) = fallback(&mut bindings, session) {
+                errors.push(LoadError::Fallback {
+                    action: session.action_name(*action).to_owned(),
+                    error,
+                });
+            }
+        }
+        for action in bindings.missing_required(session) {
+            errors.push(LoadError::UnboundRequiredAction {
+                name: session.action_name(action).to_owned(),
+            });
         }
         (bindings, errors)
     }
@@ -416,10 +1060,12 @@ struct FilterBuilder {
     create_source_actions:
         fn(session: &mut Session, config: &FilterConfig) -> Result<(), FilterLoadError>,
 
-    load:
-        fn(session: &Session, config: &FilterConfig) -> Result<Box<dyn AnyFilter>, FilterLoadError>,
+    load: FilterLoader,
 }
 
+type FilterLoader =
+    fn(session: &Session, config: &FilterConfig) -> Result<Box<dyn AnyFilter>, FilterLoadError>;
+
 trait AnyFilter {
     fn ty(&self) -> &str;
     fn save(&self, session: &Session) -> FilterConfig;
@@ -467,9 +1113,19 @@ pub enum LoadError {
     UnknownAction {
         name: String,
     },
+    /// The action was referred to by a former name registered with
+    /// [`Session::create_alias`]
+    ///
+    /// Bindings are still loaded, and will be saved under the current `name`.
+    DeprecatedAction {
+        alias: String,
+        name: String,
+    },
     /// A specific input binding was not recognized
     UnknownInput {
         input: String,
+        /// The most similar name reported by [`Input::names`], if any is close
+        suggestion: Option<String>,
     },
     /// A specific input binding cannot produce data of the type expected by a
     /// specific action
@@ -479,9 +1135,179 @@ pub enum LoadError {
         actual: &'static str,
         expected: Vec<&'static str>,
     },
+    /// Inputs are bound directly to an action which is also written by a
+    /// filter, which will overwrite their state whenever it's applied
+    BoundFilterTarget {
+        action: String,
+        filter_ty: String,
+    },
+    /// The condition of a binding (e.g. `"mouse left if aiming"`) is not an
+    /// `Action<bool>`
+    ConditionTypeError {
+        condition: String,
+        actual: &'static str,
+    },
+    /// An action marked required with [`Session::set_required`] is neither
+    /// bound to any input nor produced by any filter
+    UnboundRequiredAction {
+        name: String,
+    },
+    /// A config named in [`Config::include`] could not be resolved
+    UnresolvedInclude {
+        name: String,
+        error: Arc<dyn core::error::Error + Send + Sync>,
+    },
+    /// A config includes itself, directly or indirectly
+    IncludeCycle {
+        name: String,
+    },
+    /// A fallback registered with [`BindingsFactory::add_fallback`] couldn't
+    /// be bound, e.g. because the [`Session`] passed to
+    /// [`BindingsFactory::load`] defines its action differently, and was
+    /// skipped
+    Fallback {
+        action: String,
+        error: BindError,
+    },
+    /// The config failed [`Config::verify`] with the key passed to
+    /// [`BindingsFactory::set_verify_key`], and was ignored
+    Tampered,
     Filter(FilterLoadError),
 }
 
+/// Load the bindings for `I` in `cfg`, leniently if set
+fn build_source<I: Input>(
+    session: &Session,
+    cfg: &SourceConfig,
+    lenient: bool,
+) -> (Box<dyn AnyInputBindings>, Vec<LoadError>) {
+    let mut bindings = FxHashMap::<I, Vec<Binding>>::default();
+    let mut errors = Vec::new();
+    for (name, inputs) in &cfg.bindings {
+        let Some(action) = resolve_action(session, name, &mut errors) else {
+            continue;
+        };
+        for input_str in inputs {
+            let (input_str, condition) = match input_str.rsplit_once(" if ") {
+                Some((input, condition)) => (input, Some(condition.trim())),
+                None => (&**input_str, None),
+            };
+            let condition = match condition {
+                None => None,
+                Some(condition) => {
+                    let Some(id) = resolve_action(session, condition, &mut errors) else {
+                        continue;
+                    };
+                    if let Err(error) = session.action::<bool>(id) {
+                        errors.push(LoadError::ConditionTypeError {
+                            condition: condition.to_owned(),
+                            actual: error.actual,
+                        });
+                        continue;
+                    }
+                    Some(id)
+                }
+            };
+            let mut inputs = I::from_str(input_str);
+            if inputs.is_empty() && lenient {
+                inputs = lenient::parse_input(input_str);
+            }
+            if inputs.is_empty() {
+                errors.push(LoadError::UnknownInput {
+                    input: input_str.to_owned(),
+                    suggestion: closest_name(input_str, I::names()),
+                });
+                continue;
+            }
+            let mut expected = Vec::new();
+            let mut success = false;
+            for input in &inputs {
+                if let Err(error) = session.check_type(action, input) {
+                    expected.push(error.expected);
+                } else {
+                    bindings.entry(input.clone()).or_default().push(Binding {
+                        action,
+                        condition,
+                        adapter: Adapter::None,
+                    });
+                    success = true;
+                    break;
+                }
+            }
+            if !success {
+                // Convert data of another type instead
+                if let Some((adapter, input)) = inputs
+                    .into_iter()
+                    .find_map(|i| Some((adapter(session, action, &i)?, i)))
+                {
+                    bindings.entry(input).or_default().push(Binding {
+                        action,
+                        condition,
+                        adapter,
+                    });
+                    success = true;
+                }
+            }
+            if !success {
+                errors.push(LoadError::InputTypeError {
+                    action_name: name.clone(),
+                    input: input_str.to_owned(),
+                    actual: session.definition(action).ty_name,
+                    expected,
+                })
+            }
+        }
+    }
+    (Box::new(InputBindings { bindings }), errors)
+}
+
+/// Look up the action referred to by `name` in a config
+fn resolve_action(session: &Session, name: &str, errors: &mut Vec<LoadError>) -> Option<ActionId> {
+    if let Some(action) = session.action_id(name) {
+        return Some(action);
+    }
+    if let Some(action) = session.alias_target(name) {
+        errors.push(LoadError::DeprecatedAction {
+            alias: name.to_owned(),
+            name: session.action_name(action).to_owned(),
+        });
+        return Some(action);
+    }
+    errors.push(LoadError::UnknownAction {
+        name: name.to_owned(),
+    });
+    None
+}
+
+/// Find the candidate most similar to `s`, if any is plausibly a misspelling
+fn closest_name(s: &str, candidates: Vec<String>) -> Option<String> {
+    let s = s.to_ascii_lowercase();
+    let threshold = (s.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|c| (edit_distance(&s, &c.to_ascii_lowercase()), c))
+        .filter(|&(d, _)| d <= threshold)
+        .min_by_key(|&(d, _)| d)
+        .map(|(_, c)| c)
+}
+
+/// Levenshtein distance between `a` and `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, x) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, &y) in b.iter().enumerate() {
+            cur[j + 1] = (prev[j] + usize::from(x != y))
+                .min(prev[j + 1] + 1)
+                .min(cur[j] + 1);
+        }
+        core::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
 impl From<FilterLoadError> for LoadError {
     fn from(value: FilterLoadError) -> Self {
         LoadError::Filter(value)
@@ -500,9 +1326,15 @@ pub enum FilterLoadError {
     UnknownFilter {
         ty: String,
     },
+    /// The filter takes exactly `expected` targets
     WrongOutputCount {
         expected: usize,
     },
+    /// The filter takes between `min` and `max` targets, inclusive
+    OutputCountOutOfRange {
+        min: usize,
+        max: usize,
+    },
     UnknownTarget {
         output: String,
     },
@@ -514,6 +1346,11 @@ pub enum FilterLoadError {
         action: String,
         error: TypeError,
     },
+    /// An entry in [`FilterConfig::params`] is missing or malformed
+    InvalidParam {
+        filter_ty: String,
+        param: String,
+    },
     Cycle,
 }
 
@@ -533,6 +1370,22 @@ pub struct Bindings {
     filters: Slab<Box<dyn AnyFilter>>,
     /// Maps actions to the index in `filters` of the filter that consumes them
     filter_source_actions: FxHashMap<ActionId, FilterId>,
+    /// `filters` in topological order
+    filter_order: Vec<FilterId>,
+    /// Target actions of each filter in `filter_order`
+    filter_targets: Vec<Vec<ActionId>>,
+    /// Position in `filter_order` of each filter, indexed like `filters`
+    filter_rank: Vec<usize>,
+    /// Whether filters run in [`update`](Self::update) rather than immediately
+    deferred: bool,
+    /// [`Input::NAME`]s of sources whose inputs are currently ignored
+    disabled_sources: FxHashSet<String>,
+    /// The [`Session`] these bindings refer to, once known
+    session: Option<SessionId>,
+    /// See [`Config::devices`]
+    devices: Vec<DeviceAlias>,
+    /// See [`Config::accessibility`]
+    accessibility: Accessibility,
 }
 
 impl Bindings {
@@ -544,9 +1397,13 @@ impl Bindings {
     /// Convert the current set of bindings into serializable form
     ///
     /// `session` must be the same one used to create all [`Action`]s described
-    /// in these bindings.
+    /// in these bindings. Bindings and filters that refer to actions since
+    /// removed from `session` are omitted.
     pub fn save(&self, session: &Session) -> Config {
+        self.check_session(session);
         Config {
+            include: Vec::new(),
+            devices: self.devices.clone(),
             sources: self
                 .actions
                 .values()
@@ -555,19 +1412,121 @@ impl Bindings {
             filters: self
                 .filters
                 .iter()
+                .filter(|(_, filter)| {
+                    // Filters wired to removed actions can't be described by name
+                    filter
+                        .source_actions()
+                        .into_iter()
+                        .chain(filter.target_actions())
+                        .all(|action| session.contains(action))
+                })
                 .map(|(_, filter)| filter.save(session))
                 .collect(),
+            accessibility: self.accessibility,
+            checksum: None,
         }
     }
 
-    /// Add a filter to the filter graph
-    pub fn add_filter<F: Filter>(&mut self, filter: F) -> Result<FilterId, FilterCycle> {
-        self.add_any_filter(Box::new(filter))
-    }
-
-    fn add_any_filter(&mut self, filter: Box<dyn AnyFilter>) -> Result<FilterId, FilterCycle> {
-        // This makes repeated calls to `add_filter` something like quadratic in
-        // the length of the longest path through the filter graph. Don't make
+    /// Like [`save`](Self::save), but only include bindings and filters that
+    /// differ from `defaults`
+    ///
+    /// Keeps user config files minimal, so that changes to shipped defaults
+    /// reach users who haven't customized the affected actions. An action
+    /// whose bindings from a source were all removed is saved with an empty
+    /// list of inputs. Removal of a default filter cannot be represented. Use
+    /// [`Config::apply_overrides`] to combine the result with the defaults
+    /// before loading.
+    pub fn save_overrides(&self, defaults: &Bindings, session: &Session) -> Config {
+        defaults.check_session(session);
+        let defaults = defaults.save(session);
+        let current = self.save(session);
+        let mut current_sources = current.sources;
+        // Sources with no bindings left still need their defaults overridden
+        for default in &defaults.sources {
+            if !current_sources.iter().any(|s| s.ty == default.ty) {
+                current_sources.push(SourceConfig {
+                    ty: default.ty.clone(),
+                    bindings: Vec::new(),
+                });
+            }
+        }
+        let mut sources = Vec::new();
+        for source in current_sources {
+            let default = defaults.sources.iter().find(|s| s.ty == source.ty);
+            let default_inputs = |action: &str| {
+                let mut inputs = default
+                    .and_then(|d| d.bindings.iter().find(|(a, _)| a == action))
+                    .map_or_else(Vec::new, |(_, inputs)| inputs.clone());
+                inputs.sort_unstable();
+                inputs
+            };
+            let mut bindings = Vec::new();
+            for (action, mut inputs) in source.bindings.iter().cloned() {
+                inputs.sort_unstable();
+                if inputs != default_inputs(&action) {
+                    bindings.push((action, inputs));
+                }
+            }
+            for (action, _) in default.into_iter().flat_map(|d| &d.bindings) {
+                if !source.bindings.iter().any(|(a, _)| a == action) {
+                    bindings.push((action.clone(), Vec::new()));
+                }
+            }
+            if !bindings.is_empty() {
+                bindings.sort_unstable_by(|x, y| x.0.cmp(&y.0));
+                sources.push(SourceConfig {
+                    ty: source.ty,
+                    bindings,
+                });
+            }
+        }
+        Config {
+            include: Vec::new(),
+            devices: current
+                .devices
+                .into_iter()
+                .filter(|d| !defaults.devices.contains(d))
+                .collect(),
+            sources,
+            filters: current
+                .filters
+                .into_iter()
+                .filter(|f| !defaults.filters.contains(f))
+                .collect(),
+            accessibility: if current.accessibility != defaults.accessibility {
+                current.accessibility
+            } else {
+                Accessibility::default()
+            },
+            checksum: None,
+        }
+    }
+
+    /// Add a filter to the filter graph
+    ///
+    /// Panics if the filter's source or target actions aren't in `session`,
+    /// e.g. if they were created with another session.
+    pub fn add_filter<F: Filter>(
+        &mut self,
+        filter: F,
+        session: &Session,
+    ) -> Result<FilterId, FilterCycle> {
+        self.check_session(session);
+        self.session = Some(session.id);
+        assert!(
+            filter
+                .source_actions()
+                .into_iter()
+                .chain(filter.target_actions())
+                .all(|action| session.contains(action)),
+            "filter refers to actions not in this session"
+        );
+        self.add_any_filter(Box::new(filter))
+    }
+
+    fn add_any_filter(&mut self, filter: Box<dyn AnyFilter>) -> Result<FilterId, FilterCycle> {
+        // This makes repeated calls to `add_filter` something like quadratic in
+        // the length of the longest path through the filter graph. Don't make
         // huge filter graphs.
         if self.would_introduce_cycle(&*filter) {
             return Err(FilterCycle);
@@ -577,9 +1536,50 @@ impl Bindings {
         self.filter_source_actions
             .extend(filter.source_actions().into_iter().map(|x| (x, id)));
         self.filters.insert(filter);
+        self.sort_filters();
         Ok(id)
     }
 
+    /// Recompute `filter_order`
+    fn sort_filters(&mut self) {
+        // Kahn's algorithm, where each filter depends on the filters that write
+        // its source actions
+        let mut dependencies = FxHashMap::<FilterId, usize>::default();
+        for (_, filter) in &self.filters {
+            for target in filter.target_actions() {
+                if let Some(&consumer) = self.filter_source_actions.get(&target) {
+                    *dependencies.entry(consumer).or_default() += 1;
+                }
+            }
+        }
+        let mut ready = self
+            .filters
+            .iter()
+            .map(|(i, _)| FilterId(i as u32))
+            .filter(|id| !dependencies.contains_key(id))
+            .collect::<Vec<_>>();
+        self.filter_order.clear();
+        self.filter_targets.clear();
+        self.filter_rank.clear();
+        self.filter_rank.resize(self.filters.capacity(), usize::MAX);
+        while let Some(id) = ready.pop() {
+            self.filter_rank[id.0 as usize] = self.filter_order.len();
+            self.filter_order.push(id);
+            let targets = self.filters[id.0 as usize].target_actions();
+            for target in &targets {
+                let Some(&consumer) = self.filter_source_actions.get(target) else {
+                    continue;
+                };
+                let count = dependencies.get_mut(&consumer).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(consumer);
+                }
+            }
+            self.filter_targets.push(targets);
+        }
+    }
+
     fn would_introduce_cycle(&self, filter: &dyn AnyFilter) -> bool {
         let mut checker = CycleChecker::new(&self.filter_source_actions, &self.filters);
         for target in filter.target_actions() {
@@ -598,11 +1598,42 @@ impl Bindings {
         self.filter_source_actions.get(&action).copied()
     }
 
+    /// Look up the filter that writes `action`, if any
+    pub fn producer(&self, action: ActionId) -> Option<FilterId> {
+        self.filters
+            .iter()
+            .find(|(_, filter)| filter.target_actions().contains(&action))
+            .map(|(i, _)| FilterId(i as u32))
+    }
+
     /// Look up the type name of a filter
     pub fn filter_ty(&self, filter: FilterId) -> &str {
         self.filters.get(filter.0 as usize).unwrap().ty()
     }
 
+    /// Enumerate all filters, in the order they run
+    ///
+    /// Each filter runs after any filters that produce its source actions.
+    pub fn filters(&self) -> impl Iterator<Item = FilterId> + '_ {
+        self.filter_order.iter().copied()
+    }
+
+    /// Look up the actions a filter consumes
+    pub fn filter_sources(&self, filter: FilterId) -> Vec<ActionId> {
+        self.filters
+            .get(filter.0 as usize)
+            .unwrap()
+            .source_actions()
+    }
+
+    /// Look up the actions a filter writes
+    pub fn filter_targets(&self, filter: FilterId) -> Vec<ActionId> {
+        self.filters
+            .get(filter.0 as usize)
+            .unwrap()
+            .target_actions()
+    }
+
     /// Remove `filter`
     ///
     /// This will leave any bindings for source actions for the removed filter
@@ -612,19 +1643,343 @@ impl Bindings {
         for action in filter.source_actions() {
             self.filter_source_actions.remove(&action);
         }
+        self.sort_filters();
+    }
+
+    /// Suppress or restore all inputs of type `I`
+    ///
+    /// While disabled, [`handle`](Self::handle) ignores inputs from the
+    /// source, but its bindings are retained. Useful for e.g. ignoring
+    /// gamepads while another application has focus. Disabling a source
+    /// releases actions in `seat` held by its `bool` inputs, since the
+    /// releases would otherwise be ignored.
+    pub fn set_source_enabled<I: Input>(&mut self, enabled: bool, seat: &mut Seat) {
+        self.set_source_enabled_by_name(I::NAME, enabled, seat);
+    }
+
+    /// Suppress or restore all inputs from the source with [`Input::NAME`]
+    /// `name`
+    ///
+    /// See [`set_source_enabled`](Self::set_source_enabled).
+    pub fn set_source_enabled_by_name(&mut self, name: &str, enabled: bool, seat: &mut Seat) {
+        if enabled {
+            self.disabled_sources.remove(name);
+            return;
+        }
+        if let Some(source) = self.actions.values().find(|x| x.name() == name) {
+            source.release(self, seat);
+        }
+        self.disabled_sources.insert(name.to_owned());
+    }
+
+    /// Whether inputs from the source with [`Input::NAME`] `name` are handled
+    pub fn is_source_enabled(&self, name: &str) -> bool {
+        !self.disabled_sources.contains(name)
+    }
+
+    /// Get the actions marked required with [`Session::set_required`] which
+    /// are neither bound to any input nor produced by any filter
+    ///
+    /// Checked automatically by [`BindingsFactory::load`]; useful to recheck
+    /// after rebinding at runtime.
+    pub fn missing_required(&self, session: &Session) -> Vec<ActionId> {
+        self.check_session(session);
+        let bound = self
+            .actions
+            .values()
+            .flat_map(|source| source.actions())
+            .collect::<FxHashSet<_>>();
+        let mut missing = session
+            .required
+            .iter()
+            .copied()
+            .filter(|&a| !bound.contains(&a) && self.producer(a).is_none())
+            .collect::<Vec<_>>();
+        missing.sort_unstable_by(|&x, &y| session.action_name(x).cmp(session.action_name(y)));
+        missing
+    }
+
+    /// Describe the overall state of these bindings
+    ///
+    /// Useful for logging, or for warning users about unbound actions.
+    pub fn summary(&self, session: &Session) -> Summary {
+        self.check_session(session);
+        let mut bound = FxHashSet::default();
+        let mut bindings_per_source = Vec::new();
+        let mut duplicate_inputs = Vec::new();
+        for source in self.actions.values() {
+            let entries = source.entries();
+            bindings_per_source.push((
+                source.name().to_owned(),
+                entries.iter().map(|(_, actions)| actions.len()).sum(),
+            ));
+            for (input, actions) in entries {
+                let actions = actions
+                    .into_iter()
+                    .filter(|&a| session.contains(a))
+                    .collect::<Vec<_>>();
+                bound.extend(actions.iter().copied());
+                if actions.len() > 1 {
+                    let mut actions = actions
+                        .into_iter()
+                        .map(|a| session.action_name(a).to_owned())
+                        .collect::<Vec<_>>();
+                    actions.sort_unstable();
+                    duplicate_inputs.push(DuplicateInput {
+                        source: source.name().to_owned(),
+                        input,
+                        actions,
+                    });
+                }
+            }
+        }
+        for (_, filter) in &self.filters {
+            bound.extend(filter.target_actions());
+        }
+        let mut unbound_actions = session
+            .actions()
+            .filter(|a| !bound.contains(a))
+            .map(|a| session.action_name(a).to_owned())
+            .collect::<Vec<_>>();
+        bindings_per_source.sort_unstable();
+        unbound_actions.sort_unstable();
+        duplicate_inputs.sort_unstable_by(|x, y| (&x.source, &x.input).cmp(&(&y.source, &y.input)));
+        Summary {
+            bindings_per_source,
+            unbound_actions,
+            filters: self.filters.len(),
+            duplicate_inputs,
+        }
+    }
+
+    /// Render a human-readable table of the inputs bound to each action, in
+    /// Markdown
+    ///
+    /// Actions are grouped into sections by the prefix of their name before
+    /// the first `.`, if any, e.g. `ui.confirm` is listed under `ui`. Useful
+    /// for displaying or exporting documentation of the current controls.
+    ///
+    /// Inputs are described according to `names`, which may be empty.
+    pub fn cheat_sheet(&self, session: &Session, names: &DisplayNames) -> String {
+        self.check_session(session);
+        use core::fmt::Write;
+
+        let mut inputs = FxHashMap::<ActionId, Vec<String>>::default();
+        for source in self.actions.values() {
+            for (input, actions) in source.entries() {
+                let input = names.get(source.name(), &input).unwrap_or(&input);
+                for action in actions {
+                    inputs.entry(action).or_default().push(input.to_owned());
+                }
+            }
+        }
+        let mut groups = alloc::collections::BTreeMap::<&str, Vec<(&str, String)>>::new();
+        for action in session.actions() {
+            let name = session.action_name(action);
+            let group = name.split_once('.').map_or("", |(group, _)| group);
+            let inputs = match inputs.get_mut(&action) {
+                Some(inputs) => {
+                    inputs.sort_unstable();
+                    inputs.join(", ")
+                }
+                None => "*unbound*".to_owned(),
+            };
+            groups.entry(group).or_default().push((name, inputs));
+        }
+
+        let mut out = String::new();
+        for (group, mut actions) in groups {
+            actions.sort_unstable();
+            if !group.is_empty() {
+                writeln!(out, "## {group}\n").unwrap();
+            }
+            writeln!(out, "| Action | Inputs |").unwrap();
+            writeln!(out, "| --- | --- |").unwrap();
+            for (name, inputs) in actions {
+                writeln!(out, "| {name} | {inputs} |").unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+        out
+    }
+
+    /// Control whether filters are evaluated eagerly or deferred
+    ///
+    /// By default, filters are reevaluated by [`handle`](Self::handle) every
+    /// time one of their source actions changes, so that every intermediate
+    /// state is observable. When deferred, [`handle`](Self::handle) only
+    /// records which actions changed, and each affected filter runs once, on
+    /// the latest state of its sources, in the next call to
+    /// [`update`](Self::update). This is much cheaper for high-frequency
+    /// inputs like mouse motion.
+    pub fn set_deferred(&mut self, deferred: bool) {
+        self.deferred = deferred;
+    }
+
+    /// Perform due pushes scheduled with [`Seat::push_at`], then evaluate
+    /// filters whose source actions changed since the last call
+    ///
+    /// Only needed when [`set_deferred`](Self::set_deferred) is enabled or
+    /// pushes are scheduled, e.g. by a [`Macro`](filter::Macro). Should be
+    /// called after handling a batch of events (e.g. once per frame) and
+    /// before reading any filter targets from `seat`. Filters run in
+    /// dependency order, each at most once.
+    pub fn update(&self, seat: &mut Seat) {
+        seat.run_scheduled(|action| self.filter_source_actions.contains_key(&action));
+        if seat.dirty.is_empty() {
+            return;
+        }
+        let mut dirty = core::mem::take(&mut seat.dirty);
+        self.apply_filters(&mut dirty, seat);
+        dirty.clear();
+        // Reuse the allocation
+        seat.dirty = dirty;
+    }
+
+    /// Apply every filter that depends on an action in `dirty`, in dependency
+    /// order, adding their targets to `dirty`
+    pub(crate) fn apply_filters(&self, dirty: &mut FxHashSet<ActionId>, seat: &mut Seat) {
+        // Positions in `filter_order` of filters yet to run
+        let mut pending = dirty
+            .iter()
+            .filter_map(|action| self.filter_source_actions.get(action))
+            .map(|id| self.filter_rank[id.0 as usize])
+            .collect::<alloc::collections::BTreeSet<_>>();
+        while let Some(rank) = pending.pop_first() {
+            let filter = &self.filters[self.filter_order[rank].0 as usize];
+            seat.filtering = true;
+            filter.apply(seat);
+            seat.filtering = false;
+            for &target in &self.filter_targets[rank] {
+                dirty.insert(target);
+                if let Some(consumer) = self.filter_source_actions.get(&target) {
+                    pending.insert(self.filter_rank[consumer.0 as usize]);
+                }
+            }
+        }
     }
 
     /// Introduce a new binding from `input` to `action`
     ///
     /// All [`Action`]s in a set of bindings must be created from the same
     /// [`Session`].
+    ///
+    /// Fails if `action` is written by a filter, since the filter would
+    /// overwrite the input's state whenever it's next applied. Vector inputs
+    /// may be bound to actions of any [`Vector2`] type.
     pub fn bind<I: Input>(
         &mut self,
         input: I,
         action: ActionId,
         session: &Session,
-    ) -> Result<(), TypeError> {
-        session.check_type(action, &input)?;
+    ) -> Result<(), BindError> {
+        let adapter = self.check_bind(&input, action, session)?;
+        self.insert_binding(
+            input,
+            Binding {
+                action,
+                condition: None,
+                adapter,
+            },
+        );
+        Ok(())
+    }
+
+    /// Introduce a new binding from `input` to `action` which only takes
+    /// effect while `condition` is `true`
+    ///
+    /// Enables context-sensitive controls, e.g. binding the same button to
+    /// different actions depending on whether the player is mounted. Updates
+    /// to `input` while `condition` is `false` or unset are ignored, so a
+    /// held-state action may not observe a release that occurs after
+    /// `condition` changes.
+    pub fn bind_if<I: Input>(
+        &mut self,
+        input: I,
+        action: ActionId,
+        condition: Action<bool>,
+        session: &Session,
+    ) -> Result<(), BindError> {
+        let adapter = self.check_bind(&input, action, session)?;
+        self.insert_binding(
+            input,
+            Binding {
+                action,
+                condition: Some(condition.id),
+                adapter,
+            },
+        );
+        Ok(())
+    }
+
+    /// Introduce a new binding from `bool` `input` to `action` which fires on
+    /// each rising edge
+    ///
+    /// Lets a button's held state trigger an event action without the input
+    /// source defining a separate press input. Configs get such bindings
+    /// automatically when an input has no variant matching an `Action<()>`
+    /// but does produce `bool`s.
+    pub fn bind_edge<I: Input>(
+        &mut self,
+        input: I,
+        action: Action<()>,
+        session: &Session,
+    ) -> Result<(), BindError> {
+        self.check_session(session);
+        self.session = Some(session.id);
+        if input.visit_type::<GetTypeId>() != TypeId::of::<bool>() {
+            return Err(TypeError {
+                expected: type_name::<bool>(),
+                actual: input.visit_type::<GetTypeName>(),
+            }
+            .into());
+        }
+        if let Some(filter) = self.producer(action.id) {
+            return Err(BindError::FilterTarget(filter));
+        }
+        self.insert_binding(
+            input,
+            Binding {
+                action: action.id,
+                condition: None,
+                adapter: Adapter::Edge,
+            },
+        );
+        Ok(())
+    }
+
+    fn check_bind<I: Input>(
+        &mut self,
+        input: &I,
+        action: ActionId,
+        session: &Session,
+    ) -> Result<Adapter, BindError> {
+        self.check_session(session);
+        self.session = Some(session.id);
+        let adapter = match session.check_type(action, input) {
+            Ok(()) => Adapter::None,
+            Err(e) => match adapter(session, action, input) {
+                Some(adapter @ Adapter::Vector(_)) => adapter,
+                _ => return Err(e.into()),
+            },
+        };
+        if let Some(filter) = self.producer(action) {
+            return Err(BindError::FilterTarget(filter));
+        }
+        Ok(adapter)
+    }
+
+    /// Panic if these bindings are known to belong to a different session
+    fn check_session(&self, session: &Session) {
+        if let Some(id) = self.session {
+            assert_eq!(
+                id, session.id,
+                "bindings used with a different session than they were created with"
+            );
+        }
+    }
+
+    fn insert_binding<I: Input>(&mut self, input: I, binding: Binding) {
         let bindings = self
             .actions
             .entry(TypeId::of::<I>())
@@ -632,11 +1987,12 @@ impl Bindings {
         let bindings = (&mut **bindings as &mut dyn Any)
             .downcast_mut::<InputBindings<I>>()
             .unwrap();
-        bindings.bindings.entry(input).or_default().push(action);
-        Ok(())
+        bindings.bindings.entry(input).or_default().push(binding);
     }
 
     /// Enumerate all actions triggered by any input of type `I`
+    ///
+    /// Includes actions bound conditionally with [`bind_if`](Self::bind_if).
     pub fn bindings_for_any<I: Input>(&self) -> Vec<(I, Vec<ActionId>)> {
         let Some(bindings) = self.actions.get(&TypeId::of::<I>()) else {
             return Vec::new();
@@ -647,11 +2003,13 @@ impl Bindings {
         bindings
             .bindings
             .iter()
-            .map(|(i, acts)| (i.clone(), acts.clone()))
+            .map(|(i, acts)| (i.clone(), acts.iter().map(|b| b.action).collect()))
             .collect()
     }
 
     /// Enumerate all actions triggered by `input`
+    ///
+    /// Includes actions bound conditionally with [`bind_if`](Self::bind_if).
     pub fn bindings_for<I: Input>(&self, input: &I) -> Vec<ActionId> {
         let Some(bindings) = self.actions.get(&TypeId::of::<I>()) else {
             return Vec::new();
@@ -659,10 +2017,14 @@ impl Bindings {
         let bindings = (&**bindings as &dyn Any)
             .downcast_ref::<InputBindings<I>>()
             .unwrap();
-        bindings.bindings.get(input).cloned().unwrap_or_default()
+        bindings
+            .bindings
+            .get(input)
+            .map(|acts| acts.iter().map(|b| b.action).collect())
+            .unwrap_or_default()
     }
 
-    /// Unbind `input` from `action`
+    /// Unbind `input` from `action`, regardless of condition
     ///
     /// Returns whether `input` was bound to `action`
     pub fn unbind<I: Input>(&mut self, input: &I, action: ActionId) -> bool {
@@ -676,11 +2038,9 @@ impl Bindings {
         let Some(actions) = bindings.bindings.get_mut(input) else {
             return false;
         };
-        let Some(i) = actions.iter().position(|&a| a == action) else {
-            return false;
-        };
-        actions.swap_remove(i);
-        true
+        let len = actions.len();
+        actions.retain(|b| b.action != action);
+        actions.len() != len
     }
 
     /// Unbind `input` from all actions
@@ -708,6 +2068,33 @@ impl Bindings {
         data: T,
         seat: &mut Seat,
     ) -> Result<(), TypeError> {
+        self.dispatch(input, data, seat)?;
+        Ok(())
+    }
+
+    /// Like [`handle`](Self::handle), but if `consumed` is set, only deliver
+    /// releases
+    ///
+    /// See [`HandleInput::handle_consumed`].
+    pub fn handle_consumed<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+        consumed: bool,
+    ) -> Result<(), TypeError> {
+        HandleInput::handle_consumed(self, input, data, seat, consumed)
+    }
+
+    /// Implementation of [`handle`](Self::handle)
+    ///
+    /// Returns `data` if it wasn't delivered to any action.
+    fn dispatch<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+    ) -> Result<Option<T>, TypeError> {
         if TypeId::of::<T>() != input.visit_type::<GetTypeId>() {
             // `input` can't produce data of type `T`
             return Err(TypeError {
@@ -715,9 +2102,45 @@ impl Bindings {
                 actual: type_name::<T>(),
             });
         }
+        if !self.disabled_sources.is_empty() && self.disabled_sources.contains(I::NAME) {
+            return Ok(Some(data));
+        }
+        if let Some(id) = self.session {
+            match seat.session {
+                None => seat.session = Some(id),
+                Some(seat_id) => assert_eq!(
+                    id, seat_id,
+                    "seat used with bindings from a different session"
+                ),
+            }
+        }
+        seat.last_source = Some(LastSource {
+            source: I::NAME,
+            family: input.device_family(),
+        });
+        if !seat.attributed.is_empty() {
+            seat.input = Some(EventSource {
+                source: I::NAME,
+                family: input.device_family(),
+                input: input.to_string(),
+            });
+        }
+        let undelivered = self.deliver(input, data, seat);
+        seat.input = None;
+        Ok(undelivered)
+    }
+
+    /// Push `data` from `input` to every action it's bound to, returning it
+    /// if there were none
+    fn deliver<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+    ) -> Option<T> {
         let Some(actions) = self.actions.get(&TypeId::of::<I>()) else {
             // No bindings exist for inputs of this type
-            return Ok(());
+            return Some(data);
         };
         let Some(bindings) = (&**actions as &dyn Any)
             .downcast_ref::<InputBindings<I>>()
@@ -726,27 +2149,104 @@ impl Bindings {
             .get(input)
         else {
             // No bindings exist for this specific input
-            return Ok(());
+            return Some(data);
+        };
+        // Evaluate every condition before pushing anything, so a binding can't
+        // enable or disable another binding of the same input
+        let active = bindings
+            .iter()
+            .filter(|b| b.is_active(seat))
+            .collect::<Vec<_>>();
+        let Some((last, rest)) = active.split_last() else {
+            return Some(data);
         };
-        for &action in bindings {
-            // Guaranteed to succeed because we check types at bind time
-            seat.push(action, data.clone()).unwrap();
+        for binding in rest {
+            self.push_binding(input, binding, data.clone(), seat);
+        }
+        // Move rather than clone into the final action, since payloads may be
+        // expensive to clone
+        self.push_binding(input, last, data, seat);
+        None
+    }
+
+    /// Push `data` from `input` to the action of `binding`
+    fn push_binding<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        binding: &Binding,
+        data: T,
+        seat: &mut Seat,
+    ) {
+        match binding.adapter {
+            Adapter::None => self.push(binding.action, data, seat),
+            Adapter::Edge => {
+                // Edge adapters are only made for `bool` inputs
+                let held = *(&data as &dyn Any).downcast_ref::<bool>().unwrap();
+                let edges = seat
+                    .edges
+                    .entry(TypeId::of::<I>())
+                    .or_insert_with(|| Box::new(FxHashSet::<(ActionId, I)>::default()))
+                    .downcast_mut::<FxHashSet<(ActionId, I)>>()
+                    .unwrap();
+                let key = (binding.action, input.clone());
+                if !held {
+                    edges.remove(&key);
+                } else if edges.insert(key) {
+                    self.push(binding.action, (), seat);
+                }
+            }
+            Adapter::Vector(ty) => {
+                // Vector adapters are only made for `mint::Vector2<f64>` inputs
+                let &xy = (&data as &dyn Any)
+                    .downcast_ref::<mint::Vector2<f64>>()
+                    .unwrap();
+                let result = ty.push(seat, binding.action, xy.into());
+                self.pushed(binding.action, result, seat);
+            }
+        }
+    }
+
+    fn push<T: Clone + 'static>(&self, action: ActionId, data: T, seat: &mut Seat) {
+        let result = seat.push_changed(action, data);
+        self.pushed(action, result, seat);
+    }
+
+    /// Propagate the result of pushing to `action`
+    fn pushed(&self, action: ActionId, result: Result<bool, PushError>, seat: &mut Seat) {
+        // Types are checked at bind time, so this can only fail if the action
+        // has since been removed from the session
+        if !matches!(result, Ok(true)) {
+            return;
+        }
+        if !self.deferred {
             self.propagate(action, seat);
+        } else if self.filter_source_actions.contains_key(&action) {
+            seat.dirty.insert(action);
         }
-        Ok(())
     }
 
     /// Update actions populated from filters dependent on `action` in `seat`
+    ///
+    /// Each filter runs at most once, after all of its dependencies, even if
+    /// it's reachable along multiple paths.
     fn propagate(&self, action: ActionId, seat: &mut Seat) {
-        let mut dirty = vec![action];
-        while let Some(action) = dirty.pop() {
-            let Some(&filter) = self.filter_source_actions.get(&action) else {
-                continue;
-            };
-            let filter = &self.filters[filter.0 as usize];
-            filter.apply(seat);
-            dirty.extend(filter.target_actions())
+        if !self.filter_source_actions.contains_key(&action) {
+            return;
         }
+        let mut dirty = FxHashSet::default();
+        dirty.insert(action);
+        self.apply_filters(&mut dirty, seat);
+    }
+}
+
+impl HandleInput for Bindings {
+    fn handle<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+    ) -> Result<(), TypeError> {
+        Bindings::handle(self, input, data, seat)
     }
 }
 
@@ -764,6 +2264,14 @@ impl Clone for Bindings {
                 .map(|(i, f)| (i, AnyFilter::clone(&**f)))
                 .collect(),
             filter_source_actions: self.filter_source_actions.clone(),
+            filter_order: self.filter_order.clone(),
+            filter_targets: self.filter_targets.clone(),
+            filter_rank: self.filter_rank.clone(),
+            deferred: self.deferred,
+            disabled_sources: self.disabled_sources.clone(),
+            session: self.session,
+            devices: self.devices.clone(),
+            accessibility: self.accessibility,
         }
     }
 }
@@ -803,13 +2311,112 @@ impl<'a> CycleChecker<'a> {
     }
 }
 
+/// Overview of a set of [`Bindings`], from [`Bindings::summary`]
+#[derive(Debug, Clone)]
+pub struct Summary {
+    /// Number of bindings from each input source, by [`Input::NAME`]
+    pub bindings_per_source: Vec<(String, usize)>,
+    /// Names of actions which are neither bound to any input nor written by
+    /// any filter
+    pub unbound_actions: Vec<String>,
+    /// Number of filters
+    pub filters: usize,
+    /// Inputs bound to more than one action
+    pub duplicate_inputs: Vec<DuplicateInput>,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (source, count) in &self.bindings_per_source {
+            writeln!(f, "{source}: {count} bindings")?;
+        }
+        writeln!(f, "{} filters", self.filters)?;
+        if !self.unbound_actions.is_empty() {
+            writeln!(
+                f,
+                "{} unbound actions: {}",
+                self.unbound_actions.len(),
+                self.unbound_actions.join(", ")
+            )?;
+        }
+        for dup in &self.duplicate_inputs {
+            writeln!(
+                f,
+                "{} {:?} bound to multiple actions: {}",
+                dup.source,
+                dup.input,
+                dup.actions.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// An input bound to more than one action
+#[derive(Debug, Clone)]
+pub struct DuplicateInput {
+    /// [`Input::NAME`] of the input's source
+    pub source: String,
+    pub input: String,
+    /// Names of the actions `input` is bound to
+    pub actions: Vec<String>,
+}
+
+/// Reasons why [`Bindings::bind`] might fail
+#[derive(Debug, Clone)]
+pub enum BindError {
+    /// The input doesn't produce data of the action's type
+    Type(TypeError),
+    /// The action is written by a filter
+    FilterTarget(FilterId),
+}
+
+impl From<TypeError> for BindError {
+    fn from(value: TypeError) -> Self {
+        BindError::Type(value)
+    }
+}
+
+impl fmt::Display for BindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BindError::Type(ref e) => e.fmt(f),
+            BindError::FilterTarget(_) => f.write_str("action is written by a filter"),
+        }
+    }
+}
+
+impl core::error::Error for BindError {}
+
 /// Error indicating that a filter would create a feedback loop
 #[derive(Debug, Copy, Clone)]
 pub struct FilterCycle;
 
-trait AnyInputBindings: Any {
+trait AnyInputBindings: Any + MaybeSendSync {
     fn save(&self, session: &Session) -> SourceConfig;
     fn clone(&self) -> Box<dyn AnyInputBindings>;
+    /// Actions bound to any input
+    fn actions(&self) -> Vec<ActionId>;
+    /// [`Input::NAME`]
+    fn name(&self) -> &'static str;
+    /// Human-readable form of each bound input, with the actions it's bound to
+    fn entries(&self) -> Vec<(String, Vec<ActionId>)>;
+    /// Like `entries`, with details for display
+    fn describe(&self) -> Vec<BoundInput>;
+    /// Pass the input named `input` to `bindings` with `value`, if valid
+    fn replay(&self, bindings: &Bindings, input: &str, value: &InputValue, seat: &mut Seat)
+    -> bool;
+    /// Pass `false` to `bindings` for every `bool` input whose actions are held
+    fn release(&self, bindings: &Bindings, seat: &mut Seat);
+}
+
+/// An input and its bindings, from [`AnyInputBindings::describe`]
+struct BoundInput {
+    /// From [`Input::to_string`]
+    input: String,
+    /// From [`Input::glyph`]
+    glyph: Option<String>,
+    bindings: Vec<Binding>,
 }
 
 impl<I: Input> AnyInputBindings for InputBindings<I> {
@@ -817,12 +2424,23 @@ impl<I: Input> AnyInputBindings for InputBindings<I> {
         let mut bindings = FxHashMap::<String, Vec<String>>::default();
         // Transpose
         for (input, actions) in &self.bindings {
-            for &action in actions {
-                let name = session.action_name(action);
+            for binding in actions {
+                if !session.contains(binding.action)
+                    || binding.condition.is_some_and(|c| !session.contains(c))
+                {
+                    // Dangling binding to a removed action
+                    continue;
+                }
+                let name = session.action_name(binding.action);
                 if !bindings.contains_key(name) {
                     bindings.insert(name.to_owned(), Vec::new());
                 }
-                bindings.get_mut(name).unwrap().push(input.to_string());
+                let mut input = input.to_string();
+                if let Some(condition) = binding.condition {
+                    input.push_str(" if ");
+                    input.push_str(session.action_name(condition));
+                }
+                bindings.get_mut(name).unwrap().push(input);
             }
         }
         let mut bindings = bindings.into_iter().collect::<Vec<_>>();
@@ -837,12 +2455,122 @@ impl<I: Input> AnyInputBindings for InputBindings<I> {
     fn clone(&self) -> Box<dyn AnyInputBindings> {
         Box::new(Clone::clone(self))
     }
+    fn actions(&self) -> Vec<ActionId> {
+        self.bindings
+            .values()
+            .flat_map(|bindings| bindings.iter().map(|b| b.action))
+            .collect()
+    }
+    fn name(&self) -> &'static str {
+        I::NAME
+    }
+    fn entries(&self) -> Vec<(String, Vec<ActionId>)> {
+        self.bindings
+            .iter()
+            .filter(|(_, bindings)| !bindings.is_empty())
+            .map(|(input, bindings)| {
+                (
+                    input.to_string(),
+                    bindings.iter().map(|b| b.action).collect(),
+                )
+            })
+            .collect()
+    }
+    fn describe(&self) -> Vec<BoundInput> {
+        self.bindings
+            .iter()
+            .filter(|(_, bindings)| !bindings.is_empty())
+            .map(|(input, bindings)| BoundInput {
+                input: input.to_string(),
+                glyph: input.glyph(),
+                bindings: bindings.clone(),
+            })
+            .collect()
+    }
+
+    fn replay(
+        &self,
+        bindings: &Bindings,
+        input: &str,
+        value: &InputValue,
+        seat: &mut Seat,
+    ) -> bool {
+        replay::replay_input::<I>(bindings, input, value, seat)
+    }
+
+    fn release(&self, bindings: &Bindings, seat: &mut Seat) {
+        for (input, list) in &self.bindings {
+            if !has_type::<bool, I>(input) {
+                continue;
+            }
+            for binding in list {
+                let held = match binding.adapter {
+                    // Releasing only forgets the edge
+                    Adapter::Edge => true,
+                    _ => {
+                        seat.get(Action::<bool> {
+                            id: binding.action,
+                            _marker: PhantomData,
+                        }) == Some(true)
+                    }
+                };
+                if held {
+                    bindings.push_binding(input, binding, false, seat);
+                }
+            }
+        }
+    }
+}
+
+struct InputBindings<I: Input> {
+    bindings: FxHashMap<I, Vec<Binding>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Binding {
+    action: ActionId,
+    /// `Action<bool>` which must be `true` for this binding to take effect
+    condition: Option<ActionId>,
+    adapter: Adapter,
+}
+
+/// Conversion of data passing through a [`Binding`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Adapter {
+    /// Data is pushed unchanged
+    None,
+    /// `bool` data is converted into `()` on rising edges
+    Edge,
+    /// `mint::Vector2<f64>` data is converted into another vector type
+    Vector(VectorType),
+}
+
+/// A conversion allowing `input` to drive `action` despite their types
+/// differing, if any
+fn adapter<I: Input>(session: &Session, action: ActionId, input: &I) -> Option<Adapter> {
+    let action_ty = session.definition(action).ty;
+    let input_ty = input.visit_type::<GetTypeId>();
+    if action_ty == TypeId::of::<()>() && input_ty == TypeId::of::<bool>() {
+        return Some(Adapter::Edge);
+    }
+    if input_ty == TypeId::of::<mint::Vector2<f64>>() {
+        return VectorType::find(action_ty).map(Adapter::Vector);
+    }
+    None
+}
+
+impl Binding {
+    fn is_active(&self, seat: &Seat) -> bool {
+        self.condition.is_none_or(|id| {
+            seat.get(Action::<bool> {
+                id,
+                _marker: PhantomData,
+            })
+            .unwrap_or(false)
+        })
+    }
 }
 
-struct InputBindings<I: Input> {
-    bindings: FxHashMap<I, Vec<ActionId>>,
-}
-
 impl<I: Input> Clone for InputBindings<I> {
     fn clone(&self) -> Self {
         Self {
@@ -863,6 +2591,25 @@ impl<I: Input> Default for InputBindings<I> {
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Config {
+    /// Names of other configs to load beneath this one
+    ///
+    /// Each included config is applied in order, and this config's own
+    /// entries last, as if by [`Config::apply_overrides`]. Resolved by
+    /// [`BindingsFactory::load`]; see
+    /// [`BindingsFactory::set_include_resolver`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    pub include: Vec<String>,
+    /// Names for specific devices, which inputs may refer to
+    ///
+    /// See [`DeviceAlias`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    pub devices: Vec<DeviceAlias>,
     #[cfg_attr(
         feature = "serde",
         serde(skip_serializing_if = "Vec::is_empty", default)
@@ -873,6 +2620,55 @@ pub struct Config {
         serde(skip_serializing_if = "Vec::is_empty", default)
     )]
     pub filters: Vec<FilterConfig>,
+    /// Presets applied when loaded
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Accessibility::is_off", default)
+    )]
+    pub accessibility: Accessibility,
+    /// Authenticates the rest of the config, if set by [`Config::sign`]
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub checksum: Option<String>,
+}
+
+impl Config {
+    /// Replace entries in `self` with those from `overrides`
+    ///
+    /// Each action's inputs from a given source are replaced wholesale by
+    /// those in `overrides`, if present. Each filter in `overrides` replaces
+    /// any filters with overlapping targets, and each device alias any with
+    /// the same label. Accessibility presets enabled in either are enabled in
+    /// the result. Typically used to combine
+    /// default bindings with the output of [`Bindings::save_overrides`].
+    pub fn apply_overrides(&mut self, overrides: &Config) {
+        self.accessibility = self.accessibility.union(&overrides.accessibility);
+        for device in &overrides.devices {
+            match self.devices.iter_mut().find(|d| d.alias == device.alias) {
+                Some(existing) => existing.clone_from(device),
+                None => self.devices.push(device.clone()),
+            }
+        }
+        for source in &overrides.sources {
+            let Some(target) = self.sources.iter_mut().find(|s| s.ty == source.ty) else {
+                self.sources.push(source.clone());
+                continue;
+            };
+            for (action, inputs) in &source.bindings {
+                match target.bindings.iter_mut().find(|(a, _)| a == action) {
+                    Some((_, existing)) => existing.clone_from(inputs),
+                    None => target.bindings.push((action.clone(), inputs.clone())),
+                }
+            }
+        }
+        for filter in &overrides.filters {
+            self.filters
+                .retain(|f| !f.targets.iter().any(|t| filter.targets.contains(t)));
+            self.filters.push(filter.clone());
+        }
+    }
 }
 
 /// Subset of serialized [`Bindings`] associated with a specific input source
@@ -884,17 +2680,74 @@ pub struct SourceConfig {
     #[cfg_attr(feature = "serde", serde(rename = "type"))]
     pub ty: String,
     /// Maps action names to inputs from this input source
+    ///
+    /// An input may be suffixed with `if <action>`, e.g. `"mouse left if
+    /// aiming"`, to bind it only while the named `Action<bool>` is `true`.
     #[cfg_attr(feature = "serde", serde(with = "tuple_vec_map"))]
     pub bindings: Vec<(String, Vec<String>)>,
 }
 
 /// Serialized form of a single filter's configuration
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FilterConfig {
     #[cfg_attr(feature = "serde", serde(rename = "type"))]
     pub ty: String,
     pub targets: Vec<String>,
+    /// Filter-specific parameters, interpreted according to `ty`
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    pub params: Vec<String>,
+}
+
+/// A table of human-readable descriptions of inputs, e.g. for localization
+///
+/// Maps [`Input::NAME`]s to tables mapping the canonical strings produced by
+/// [`Input::to_string`] to display strings. Inputs with no entry are
+/// displayed in canonical form. Can be loaded from a config file, e.g. in
+/// TOML:
+///
+/// ```toml
+/// [winit]
+/// space = "Leertaste"
+/// "mouse left" = "Linke Maustaste"
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct DisplayNames {
+    sources: FxHashMap<String, FxHashMap<String, String>>,
+}
+
+impl DisplayNames {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Describe `input` as `name`
+    pub fn insert<I: Input>(&mut self, input: &I, name: String) {
+        self.sources
+            .entry(I::NAME.to_owned())
+            .or_default()
+            .insert(input.to_string(), name);
+    }
+
+    /// Human-readable description of `input`
+    pub fn display<I: Input>(&self, input: &I) -> String {
+        let canonical = input.to_string();
+        match self.get(I::NAME, &canonical) {
+            Some(name) => name.to_owned(),
+            None => canonical,
+        }
+    }
+
+    /// Look up the display string for the input from the source named
+    /// `source` whose canonical form is `input`, if any
+    pub fn get(&self, source: &str, input: &str) -> Option<&str> {
+        Some(self.sources.get(source)?.get(input)?)
+    }
 }
 
 /// Represents the current state and recent history of any active [`Action`]s
@@ -906,7 +2759,218 @@ pub struct FilterConfig {
 /// call.
 #[derive(Default)]
 pub struct Seat {
-    state: Vec<Option<Box<RwLock<dyn AnyState>>>>,
+    state: Vec<Option<Slot>>,
+    /// Filter source actions changed since the last [`Bindings::update`]
+    dirty: FxHashSet<ActionId>,
+    /// Actions for which [`Seat::poll`] is never called
+    unpolled: FxHashSet<ActionId>,
+    edges: EdgeSets,
+    /// Equality of values for actions which drop duplicate pushes
+    distinct: FxHashMap<ActionId, ValueEq>,
+    last_source: Option<LastSource>,
+    /// The [`Session`] whose actions this seat holds state for, once known
+    session: Option<SessionId>,
+    /// [`SystemClock`] if unset, or without std, a clock stopped at zero
+    clock: Option<Arc<dyn Clock>>,
+    /// Pushes deferred by [`Seat::push_at`], in order of time
+    scheduled: Vec<Scheduled>,
+    /// See [`Seat::filter_state`]
+    filter_state: FxHashMap<(TypeId, ActionId), Box<dyn Any>>,
+    /// Actions whose changes record their [`EventSource`]
+    attributed: FxHashSet<ActionId>,
+    /// The input being handled, if any action is attributed
+    input: Option<EventSource>,
+    /// Number of frames ended so far
+    frame: u64,
+    /// Counts pushes, so that actions changed since some point can be
+    /// identified
+    pushes: u64,
+    /// Periods for which changes to actions survive [`Seat::flush`]
+    buffered: FxHashMap<ActionId, Duration>,
+    /// Samples, if latency is being measured
+    latency: Option<LatencyLog>,
+    /// Whether a [`Filter`] is being applied, so consumption isn't measured
+    filtering: bool,
+    /// Actions which may be pushed to, if restricted
+    allowed: Option<FxHashSet<ActionId>>,
+    /// Actions which may not be pushed to
+    denied: FxHashSet<ActionId>,
+}
+
+/// Edge bindings whose input was last `true`, as an `FxHashSet<(ActionId,
+/// I)>` for each [`Input`] type `I`
+pub(crate) type EdgeSets = FxHashMap<TypeId, Box<dyn Any>>;
+
+/// Compares two values of an action's type
+type ValueEq = fn(&dyn Any, &dyn Any) -> bool;
+
+/// A push deferred by [`Seat::push_at`]
+struct Scheduled {
+    at: Duration,
+    action: ActionId,
+    push: ScheduledPush,
+}
+
+type ScheduledPush = Box<dyn FnOnce(&mut Seat) -> Result<bool, PushError>>;
+
+struct Slot {
+    /// Generation of the [`ActionId`] this state belongs to
+    generation: u32,
+    /// [`Seat::now`] as of the most recent push
+    changed: Duration,
+    /// [`Seat::pushes`] as of the most recent push
+    pushed: u64,
+    /// [`Seat::now`] as of the most recent activation, if any
+    activated: Option<Duration>,
+    state: SlotState,
+    /// Sources of the newest queued changes, if the action is attributed
+    ///
+    /// May be shorter than the queue, if attribution began after some changes
+    /// were queued.
+    sources: Option<Mutex<VecDeque<Option<EventSource>>>>,
+    /// When each queued change was made
+    history: Mutex<History>,
+    /// Whether the latest value hasn't been observed by [`Seat::get`], for
+    /// latency measurement
+    unread: AtomicBool,
+}
+
+/// When each queued change was made, oldest first
+#[derive(Default)]
+struct History {
+    tags: VecDeque<Tag>,
+    /// Sum of `count` over `tags`
+    recorded: usize,
+}
+
+/// When a run of consecutive queued changes were made
+struct Tag {
+    /// [`Seat::frame`]
+    frame: u64,
+    /// [`Seat::now`]
+    at: Duration,
+    /// Number of changes
+    count: usize,
+}
+
+impl Slot {
+    /// Record that changes beyond those already recorded were queued at `at`,
+    /// during `frame`
+    fn record(&self, frame: u64, at: Duration, source: &Option<EventSource>) {
+        let queued = self.state.get().read().queued();
+        let mut history = self.history.lock();
+        if history.recorded < queued {
+            let count = queued - history.recorded;
+            history.recorded = queued;
+            match history.tags.back_mut() {
+                Some(tag) if tag.frame == frame && tag.at == at => tag.count += count,
+                _ => history.tags.push_back(Tag { frame, at, count }),
+            }
+        }
+        if let Some(ref sources) = self.sources {
+            let mut sources = sources.lock();
+            if sources.len() < queued {
+                sources.push_back(source.clone());
+            }
+        }
+    }
+
+    /// Number of queued changes made less than `window` before `now`
+    fn queued_within(&self, now: Duration, window: Duration) -> usize {
+        let history = self.history.lock();
+        history
+            .tags
+            .iter()
+            .rev()
+            .take_while(|tag| tag.at + window > now)
+            .map(|tag| tag.count)
+            .sum()
+    }
+
+    /// The frame and time of the oldest queued change, if any
+    fn next_change(&self) -> Option<(u64, Duration)> {
+        let history = self.history.lock();
+        history.tags.front().map(|tag| (tag.frame, tag.at))
+    }
+
+    /// Discard records of changes no longer queued, returning the source of
+    /// the oldest discarded change, if any
+    fn trim(&self) -> Option<EventSource> {
+        let queued = self.state.get().read().queued();
+        let mut history = self.history.lock();
+        let mut excess = history.recorded.saturating_sub(queued);
+        history.recorded -= excess;
+        while excess > 0 {
+            let tag = history.tags.front_mut().unwrap();
+            let discarded = excess.min(tag.count);
+            tag.count -= discarded;
+            excess -= discarded;
+            if tag.count == 0 {
+                history.tags.pop_front();
+            }
+        }
+        drop(history);
+        let mut sources = self.sources.as_ref()?.lock();
+        let mut oldest = None;
+        while sources.len() > queued {
+            let source = sources.pop_front().unwrap();
+            if oldest.is_none() {
+                oldest = Some(source);
+            }
+        }
+        oldest.flatten()
+    }
+}
+
+/// Storage for a single action's state
+///
+/// The most common action types are stored inline, to avoid allocating.
+enum SlotState {
+    Unit(RwLock<ActionState<(), UnitQueue>>),
+    Bool(RwLock<ActionState<bool, BitQueue>>),
+    Other(Box<RwLock<dyn AnyState>>),
+}
+
+impl SlotState {
+    fn new<T: 'static + Clone>(value: T, polled: bool) -> Self {
+        if TypeId::of::<T>() == TypeId::of::<()>() {
+            SlotState::Unit(RwLock::new(ActionState::new((), polled)))
+        } else if TypeId::of::<T>() == TypeId::of::<bool>() {
+            let value = cast::<T, bool>(value).unwrap();
+            SlotState::Bool(RwLock::new(ActionState::new(value, polled)))
+        } else {
+            SlotState::Other(Box::new(RwLock::new(ActionState::<T, VecDeque<T>>::new(
+                value, polled,
+            ))))
+        }
+    }
+
+    fn get(&self) -> &RwLock<dyn AnyState> {
+        match *self {
+            SlotState::Unit(ref x) => x,
+            SlotState::Bool(ref x) => x,
+            SlotState::Other(ref x) => x,
+        }
+    }
+}
+
+/// Whether pushing `value` to an action whose latest state is `latest`
+/// activates it
+///
+/// See [`Seat::last_activation`].
+fn is_activation(latest: Option<&dyn Any>, value: &dyn Any) -> bool {
+    match value.downcast_ref::<bool>() {
+        Some(&value) => value && latest.and_then(|x| x.downcast_ref::<bool>()) != Some(&true),
+        None => true,
+    }
+}
+
+/// Convert `value` to `U` if it's the same type
+fn cast<T: 'static, U: 'static>(value: T) -> Option<U> {
+    let mut value = Some(value);
+    (&mut value as &mut dyn Any)
+        .downcast_mut::<Option<U>>()
+        .and_then(Option::take)
 }
 
 impl Seat {
@@ -915,47 +2979,361 @@ impl Seat {
         Self::default()
     }
 
+    /// Use `clock` as the source of time, rather than [`SystemClock`]
+    pub fn set_clock(&mut self, clock: impl Clock) {
+        self.clock = Some(Arc::new(clock));
+    }
+
+    /// Current time according to this seat's [`Clock`]
+    ///
+    /// Time-aware [`Filter`]s should use this rather than reading the system
+    /// clock, so that they can be driven by virtual time.
+    pub fn now(&self) -> Duration {
+        match self.clock {
+            Some(ref clock) => clock.now(),
+            #[cfg(feature = "std")]
+            None => SystemClock.now(),
+            #[cfg(not(feature = "std"))]
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Time of the most recent change to `action`'s state, according to
+    /// [`now`](Self::now), if any
+    pub fn last_change(&self, action: ActionId) -> Option<Duration> {
+        let slot = self.state.get(action.index as usize)?.as_ref()?;
+        (slot.generation == action.generation).then_some(slot.changed)
+    }
+
+    /// Time of the most recent activation of `action`, according to
+    /// [`now`](Self::now), if any
+    ///
+    /// Every push activates an action, except for `bool` actions, which are
+    /// only activated by becoming `true`. Useful for cooldown displays and
+    /// combo windows.
+    pub fn last_activation(&self, action: ActionId) -> Option<Duration> {
+        let slot = self.state.get(action.index as usize)?.as_ref()?;
+        if slot.generation != action.generation {
+            return None;
+        }
+        slot.activated
+    }
+
+    /// Time elapsed since [`last_activation`](Self::last_activation), if any
+    pub fn since_activation(&self, action: ActionId) -> Option<Duration> {
+        Some(self.now().saturating_sub(self.last_activation(action)?))
+    }
+
+    fn slot(&self, action: ActionId) -> Option<&Slot> {
+        let slot = self.state.get(action.index as usize)?.as_ref()?;
+        if slot.generation != action.generation {
+            return None;
+        }
+        Some(slot)
+    }
+
     /// Consume the next state change affecting `action`, if any
     pub fn poll<T: 'static>(&self, action: Action<T>) -> Option<T> {
-        let mut state = self
-            .state
-            .get(action.id.0 as usize)?
-            .as_ref()?
-            .write()
-            .unwrap();
-        let state = &mut *state as &mut dyn Any;
-        state
-            .downcast_mut::<ActionState<T>>()
-            .expect("type mismatch")
-            .queue
-            .pop_front()
+        self.poll_with_source(action).map(|(value, _)| value)
+    }
+
+    /// Like [`poll`](Self::poll), but also report the input which caused the
+    /// change
+    ///
+    /// The source is only known for actions declared with
+    /// [`set_attributed`](Self::set_attributed), and for changes made while
+    /// handling an input, including by filters that aren't
+    /// [deferred](Bindings::set_deferred). Useful for varying behavior by
+    /// device, e.g. aim assist for sticks but not mice, and for tracing events
+    /// back to their inputs.
+    pub fn poll_with_source<T: 'static>(
+        &self,
+        action: Action<T>,
+    ) -> Option<(T, Option<EventSource>)> {
+        let slot = self.slot(action.id)?;
+        let made = slot.next_change();
+        let mut out = None::<T>;
+        let mut state = slot.state.get().write();
+        state.poll(&mut out);
+        let value = out?;
+        if let Some((_, at)) = made {
+            self.consumed(at);
+            if state.queued() == 0 {
+                // The latest value has now been observed
+                slot.unread.store(false, Ordering::Relaxed);
+            }
+        }
+        drop(state);
+        Some((value, slot.trim()))
+    }
+
+    /// Index of the current frame, counting from 0
+    ///
+    /// Every queued change is tagged with the frame it was made in. Frames end
+    /// at each [`flush`](Self::flush) or [`end_frame`](Self::end_frame).
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Begin a new [`frame`](Self::frame), without discarding queued changes
+    ///
+    /// Lets fixed-timestep simulations that tick at a different rate than
+    /// inputs are handled attribute each change to the right tick: mark a
+    /// boundary after handling each batch of input, then consume changes with
+    /// [`poll_until`](Self::poll_until), and [`flush`](Self::flush) only once
+    /// every frame has been simulated.
+    pub fn end_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// The [`frame`](Self::frame) in which the next change [`poll`](Self::poll)
+    /// would return was made, if any
+    pub fn next_frame(&self, action: ActionId) -> Option<u64> {
+        Some(self.slot(action)?.next_change()?.0)
+    }
+
+    /// The [`now`](Self::now) at which the next change [`poll`](Self::poll)
+    /// would return was made, if any
+    pub fn next_time(&self, action: ActionId) -> Option<Duration> {
+        Some(self.slot(action)?.next_change()?.1)
+    }
+
+    /// Like [`poll`](Self::poll), but only consume changes made during or
+    /// before `frame`
+    pub fn poll_until<T: 'static>(&self, action: Action<T>, frame: u64) -> Option<T> {
+        if self.next_frame(action.id)? > frame {
+            return None;
+        }
+        self.poll(action)
+    }
+
+    /// Like [`poll`](Self::poll), but only consume changes made before `at`
+    ///
+    /// See also [`FixedTimestep`].
+    pub fn poll_before<T: 'static>(&self, action: Action<T>, at: Duration) -> Option<T> {
+        if self.next_time(action.id)? >= at {
+            return None;
+        }
+        self.poll(action)
+    }
+
+    /// Consume all queued state changes affecting `action`, returning the most
+    /// recent, if any
+    ///
+    /// Convenient for analog data like stick positions, where only the newest
+    /// value matters.
+    pub fn poll_latest<T: 'static>(&self, action: Action<T>) -> Option<T> {
+        let slot = self.slot(action.id)?;
+        let mut out = None::<T>;
+        slot.state.get().write().poll_latest(&mut out);
+        slot.trim();
+        if out.is_some() {
+            self.consumed(slot.changed);
+            slot.unread.store(false, Ordering::Relaxed);
+        }
+        out
     }
 
     /// Observe the current state of `action`, if any
     pub fn get<T: 'static + Clone>(&self, action: Action<T>) -> Option<T> {
-        let state = self
+        let slot = self.slot(action.id)?;
+        let mut out = None::<T>;
+        slot.state.get().read().get(&mut out);
+        if self.latency.is_some() && slot.unread.swap(false, Ordering::Relaxed) {
+            self.consumed(slot.changed);
+        }
+        out
+    }
+
+    /// The source of the most recent input passed to [`Bindings::handle`] for
+    /// this seat, if any
+    ///
+    /// Updated for every input, whether or not it's bound to any actions.
+    pub fn last_source(&self) -> Option<LastSource> {
+        self.last_source
+    }
+
+    /// Declare whether [`poll`](Self::poll) will be called for `action`
+    ///
+    /// Actions are polled by default. Unpolled actions don't record a history
+    /// of state changes, only their latest state, which is all that
+    /// [`get`](Self::get) and [`Filter`]s need. Useful for intermediate
+    /// actions that only feed filters, like the sources of a
+    /// [`DPad`](filter::DPad). [`poll`](Self::poll) always returns `None` for
+    /// unpolled actions.
+    pub fn set_polled(&mut self, action: ActionId, polled: bool) {
+        if polled {
+            self.unpolled.remove(&action);
+        } else {
+            self.unpolled.insert(action);
+        }
+        if let Some(slot) = self.slot(action) {
+            slot.state.get().write().set_polled(polled);
+            slot.trim();
+        }
+    }
+
+    /// Keep unconsumed changes to `action` available to [`poll`](Self::poll)
+    /// for `window` after they're made, rather than discarding them at the
+    /// next [`flush`](Self::flush)
+    ///
+    /// Implements input buffering, e.g. so that a jump pressed shortly before
+    /// landing still happens on landing. Changes are still consumed by
+    /// polling, so each is acted on at most once. `None`, the default,
+    /// disables buffering. Has no effect on unpolled actions.
+    pub fn set_buffered(&mut self, action: ActionId, window: Option<Duration>) {
+        match window {
+            Some(window) => self.buffered.insert(action, window),
+            None => self.buffered.remove(&action),
+        };
+    }
+
+    /// Declare whether changes to `action` record the input that caused them
+    ///
+    /// Off by default, since recording costs an allocation per change. See
+    /// [`poll_with_source`](Self::poll_with_source).
+    pub fn set_attributed(&mut self, action: ActionId, attributed: bool) {
+        if attributed {
+            self.attributed.insert(action);
+        } else {
+            self.attributed.remove(&action);
+        }
+        let Some(slot) = self
             .state
-            .get(action.id.0 as usize)?
-            .as_ref()?
-            .read()
-            .unwrap();
-        let state = &*state as &dyn Any;
-        Some(
-            state
-                .downcast_ref::<ActionState<T>>()
-                .expect("type mismatch")
-                .latest
-                .clone(),
-        )
+            .get_mut(action.index as usize)
+            .and_then(|x| x.as_mut())
+        else {
+            return;
+        };
+        if slot.generation != action.generation {
+            return;
+        }
+        match (attributed, slot.sources.is_some()) {
+            (true, false) => slot.sources = Some(Mutex::default()),
+            (false, true) => slot.sources = None,
+            _ => {}
+        }
+    }
+
+    /// Restrict pushes to `actions`
+    ///
+    /// Lets seats for e.g. spectators or dead players share the same
+    /// [`Bindings`] as everyone else, while only affecting the actions their
+    /// role permits. Pushes to other actions fail with [`PushError::Denied`].
+    /// Existing state is kept. See also [`set_denied`](Self::set_denied).
+    pub fn set_allowed(&mut self, actions: impl IntoIterator<Item = ActionId>) {
+        self.allowed = Some(actions.into_iter().collect());
+    }
+
+    /// Lift any restriction from [`set_allowed`](Self::set_allowed)
+    pub fn allow_all(&mut self) {
+        self.allowed = None;
+    }
+
+    /// Declare whether pushes to `action` fail with [`PushError::Denied`]
+    ///
+    /// Takes precedence over [`set_allowed`](Self::set_allowed).
+    pub fn set_denied(&mut self, action: ActionId, denied: bool) {
+        if denied {
+            self.denied.insert(action);
+        } else {
+            self.denied.remove(&action);
+        }
+    }
+
+    /// Whether pushes to `action` are permitted by
+    /// [`set_allowed`](Self::set_allowed) and [`set_denied`](Self::set_denied)
+    pub fn is_permitted(&self, action: ActionId) -> bool {
+        !self.denied.contains(&action) && self.allowed.as_ref().is_none_or(|x| x.contains(&action))
+    }
+
+    /// Declare whether pushes to `action` equal to its latest state are dropped
+    ///
+    /// Prevents floods of identical values, e.g. repeated `true`s from key
+    /// repeat, from filling queues and waking pollers and filters needlessly.
+    /// Off by default.
+    pub fn set_distinct<T: PartialEq + 'static>(&mut self, action: Action<T>, distinct: bool) {
+        if distinct {
+            self.distinct.insert(action.id, |a, b| {
+                a.downcast_ref::<T>()
+                    .is_some_and(|a| Some(a) == b.downcast_ref::<T>())
+            });
+        } else {
+            self.distinct.remove(&action.id);
+        }
+    }
+
+    /// Reset all action state, as if no inputs had ever been handled
+    ///
+    /// Discards queued changes, latest values, and pushes scheduled with
+    /// [`push_at`](Self::push_at), so that e.g. held buttons don't leak across
+    /// level transitions or out of a pause menu. Settings such as
+    /// [`set_polled`](Self::set_polled) and the clock are preserved.
+    pub fn clear(&mut self) {
+        self.state.clear();
+        self.dirty.clear();
+        self.edges.clear();
+        self.scheduled.clear();
+        self.filter_state.clear();
+        self.last_source = None;
+        self.input = None;
     }
 
     /// Discard any state changes not consumed by calls to [`poll`](Self::poll)
     ///
     /// This must be called regularly (e.g. after running all input processing
     /// for a frame) to ensure that memory use does not grow without bound.
+    /// Storage left over from bursts of changes is released gradually; see
+    /// also [`shrink_to_fit`](Self::shrink_to_fit). Also ends the current
+    /// [`frame`](Self::frame).
     pub fn flush(&mut self) {
-        for state in self.state.iter().filter_map(Option::as_ref) {
-            state.write().unwrap().flush();
+        self.flush_with(|_| ());
+    }
+
+    /// Release all storage not needed for the current state
+    ///
+    /// Useful after a known burst of input, e.g. a frame hitch that queued
+    /// thousands of mouse deltas, or when memory is tight.
+    pub fn shrink_to_fit(&mut self) {
+        for slot in self.state.iter().flatten() {
+            slot.state.get().write().shrink_to_fit();
+            slot.history.lock().tags.shrink_to_fit();
+            if let Some(ref sources) = slot.sources {
+                sources.lock().shrink_to_fit();
+            }
+        }
+        self.state.shrink_to_fit();
+        self.dirty.shrink_to_fit();
+        self.edges.shrink_to_fit();
+        self.scheduled.shrink_to_fit();
+    }
+
+    /// Like [`flush`](Self::flush), but call `f` with each action which had
+    /// unconsumed state changes
+    ///
+    /// Actions are visited in an unspecified but consistent order. Actions
+    /// excluded from polling by [`set_polled`](Self::set_polled) are never
+    /// reported. See also [`Dispatcher`].
+    pub fn flush_with(&mut self, mut f: impl FnMut(ActionId)) {
+        self.end_frame();
+        let now = self.now();
+        for (index, slot) in self.state.iter().enumerate() {
+            let Some(slot) = slot else {
+                continue;
+            };
+            let id = ActionId {
+                index: index as u32,
+                generation: slot.generation,
+            };
+            let keep = match self.buffered.get(&id) {
+                Some(&window) => slot.queued_within(now, window),
+                None => 0,
+            };
+            let flushed = slot.state.get().write().flush(keep);
+            slot.trim();
+            if flushed {
+                f(id);
+            }
         }
     }
 
@@ -964,56 +3342,310 @@ impl Seat {
     /// Most applications do not need to call this directly. It is usually
     /// called automatically by [`Bindings::handle`], which is in turn usually
     /// called by external event handlers.
+    ///
+    /// State for a removed action is discarded when its [`ActionId`] is reused
+    /// by a new action, after which pushes to the removed action fail with
+    /// [`PushError::Stale`]. Pushes to actions the seat doesn't permit fail
+    /// with [`PushError::Denied`]. Pushes dropped due to
+    /// [`set_distinct`](Self::set_distinct) succeed.
     pub fn push<T: 'static + Clone>(
         &mut self,
         action: ActionId,
         value: T,
-    ) -> Result<(), TypeError> {
-        if self.state.len() <= action.0 as usize {
-            self.state.resize_with(action.0 as usize + 1, || None);
-        }
-        match self.state[action.0 as usize] {
-            ref mut slot @ None => {
-                *slot = Some(Box::new(RwLock::new(ActionState {
-                    queue: VecDeque::from_iter([value.clone()]),
-                    latest: value,
-                })));
+    ) -> Result<(), PushError> {
+        self.push_changed(action, value).map(drop)
+    }
+
+    /// Implementation of [`push`](Self::push)
+    ///
+    /// Returns whether the state changed, i.e. whether the push wasn't
+    /// dropped as a duplicate.
+    fn push_changed<T: 'static + Clone>(
+        &mut self,
+        action: ActionId,
+        value: T,
+    ) -> Result<bool, PushError> {
+        if !self.is_permitted(action) {
+            return Err(PushError::Denied);
+        }
+        let distinct = self.distinct.get(&action).copied();
+        let index = action.index as usize;
+        if self.state.len() <= index {
+            self.state.resize_with(index + 1, || None);
+        }
+        let now = self.now();
+        self.pushes += 1;
+        let pushed = self.pushes;
+        let slot = &mut self.state[index];
+        match *slot {
+            Some(ref slot) if slot.generation > action.generation => {
+                return Err(PushError::Stale);
             }
-            Some(ref mut state) => {
-                let mut state = state.write().unwrap();
-                let Some(state) = (&mut *state as &mut dyn Any).downcast_mut::<ActionState<T>>()
-                else {
-                    return Err(TypeError {
+            Some(ref mut slot) if slot.generation == action.generation => {
+                let mut state = slot.state.get().write();
+                if distinct.is_some_and(|eq| eq(state.latest(), &value)) {
+                    return Ok(false);
+                }
+                let activated = is_activation(Some(state.latest()), &value);
+                let mut value = Some(value);
+                if !state.push(&mut value) {
+                    return Err(PushError::Type(TypeError {
                         expected: state.data_type_name(),
                         actual: type_name::<T>(),
-                    });
-                };
-                state.latest.clone_from(&value);
-                state.queue.push_back(value);
+                    }));
+                }
+                drop(state);
+                slot.changed = now;
+                slot.pushed = pushed;
+                *slot.unread.get_mut() = true;
+                if activated {
+                    slot.activated = Some(now);
+                }
+                slot.record(self.frame, now, &self.input);
+            }
+            // Vacant, or left over from a removed action
+            _ => {
+                let new = slot.insert(Slot {
+                    generation: action.generation,
+                    changed: now,
+                    pushed,
+                    activated: is_activation(None, &value).then_some(now),
+                    state: SlotState::new(value, !self.unpolled.contains(&action)),
+                    sources: self.attributed.contains(&action).then(Mutex::default),
+                    history: Mutex::default(),
+                    unread: AtomicBool::new(true),
+                });
+                new.record(self.frame, now, &self.input);
             }
         }
-        Ok(())
+        Ok(true)
+    }
+
+    /// Perform pushes scheduled with [`push_at`](Self::push_at) which are
+    /// due, marking changed actions dirty if `filtered`
+    fn run_scheduled(&mut self, filtered: impl Fn(ActionId) -> bool) {
+        if self.scheduled.is_empty() {
+            return;
+        }
+        let now = self.now();
+        let due = self.scheduled.partition_point(|s| s.at <= now);
+        for scheduled in self.scheduled.drain(..due).collect::<Vec<_>>() {
+            if matches!((scheduled.push)(self), Ok(true)) && filtered(scheduled.action) {
+                self.dirty.insert(scheduled.action);
+            }
+        }
+    }
+
+    /// Push `value` to `action` once [`now`](Self::now) reaches `at`
+    ///
+    /// Scheduled pushes are performed in order of time by
+    /// [`Bindings::update`], which must be called regularly for them to take
+    /// effect. Pushes which fail, e.g. because the action was removed, are
+    /// dropped.
+    pub fn push_at<T: 'static + Clone>(&mut self, at: Duration, action: ActionId, value: T) {
+        let index = self.scheduled.partition_point(|s| s.at <= at);
+        self.scheduled.insert(
+            index,
+            Scheduled {
+                at,
+                action,
+                push: Box::new(move |seat| seat.push_changed(action, value)),
+            },
+        );
+    }
+
+    /// Reapply filters that read `action` once [`now`](Self::now) reaches
+    /// `at`, without changing its state
+    ///
+    /// Lets time-aware [`Filter`]s keep producing output while their sources
+    /// are unchanged. Performed by [`Bindings::update`] like
+    /// [`push_at`](Self::push_at), so a time that's already passed means the
+    /// next call.
+    pub fn wake_at(&mut self, at: Duration, action: ActionId) {
+        let index = self.scheduled.partition_point(|s| s.at <= at);
+        self.scheduled.insert(
+            index,
+            Scheduled {
+                at,
+                action,
+                push: Box::new(|_| Ok(true)),
+            },
+        );
+    }
+
+    /// State of type `T` kept by a [`Filter`] between applications, created
+    /// with [`Default`] if absent
+    ///
+    /// `key` should be an action owned by the filter, e.g. one of its source
+    /// actions, so that instances of the same filter don't share state.
+    /// Discarded by [`clear`](Self::clear).
+    pub fn filter_state<T: Default + 'static>(&mut self, key: ActionId) -> &mut T {
+        self.filter_state
+            .entry((TypeId::of::<T>(), key))
+            .or_insert_with(|| Box::new(T::default()))
+            .downcast_mut()
+            .unwrap()
     }
 }
 
+/// Reasons why [`Seat::push`] might fail
+#[derive(Debug, Clone)]
+pub enum PushError {
+    /// The action's existing state has a different type
+    Type(TypeError),
+    /// The action was removed from its [`Session`], and its [`ActionId`] reused
+    Stale,
+    /// The seat doesn't permit pushes to the action
+    ///
+    /// See [`Seat::set_allowed`].
+    Denied,
+}
+
+impl fmt::Display for PushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PushError::Type(ref e) => e.fmt(f),
+            PushError::Stale => f.write_str("stale action handle"),
+            PushError::Denied => f.write_str("action not permitted"),
+        }
+    }
+}
+
+impl core::error::Error for PushError {}
+
+/// Type-erased [`ActionState`]
+///
+/// Values are passed as `&mut Option<T>`, where `T` is the type of data
+/// associated with the action.
 trait AnyState: Any {
-    fn flush(&mut self);
+    /// Pop the oldest queued value into `out`
+    fn poll(&mut self, out: &mut dyn Any);
+    /// Discard all queued values, writing the newest into `out`
+    fn poll_latest(&mut self, out: &mut dyn Any);
+    /// Write the latest value into `out`
+    fn get(&self, out: &mut dyn Any);
+    /// The latest value
+    fn latest(&self) -> &dyn Any;
+    /// Take a new value from `value`, if it's of the expected type
+    fn push(&mut self, value: &mut dyn Any) -> bool;
+    /// Discard all but the newest `keep` queued changes, returning whether
+    /// there were any to discard
+    fn flush(&mut self, keep: usize) -> bool;
+    /// Release unused queue storage
+    fn shrink_to_fit(&mut self);
+    /// Bytes of heap storage held by the queue
+    fn queue_size(&self) -> usize;
+    /// Empty [`SeatPool`] storage for values of this type
+    fn column(&self) -> Box<dyn AnyColumn>;
     fn data_type_name(&self) -> &'static str;
+    /// Number of queued values
+    fn queued(&self) -> usize;
+    fn set_polled(&mut self, polled: bool);
 }
 
-struct ActionState<T> {
-    queue: VecDeque<T>,
+struct ActionState<T, Q> {
+    queue: Q,
     latest: T,
+    /// Whether `queue` is maintained
+    polled: bool,
+    /// Greatest length of `queue` since the last flush
+    peak: usize,
 }
 
-impl<T: 'static> AnyState for ActionState<T> {
-    fn flush(&mut self) {
-        self.queue.clear();
+impl<T: Clone, Q: Queue<T>> ActionState<T, Q> {
+    fn new(value: T, polled: bool) -> Self {
+        let mut queue = Q::default();
+        if polled {
+            queue.push_back(value.clone());
+        }
+        Self {
+            peak: queue.len(),
+            queue,
+            latest: value,
+            polled,
+        }
+    }
+}
+
+impl<T: 'static + Clone, Q: Queue<T> + 'static> AnyState for ActionState<T, Q> {
+    fn poll(&mut self, out: &mut dyn Any) {
+        *out.downcast_mut::<Option<T>>().expect("type mismatch") = self.queue.pop_front();
+    }
+
+    fn poll_latest(&mut self, out: &mut dyn Any) {
+        let out = out.downcast_mut::<Option<T>>().expect("type mismatch");
+        if self.queue.pop_front().is_some() {
+            // The newest queued value is always the latest
+            self.queue.clear();
+            *out = Some(self.latest.clone());
+        }
+    }
+
+    fn get(&self, out: &mut dyn Any) {
+        *out.downcast_mut::<Option<T>>().expect("type mismatch") = Some(self.latest.clone());
+    }
+
+    fn latest(&self) -> &dyn Any {
+        &self.latest
+    }
+
+    fn push(&mut self, value: &mut dyn Any) -> bool {
+        let Some(value) = value.downcast_mut::<Option<T>>() else {
+            return false;
+        };
+        let value = value.take().unwrap();
+        if self.polled {
+            self.latest.clone_from(&value);
+            self.queue.push_back(value);
+            self.peak = self.peak.max(self.queue.len());
+        } else {
+            self.latest = value;
+        }
+        true
+    }
+
+    fn flush(&mut self, keep: usize) -> bool {
+        if keep > 0 {
+            let excess = self.queue.len() > keep;
+            while self.queue.len() > keep {
+                self.queue.pop_front();
+            }
+            self.peak = keep;
+            return excess;
+        }
+        let nonempty = !self.queue.is_empty();
+        self.queue.flush(self.peak);
+        self.peak = 0;
+        nonempty
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.queue.shrink_to_fit();
+    }
+
+    fn queue_size(&self) -> usize {
+        self.queue.heap_size()
+    }
+
+    fn column(&self) -> Box<dyn AnyColumn> {
+        Box::new(pool::TypedColumn::<T>::default())
     }
 
     fn data_type_name(&self) -> &'static str {
         type_name::<T>()
     }
+
+    fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    fn set_polled(&mut self, polled: bool) {
+        self.polled = polled;
+        if !polled {
+            // Free any storage
+            self.queue = Q::default();
+        }
+    }
 }
 
 /// A high-level semantic control used by an application
@@ -1048,17 +3680,27 @@ impl<T> Action<T> {
 impl<T> Copy for Action<T> {}
 impl<T> Clone for Action<T> {
     fn clone(&self) -> Self {
-        Self {
-            id: self.id,
-            _marker: PhantomData,
-        }
+        *self
     }
 }
 
 /// Untyped handle to an [`Action`] in some [`Session`]
+///
+/// Handles to actions which have been removed with
+/// [`Session::remove_action`] are detected as stale, even if a newer action
+/// has taken their place.
+///
+/// Serializes as an index and generation, which are only meaningful within the
+/// [`Session`] that created it. See the `serialize` module (requires the
+/// `serde` feature) for a representation that remains stable as actions are
+/// added.
 // TODO: Nonzero
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-pub struct ActionId(u32);
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ActionId {
+    index: u32,
+    generation: u32,
+}
 
 /// Untyped handle to a [`Filter`] in some [`Bindings`]
 // TODO: Nonzero
@@ -0,0 +1,108 @@
+/// Declare a struct of [`Action`](crate::Action) handles
+///
+/// Generates a struct with a public [`Action`](crate::Action) field per
+/// entry, and a `new` function which creates each action in a
+/// [`Session`](crate::Session). Action names default to the field name, and
+/// may be overridden with `= "name"`. Names are checked for uniqueness at
+/// compile time, so `new` can only fail if the session already contains an
+/// action with one of the same names.
+///
+/// ```
+/// enact::actions! {
+///     pub struct Actions {
+///         direction: mint::Vector2<f64>,
+///         jump: (),
+///         fire: () = "shoot",
+///     }
+/// }
+///
+/// let mut session = enact::Session::new();
+/// let actions = Actions::new(&mut session).unwrap();
+/// assert_eq!(session.action_name(actions.fire.id()), "shoot");
+/// ```
+#[macro_export]
+macro_rules! actions {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field:ident : $ty:ty $(= $action_name:literal)?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                pub $field: $crate::Action<$ty>,
+            )*
+        }
+
+        impl $name {
+            /// Create all actions in `session`
+            #[allow(dead_code)]
+            pub fn new(
+                session: &mut $crate::Session,
+            ) -> ::core::result::Result<Self, $crate::DuplicateAction> {
+                const _: () = $crate::__private::assert_unique(&[
+                    $($crate::actions!(@name $field $($action_name)?)),*
+                ]);
+                ::core::result::Result::Ok(Self {
+                    $(
+                        $field: session
+                            .create_action($crate::actions!(@name $field $($action_name)?))?,
+                    )*
+                })
+            }
+        }
+    };
+    (@name $field:ident $action_name:literal) => { $action_name };
+    (@name $field:ident) => { ::core::stringify!($field) };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{vec, vec::Vec};
+    use core::marker::PhantomData;
+
+    use crate::{Action, ActionId};
+
+    /// Construct an [`Action`] without checking its type
+    pub fn action<T>(id: ActionId) -> Action<T> {
+        Action {
+            id,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Panic at compile time if `names` contains duplicates
+    pub const fn assert_unique(names: &[&str]) {
+        let mut i = 0;
+        while i < names.len() {
+            let mut j = i + 1;
+            while j < names.len() {
+                if str_eq(names[i], names[j]) {
+                    panic!("duplicate action name");
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+    }
+
+    const fn str_eq(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+}
@@ -0,0 +1,75 @@
+//! Accounting of memory held by action state
+
+use alloc::vec::Vec;
+use core::mem::{size_of, size_of_val};
+
+use crate::{ActionId, EventSource, Seat, Slot, SlotState, Tag};
+
+/// Bytes of memory held by a [`Seat`] for one action, from
+/// [`Seat::memory_usage`]
+///
+/// Heap storage owned by queued values themselves, e.g. the contents of a
+/// `String`, isn't counted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ActionMemory {
+    pub id: ActionId,
+    /// Storage for changes waiting to be [`poll`](Seat::poll)ed, including
+    /// unused capacity
+    pub queue: usize,
+    /// Everything else: the latest value, and bookkeeping for timing and
+    /// attribution of queued changes
+    pub state: usize,
+}
+
+impl ActionMemory {
+    pub fn total(&self) -> usize {
+        self.queue + self.state
+    }
+}
+
+impl Seat {
+    /// Report the memory held for every action with state, in order of
+    /// [`ActionId`]
+    ///
+    /// Cheap enough to call periodically, e.g. so that servers running many
+    /// seats can alert on queues that grow because they're never
+    /// [`flush`](Self::flush)ed or [`poll`](Self::poll)ed.
+    pub fn memory_usage(&self) -> Vec<ActionMemory> {
+        self.state
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let slot = slot.as_ref()?;
+                Some(ActionMemory {
+                    id: ActionId {
+                        index: index as u32,
+                        generation: slot.generation,
+                    },
+                    queue: slot.state.get().read().queue_size(),
+                    state: slot.state_size(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Slot {
+    /// Bytes held other than by the queue
+    fn state_size(&self) -> usize {
+        let mut size = size_of::<Option<Slot>>();
+        if let SlotState::Other(ref state) = self.state {
+            size += size_of_val(&**state);
+        }
+        size += self.history.lock().tags.capacity() * size_of::<Tag>();
+        if let Some(ref sources) = self.sources {
+            let sources = sources.lock();
+            size += sources.capacity() * size_of::<Option<EventSource>>();
+            size += sources
+                .iter()
+                .flatten()
+                .map(|x| x.input.capacity())
+                .sum::<usize>();
+        }
+        size
+    }
+}
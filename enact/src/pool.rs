@@ -0,0 +1,399 @@
+//! Compact storage of the latest action state for many seats
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    any::{Any, type_name},
+    marker::PhantomData,
+    mem,
+    time::Duration,
+};
+
+use crate::{Action, ActionId, Clock, EdgeSets, PushError, Seat, TypeError};
+
+/// Handle to a seat in a [`SeatPool`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PooledSeat {
+    index: u32,
+    generation: u32,
+}
+
+/// Latest action state for many seats, e.g. every player connected to an
+/// authoritative server
+///
+/// Where each [`Seat`] owns separately allocated state for every action, a
+/// pool stores each action's values for all seats in a single column, and
+/// tracks which values changed since the last [`flush`](Self::flush) in a
+/// bitset per action. Only the latest value of each action is retained; there
+/// is no queue to [`poll`](Seat::poll).
+///
+/// Inputs are resolved through [`with_seat`](Self::with_seat), which lends out
+/// a reusable [`Seat`] holding one pooled seat's state.
+#[derive(Default)]
+pub struct SeatPool {
+    /// Generation of each seat index
+    generations: Vec<u32>,
+    /// Indices of removed seats, available for reuse
+    free: Vec<u32>,
+    /// Indexed by [`ActionId::index`]
+    columns: Vec<Option<Column>>,
+    /// Edge bindings whose input was last `true`, indexed by seat, as in
+    /// [`Seat`]
+    edges: Vec<EdgeSets>,
+    scratch: Seat,
+}
+
+/// Every seat's state for one action
+struct Column {
+    /// Generation of the [`ActionId`] this column belongs to
+    generation: u32,
+    values: Box<dyn AnyColumn>,
+    /// Time of the most recent change and activation, indexed by seat, for
+    /// values set by [`SeatPool::with_seat`]
+    times: Vec<Option<Times>>,
+    /// Bitset of seat indices whose values changed since the last flush
+    changed: Vec<u64>,
+}
+
+/// See [`Seat::last_change`] and [`Seat::last_activation`]
+#[derive(Copy, Clone)]
+struct Times {
+    changed: Duration,
+    activated: Option<Duration>,
+}
+
+impl Column {
+    fn set_changed(&mut self, seat: u32) {
+        let (word, bit) = (seat as usize / 64, seat % 64);
+        if self.changed.len() <= word {
+            self.changed.resize(word + 1, 0);
+        }
+        self.changed[word] |= 1 << bit;
+    }
+
+    fn is_changed(&self, seat: u32) -> bool {
+        let (word, bit) = (seat as usize / 64, seat % 64);
+        self.changed.get(word).is_some_and(|x| x & (1 << bit) != 0)
+    }
+
+    fn clear_changed(&mut self, seat: u32) {
+        if let Some(word) = self.changed.get_mut(seat as usize / 64) {
+            *word &= !(1 << (seat % 64));
+        }
+    }
+}
+
+/// Type-erased [`TypedColumn`]
+pub(crate) trait AnyColumn {
+    /// Store `value` for `seat`, if it's of the expected type
+    fn set(&mut self, seat: u32, value: &dyn Any) -> bool;
+    fn get(&self, seat: u32) -> Option<&dyn Any>;
+    fn remove(&mut self, seat: u32);
+    /// Push `seat`'s value, if any, to `action` in `into`
+    fn load(&self, seat: u32, action: ActionId, into: &mut Seat);
+    /// Store the latest value of `action` in `from` for `seat`
+    fn store(&mut self, seat: u32, action: ActionId, from: &Seat);
+    fn data_type_name(&self) -> &'static str;
+}
+
+/// Values of type `T`, indexed by seat
+pub(crate) struct TypedColumn<T>(Vec<Option<T>>);
+
+impl<T> Default for TypedColumn<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T: 'static + Clone> TypedColumn<T> {
+    fn insert(&mut self, seat: u32, value: T) {
+        let index = seat as usize;
+        if self.0.len() <= index {
+            self.0.resize_with(index + 1, || None);
+        }
+        self.0[index] = Some(value);
+    }
+}
+
+impl<T: 'static + Clone> AnyColumn for TypedColumn<T> {
+    fn set(&mut self, seat: u32, value: &dyn Any) -> bool {
+        let Some(value) = value.downcast_ref::<T>() else {
+            return false;
+        };
+        self.insert(seat, value.clone());
+        true
+    }
+
+    fn get(&self, seat: u32) -> Option<&dyn Any> {
+        self.0.get(seat as usize)?.as_ref().map(|x| x as &dyn Any)
+    }
+
+    fn remove(&mut self, seat: u32) {
+        if let Some(x) = self.0.get_mut(seat as usize) {
+            *x = None;
+        }
+    }
+
+    fn load(&self, seat: u32, action: ActionId, into: &mut Seat) {
+        if let Some(Some(value)) = self.0.get(seat as usize) {
+            // Types match, as the scratch seat was cleared
+            let _ = into.push(action, value.clone());
+        }
+    }
+
+    fn store(&mut self, seat: u32, action: ActionId, from: &Seat) {
+        let action = Action::<T> {
+            id: action,
+            _marker: PhantomData,
+        };
+        if let Some(value) = from.get(action) {
+            self.insert(seat, value);
+        }
+    }
+
+    fn data_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+}
+
+impl SeatPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the clock of the [`Seat`] lent out by [`with_seat`](Self::with_seat)
+    pub fn set_clock(&mut self, clock: impl Clock) {
+        self.scratch.set_clock(clock);
+    }
+
+    /// Add a seat with no action state
+    pub fn insert(&mut self) -> PooledSeat {
+        let index = self.free.pop().unwrap_or_else(|| {
+            self.generations.push(0);
+            (self.generations.len() - 1) as u32
+        });
+        PooledSeat {
+            index,
+            generation: self.generations[index as usize],
+        }
+    }
+
+    /// Discard a seat's state
+    ///
+    /// Returns `false` if the seat was already removed.
+    pub fn remove(&mut self, seat: PooledSeat) -> bool {
+        if !self.contains(seat) {
+            return false;
+        }
+        for column in self.columns.iter_mut().flatten() {
+            column.values.remove(seat.index);
+            if let Some(times) = column.times.get_mut(seat.index as usize) {
+                *times = None;
+            }
+            column.clear_changed(seat.index);
+        }
+        if let Some(edges) = self.edges.get_mut(seat.index as usize) {
+            edges.clear();
+        }
+        self.generations[seat.index as usize] += 1;
+        self.free.push(seat.index);
+        true
+    }
+
+    pub fn contains(&self, seat: PooledSeat) -> bool {
+        // Removal advances the generation
+        self.generations.get(seat.index as usize) == Some(&seat.generation)
+    }
+
+    /// Number of seats in the pool
+    pub fn len(&self) -> usize {
+        self.generations.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Set the latest value of `action` for `seat`
+    ///
+    /// Fails with [`PushError::Stale`] if the seat was removed.
+    pub fn push<T: 'static + Clone>(
+        &mut self,
+        seat: PooledSeat,
+        action: ActionId,
+        value: T,
+    ) -> Result<(), PushError> {
+        if !self.contains(seat) {
+            return Err(PushError::Stale);
+        }
+        let column = column(&mut self.columns, action, || {
+            Box::new(TypedColumn::<T>::default())
+        })?;
+        if !column.values.set(seat.index, &value) {
+            return Err(PushError::Type(TypeError {
+                expected: column.values.data_type_name(),
+                actual: type_name::<T>(),
+            }));
+        }
+        if let Some(times) = column.times.get_mut(seat.index as usize) {
+            *times = None;
+        }
+        column.set_changed(seat.index);
+        Ok(())
+    }
+
+    /// The latest value of `action` for `seat`, if any
+    pub fn get<T: 'static + Clone>(&self, seat: PooledSeat, action: Action<T>) -> Option<T> {
+        if !self.contains(seat) {
+            return None;
+        }
+        let column = self.get_column(action.id)?;
+        column.values.get(seat.index)?.downcast_ref::<T>().cloned()
+    }
+
+    /// Whether `action` changed for `seat` since the last
+    /// [`flush`](Self::flush)
+    pub fn changed(&self, seat: PooledSeat, action: ActionId) -> bool {
+        self.contains(seat)
+            && self
+                .get_column(action)
+                .is_some_and(|x| x.is_changed(seat.index))
+    }
+
+    /// Forget which actions changed, for every seat at once
+    pub fn flush(&mut self) {
+        self.flush_with(|_, _| ());
+    }
+
+    /// Like [`flush`](Self::flush), but call `f` with each seat and action
+    /// which changed
+    ///
+    /// Changes are visited in order of action, then seat.
+    pub fn flush_with(&mut self, mut f: impl FnMut(PooledSeat, ActionId)) {
+        for (action, column) in self.columns.iter_mut().enumerate() {
+            let Some(column) = column else {
+                continue;
+            };
+            let id = ActionId {
+                index: action as u32,
+                generation: column.generation,
+            };
+            for (word, bits) in column.changed.iter_mut().enumerate() {
+                let mut remaining = *bits;
+                while remaining != 0 {
+                    let index = (word * 64) as u32 + remaining.trailing_zeros();
+                    remaining &= remaining - 1;
+                    let seat = PooledSeat {
+                        index,
+                        generation: self.generations[index as usize],
+                    };
+                    f(seat, id);
+                }
+                *bits = 0;
+            }
+        }
+    }
+
+    /// Run `f` with a [`Seat`] holding the latest state of `seat`, e.g. to
+    /// pass inputs to [`Bindings::handle`](crate::Bindings::handle), then
+    /// store the resulting changes
+    ///
+    /// The latest values of actions are retained between calls, along with
+    /// the times they last changed and activated and the state of edge
+    /// bindings. Other state, such as pushes scheduled with
+    /// [`Seat::push_at`], isn't, so should be fully resolved within `f`.
+    /// Returns `None` if the seat was removed.
+    pub fn with_seat<R>(&mut self, seat: PooledSeat, f: impl FnOnce(&mut Seat) -> R) -> Option<R> {
+        if !self.contains(seat) {
+            return None;
+        }
+        for (action, column) in self.columns.iter().enumerate() {
+            let Some(column) = column else {
+                continue;
+            };
+            let id = ActionId {
+                index: action as u32,
+                generation: column.generation,
+            };
+            column.values.load(seat.index, id, &mut self.scratch);
+            let times = column.times.get(seat.index as usize).copied().flatten();
+            if let (Some(times), Some(Some(slot))) = (times, self.scratch.state.get_mut(action)) {
+                slot.changed = times.changed;
+                slot.activated = times.activated;
+            }
+        }
+        if let Some(edges) = self.edges.get_mut(seat.index as usize) {
+            mem::swap(&mut self.scratch.edges, edges);
+        }
+        // Only changes made by `f` are stored
+        self.scratch.flush();
+        let before = self.scratch.pushes;
+        let out = f(&mut self.scratch);
+        for (action, slot) in self.scratch.state.iter().enumerate() {
+            let Some(slot) = slot.as_ref().filter(|x| x.pushed > before) else {
+                continue;
+            };
+            let id = ActionId {
+                index: action as u32,
+                generation: slot.generation,
+            };
+            let state = slot.state.get().read();
+            let Ok(column) = column(&mut self.columns, id, || state.column()) else {
+                continue;
+            };
+            drop(state);
+            column.values.store(seat.index, id, &self.scratch);
+            let index = seat.index as usize;
+            if column.times.len() <= index {
+                column.times.resize(index + 1, None);
+            }
+            column.times[index] = Some(Times {
+                changed: slot.changed,
+                activated: slot.activated,
+            });
+            column.set_changed(seat.index);
+        }
+        if !self.scratch.edges.is_empty() {
+            let index = seat.index as usize;
+            if self.edges.len() <= index {
+                self.edges.resize_with(index + 1, EdgeSets::default);
+            }
+            mem::swap(&mut self.edges[index], &mut self.scratch.edges);
+        }
+        self.scratch.clear();
+        Some(out)
+    }
+
+    fn get_column(&self, action: ActionId) -> Option<&Column> {
+        self.columns
+            .get(action.index as usize)?
+            .as_ref()
+            .filter(|x| x.generation == action.generation)
+    }
+}
+
+/// The column for `action`, created by `new` if necessary
+fn column(
+    columns: &mut Vec<Option<Column>>,
+    action: ActionId,
+    new: impl FnOnce() -> Box<dyn AnyColumn>,
+) -> Result<&mut Column, PushError> {
+    let index = action.index as usize;
+    if columns.len() <= index {
+        columns.resize_with(index + 1, || None);
+    }
+    let column = &mut columns[index];
+    match *column {
+        Some(ref x) if x.generation > action.generation => return Err(PushError::Stale),
+        Some(ref x) if x.generation == action.generation => {}
+        // Vacant, or left over from a removed action
+        _ => {
+            *column = Some(Column {
+                generation: action.generation,
+                values: new(),
+                times: Vec::new(),
+                changed: Vec::new(),
+            });
+        }
+    }
+    Ok(column.as_mut().unwrap())
+}
@@ -0,0 +1,175 @@
+use alloc::collections::VecDeque;
+
+/// FIFO storage for the history of an action's state
+///
+/// Specialized for common action types so that they can be stored without
+/// allocating.
+pub trait Queue<T>: Default {
+    fn push_back(&mut self, value: T);
+    fn pop_front(&mut self) -> Option<T>;
+    fn clear(&mut self);
+    fn is_empty(&self) -> bool;
+    fn len(&self) -> usize;
+
+    /// Discard all values, gradually releasing storage much larger than the
+    /// `peak` number of values held at once since the last flush
+    ///
+    /// Releases at most half of the capacity per call, so that a single burst
+    /// doesn't permanently inflate memory use, yet steady use doesn't
+    /// reallocate every time.
+    fn flush(&mut self, peak: usize) {
+        let _ = peak;
+        self.clear();
+    }
+
+    /// Release all unused storage
+    fn shrink_to_fit(&mut self) {}
+
+    /// Bytes of heap storage held
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+/// Clear `queue`, halving its capacity if it's much larger than `peak`
+fn decay<T>(queue: &mut VecDeque<T>, peak: usize) {
+    queue.clear();
+    if peak < queue.capacity() / 4 {
+        queue.shrink_to(queue.capacity() / 2);
+    }
+}
+
+impl<T> Queue<T> for VecDeque<T> {
+    fn push_back(&mut self, value: T) {
+        VecDeque::push_back(self, value);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        VecDeque::pop_front(self)
+    }
+
+    fn clear(&mut self) {
+        VecDeque::clear(self);
+    }
+
+    fn is_empty(&self) -> bool {
+        VecDeque::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn flush(&mut self, peak: usize) {
+        decay(self, peak);
+    }
+
+    fn shrink_to_fit(&mut self) {
+        VecDeque::shrink_to_fit(self);
+    }
+
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>()
+    }
+}
+
+/// A queue of `()`, i.e. a counter
+#[derive(Default)]
+pub struct UnitQueue {
+    len: usize,
+}
+
+impl Queue<()> for UnitQueue {
+    fn push_back(&mut self, (): ()) {
+        self.len += 1;
+    }
+
+    fn pop_front(&mut self) -> Option<()> {
+        self.len = self.len.checked_sub(1)?;
+        Some(())
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// A queue of `bool`s packed into an integer
+///
+/// Only allocates if more than [`BitQueue::INLINE`] values are queued at once.
+#[derive(Default)]
+pub struct BitQueue {
+    /// The oldest value is in the least significant bit
+    bits: u64,
+    len: u32,
+    /// Values queued after `bits` filled up
+    overflow: VecDeque<bool>,
+}
+
+impl BitQueue {
+    const INLINE: u32 = u64::BITS;
+}
+
+impl Queue<bool> for BitQueue {
+    fn push_back(&mut self, value: bool) {
+        if self.len == Self::INLINE {
+            self.overflow.push_back(value);
+            return;
+        }
+        self.bits |= u64::from(value) << self.len;
+        self.len += 1;
+    }
+
+    fn pop_front(&mut self) -> Option<bool> {
+        if self.len == 0 {
+            return None;
+        }
+        let value = self.bits & 1 != 0;
+        self.bits >>= 1;
+        self.len -= 1;
+        if let Some(next) = self.overflow.pop_front() {
+            self.bits |= u64::from(next) << self.len;
+            self.len += 1;
+        }
+        Some(value)
+    }
+
+    fn clear(&mut self) {
+        self.bits = 0;
+        self.len = 0;
+        self.overflow.clear();
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn len(&self) -> usize {
+        self.len as usize + self.overflow.len()
+    }
+
+    fn flush(&mut self, peak: usize) {
+        self.bits = 0;
+        self.len = 0;
+        decay(
+            &mut self.overflow,
+            peak.saturating_sub(Self::INLINE as usize),
+        );
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.overflow.shrink_to_fit();
+    }
+
+    fn heap_size(&self) -> usize {
+        Queue::heap_size(&self.overflow)
+    }
+}
@@ -0,0 +1,297 @@
+//! Recording of inputs, and replay to detect changes in how they're resolved
+
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::{any::Any, cell::RefCell, time::Duration};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ActionId, Bindings, FxHashMap, HandleInput, Input, ManualClock, Seat, Session, TypeError,
+    has_type,
+};
+
+/// A log of inputs and the action changes they caused, from a [`Recorder`]
+///
+/// [`check`](Self::check) replays the inputs against other bindings and
+/// reports where the resulting action changes differ, catching accidental
+/// changes in behavior after editing configs or filters.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recording {
+    pub events: Vec<RecordedEvent>,
+}
+
+/// An input, or a call to [`Bindings::update`], and its effects
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordedEvent {
+    /// [`Seat::now`] when the event occurred
+    pub at: Duration,
+    /// `None` for a call to [`Bindings::update`]
+    pub input: Option<RecordedInput>,
+    /// Names of actions whose latest value changed as a result, with their
+    /// new values formatted as in [`Seat::debug_snapshot`], in order of name
+    pub changes: Vec<(String, String)>,
+}
+
+/// An input passed to [`Bindings::handle`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecordedInput {
+    /// [`Input::NAME`] of the input's source
+    pub source: String,
+    /// From [`Input::to_string`]
+    pub input: String,
+    pub value: InputValue,
+}
+
+impl RecordedInput {
+    /// Describe `input` producing `data`, if `data` is of a type covered by
+    /// [`InputValue`]
+    pub fn new<I: Input, T: 'static>(input: &I, data: &T) -> Option<Self> {
+        Some(Self {
+            source: I::NAME.to_owned(),
+            input: input.to_string(),
+            value: InputValue::new(data)?,
+        })
+    }
+
+    /// Pass this input to `bindings`, as its source's backend would have
+    ///
+    /// Returns `false` if `bindings` has no bindings for the source, or
+    /// `input` isn't a valid input producing `value`.
+    pub fn handle(&self, bindings: &Bindings, seat: &mut Seat) -> bool {
+        bindings
+            .actions
+            .values()
+            .find(|x| x.name() == self.source)
+            .is_some_and(|source| source.replay(bindings, &self.input, &self.value, seat))
+    }
+}
+
+/// Data produced by an input
+///
+/// Covers the types produced by the backends in this project. Inputs
+/// producing other types aren't recorded.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InputValue {
+    Unit,
+    Bool(bool),
+    F64(f64),
+    Vector2([f64; 2]),
+    Vector3([f64; 3]),
+    String(String),
+}
+
+impl InputValue {
+    fn new<T: 'static>(data: &T) -> Option<Self> {
+        let data = data as &dyn Any;
+        Some(if data.is::<()>() {
+            InputValue::Unit
+        } else if let Some(&x) = data.downcast_ref::<bool>() {
+            InputValue::Bool(x)
+        } else if let Some(&x) = data.downcast_ref::<f64>() {
+            InputValue::F64(x)
+        } else if let Some(&x) = data.downcast_ref::<mint::Vector2<f64>>() {
+            InputValue::Vector2(x.into())
+        } else if let Some(&x) = data.downcast_ref::<mint::Vector3<f64>>() {
+            InputValue::Vector3(x.into())
+        } else if let Some(x) = data.downcast_ref::<String>() {
+            InputValue::String(x.clone())
+        } else {
+            return None;
+        })
+    }
+
+    /// Whether `input` produces data of this type
+    fn fits<I: Input>(&self, input: &I) -> bool {
+        match *self {
+            InputValue::Unit => has_type::<(), I>(input),
+            InputValue::Bool(_) => has_type::<bool, I>(input),
+            InputValue::F64(_) => has_type::<f64, I>(input),
+            InputValue::Vector2(_) => has_type::<mint::Vector2<f64>, I>(input),
+            InputValue::Vector3(_) => has_type::<mint::Vector3<f64>, I>(input),
+            InputValue::String(_) => has_type::<String, I>(input),
+        }
+    }
+}
+
+/// A difference between a [`Recording`] and its replay, from
+/// [`Recording::check`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// Index into [`Recording::events`]
+    pub event: usize,
+    pub action: String,
+    /// The recorded value, or `None` if the action didn't change
+    pub expected: Option<String>,
+    /// The replayed value, or `None` if the action didn't change
+    pub actual: Option<String>,
+}
+
+/// [`HandleInput`] implementation that records inputs and their effects
+///
+/// Pass to backend input handlers in place of the [`Bindings`], and call
+/// [`update`](Self::update) in place of [`Bindings::update`]. Only actions
+/// whose types can be formatted, as for [`Seat::debug_snapshot`], have their
+/// changes recorded.
+pub struct Recorder<'a> {
+    session: &'a Session,
+    bindings: &'a Bindings,
+    state: RefCell<RecorderState>,
+}
+
+#[derive(Default)]
+struct RecorderState {
+    recording: Recording,
+    /// Latest formatted value of each action
+    values: FxHashMap<ActionId, String>,
+}
+
+impl<'a> Recorder<'a> {
+    pub fn new(session: &'a Session, bindings: &'a Bindings) -> Self {
+        Self {
+            session,
+            bindings,
+            state: RefCell::default(),
+        }
+    }
+
+    /// See [`Bindings::update`]
+    pub fn update(&self, seat: &mut Seat) {
+        self.bindings.update(seat);
+        self.record(None, seat);
+    }
+
+    /// Stop recording
+    pub fn finish(self) -> Recording {
+        self.state.into_inner().recording
+    }
+
+    fn record(&self, input: Option<RecordedInput>, seat: &Seat) {
+        let state = &mut *self.state.borrow_mut();
+        let changes = changes(self.session, seat, &mut state.values);
+        state.recording.events.push(RecordedEvent {
+            at: seat.now(),
+            input,
+            changes,
+        });
+    }
+}
+
+impl HandleInput for Recorder<'_> {
+    fn handle<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+    ) -> Result<(), TypeError> {
+        let recorded = RecordedInput::new(input, &data);
+        self.bindings.handle(input, data, seat)?;
+        if let Some(recorded) = recorded {
+            self.record(Some(recorded), seat);
+        }
+        Ok(())
+    }
+}
+
+/// Actions in `seat` whose formatted values differ from those in `values`,
+/// which is updated to match
+fn changes(
+    session: &Session,
+    seat: &Seat,
+    values: &mut FxHashMap<ActionId, String>,
+) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    for action in seat.debug_snapshot(session) {
+        let Some(value) = action.value else {
+            continue;
+        };
+        if values.get(&action.id) != Some(&value) {
+            values.insert(action.id, value.clone());
+            out.push((action.name, value));
+        }
+    }
+    out.sort_unstable();
+    out
+}
+
+impl Recording {
+    /// Replay the recorded inputs against `bindings` on virtual time, and
+    /// report every difference in the resulting action changes, in order
+    ///
+    /// Inputs from sources with no bindings in `bindings` are skipped.
+    pub fn check(&self, session: &Session, bindings: &Bindings) -> Vec<Divergence> {
+        let clock = ManualClock::new();
+        let mut seat = Seat::new();
+        seat.set_clock(clock.clone());
+        let mut values = FxHashMap::default();
+        let mut out = Vec::new();
+        for (index, event) in self.events.iter().enumerate() {
+            clock.set(event.at);
+            match event.input {
+                Some(ref input) => {
+                    input.handle(bindings, &mut seat);
+                }
+                None => bindings.update(&mut seat),
+            }
+            let actual = changes(session, &seat, &mut values);
+            let mut expected = event.changes.iter().peekable();
+            let mut actual = actual.into_iter().peekable();
+            loop {
+                let (action, expected, actual) = match (expected.peek(), actual.peek()) {
+                    (None, None) => break,
+                    (Some(x), Some(y)) if x.0 == y.0 => {
+                        let x = expected.next().unwrap();
+                        let y = actual.next().unwrap();
+                        if x.1 == y.1 {
+                            continue;
+                        }
+                        (y.0, Some(x.1.clone()), Some(y.1))
+                    }
+                    (Some(x), y) if y.is_none_or(|y| x.0 < y.0) => {
+                        let x = expected.next().unwrap();
+                        (x.0.clone(), Some(x.1.clone()), None)
+                    }
+                    _ => {
+                        let y = actual.next().unwrap();
+                        (y.0, None, Some(y.1))
+                    }
+                };
+                out.push(Divergence {
+                    event: index,
+                    action,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        out
+    }
+}
+
+/// Parse `input` as an `I`, and pass it to `bindings` with `value`
+///
+/// Returns whether `input` was valid.
+pub(crate) fn replay_input<I: Input>(
+    bindings: &Bindings,
+    input: &str,
+    value: &InputValue,
+    seat: &mut Seat,
+) -> bool {
+    let Some(input) = I::from_str(input).into_iter().find(|x| value.fits(x)) else {
+        return false;
+    };
+    // Types were checked above
+    match *value {
+        InputValue::Unit => bindings.handle(&input, (), seat),
+        InputValue::Bool(x) => bindings.handle(&input, x, seat),
+        InputValue::F64(x) => bindings.handle(&input, x, seat),
+        InputValue::Vector2(x) => bindings.handle(&input, mint::Vector2::from(x), seat),
+        InputValue::Vector3(x) => bindings.handle(&input, mint::Vector3::from(x), seat),
+        InputValue::String(ref x) => bindings.handle(&input, x.clone(), seat),
+    }
+    .is_ok()
+}
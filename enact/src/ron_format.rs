@@ -0,0 +1,35 @@
+//! [RON](https://github.com/ron-rs/ron) encoding of [`Config`], for configs
+//! with deeply structured filters that are awkward to write in TOML
+//!
+//! Uses the same serde representation as any other format, so configs can be
+//! converted freely between them. For example:
+//!
+//! ```ron
+//! (
+//!     sources: [
+//!         (type: "winit", bindings: {"jump": ["space"]}),
+//!     ],
+//!     filters: [
+//!         (type: "macro", targets: ["dash"], params: ["jump", "0 dash", "150 dash"]),
+//!     ],
+//! )
+//! ```
+
+use alloc::string::String;
+
+use crate::Config;
+
+pub use ron::error::SpannedError as RonError;
+
+impl Config {
+    /// Parse a config written in RON
+    pub fn from_ron(s: &str) -> Result<Self, RonError> {
+        ron::from_str(s)
+    }
+
+    /// Write this config as human-readable RON
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::new())
+            .expect("configs are always serializable")
+    }
+}
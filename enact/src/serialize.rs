@@ -0,0 +1,62 @@
+//! Session-aware serialization of action references
+//!
+//! [`ActionId`]s are only meaningful with respect to the [`Session`] they were
+//! created in, and may change when actions are added or reordered. Formats
+//! which outlive a single run of an application, such as save files or replay
+//! logs, should instead refer to actions by name. [`ActionName`] serializes an
+//! [`ActionId`] as its name, and [`ActionSeed`] resolves a name back into an
+//! [`ActionId`].
+
+use core::fmt;
+
+use serde::{
+    Deserializer, Serialize, Serializer,
+    de::{self, DeserializeSeed, Visitor},
+};
+
+use crate::{ActionId, Session};
+
+/// Serializes an [`ActionId`] as the name of the action in a [`Session`]
+#[derive(Copy, Clone)]
+pub struct ActionName<'a> {
+    pub session: &'a Session,
+    pub id: ActionId,
+}
+
+impl Serialize for ActionName<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.session.action_name(self.id))
+    }
+}
+
+/// Deserializes an action name into the [`ActionId`] it identifies in a
+/// [`Session`]
+///
+/// Names registered with [`Session::create_alias`] are accepted as well.
+#[derive(Copy, Clone)]
+pub struct ActionSeed<'a> {
+    pub session: &'a Session,
+}
+
+impl<'de> DeserializeSeed<'de> for ActionSeed<'_> {
+    type Value = ActionId;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<ActionId, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for ActionSeed<'_> {
+    type Value = ActionId;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an action name")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<ActionId, E> {
+        self.session
+            .action_id(v)
+            .or_else(|| self.session.alias_target(v))
+            .ok_or_else(|| E::custom(format_args!("unknown action: {v}")))
+    }
+}
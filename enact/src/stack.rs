@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+use core::any::{TypeId, type_name};
+
+use crate::{Bindings, GetTypeId, GetTypeName, HandleInput, Input, Seat, TypeError};
+
+/// Layers of [`Bindings`] which handle events top-down
+///
+/// Each event is delivered to the topmost layer which binds its input, and
+/// hidden from all layers below. Useful for modal contexts like a pause menu
+/// or console, which can be pushed over gameplay bindings when opened and
+/// popped when closed.
+///
+/// Every layer should be used with the same [`Session`](crate::Session) and
+/// [`Seat`]s. Note that an input held when a layer binding it is pushed will
+/// be released into that layer, not the one that saw it pressed.
+#[derive(Clone, Default)]
+pub struct BindingStack {
+    /// Bottom layer first
+    layers: Vec<Layer>,
+}
+
+#[derive(Clone)]
+struct Layer {
+    bindings: Bindings,
+    /// Whether events are hidden from lower layers even if not bound here
+    opaque: bool,
+}
+
+impl BindingStack {
+    /// Create a stack with no layers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer on top, which receives events for inputs it binds
+    pub fn push(&mut self, bindings: Bindings) {
+        self.layers.push(Layer {
+            bindings,
+            opaque: false,
+        });
+    }
+
+    /// Add a layer on top which hides all events from lower layers, whether
+    /// or not it binds them
+    pub fn push_opaque(&mut self, bindings: Bindings) {
+        self.layers.push(Layer {
+            bindings,
+            opaque: true,
+        });
+    }
+
+    /// Remove the top layer
+    pub fn pop(&mut self) -> Option<Bindings> {
+        self.layers.pop().map(|layer| layer.bindings)
+    }
+
+    /// The top layer, if any
+    pub fn top(&self) -> Option<&Bindings> {
+        self.layers.last().map(|layer| &layer.bindings)
+    }
+
+    /// The top layer, if any, e.g. for rebinding
+    pub fn top_mut(&mut self) -> Option<&mut Bindings> {
+        self.layers.last_mut().map(|layer| &mut layer.bindings)
+    }
+
+    /// Every layer, bottom first
+    pub fn layers(&self) -> impl DoubleEndedIterator<Item = &Bindings> + '_ {
+        self.layers.iter().map(|layer| &layer.bindings)
+    }
+
+    /// Number of layers
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether there are no layers
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Change the state of `input` to `data` in `seat`, according to the
+    /// topmost layer which binds `input`
+    ///
+    /// See [`Bindings::handle`].
+    pub fn handle<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+    ) -> Result<(), TypeError> {
+        if TypeId::of::<T>() != input.visit_type::<GetTypeId>() {
+            return Err(TypeError {
+                expected: input.visit_type::<GetTypeName>(),
+                actual: type_name::<T>(),
+            });
+        }
+        let mut data = data;
+        for layer in self.layers.iter().rev() {
+            match layer.bindings.dispatch(input, data, seat)? {
+                Some(undelivered) if !layer.opaque => data = undelivered,
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Like [`handle`](Self::handle), but if `consumed` is set, only deliver
+    /// releases
+    ///
+    /// See [`HandleInput::handle_consumed`].
+    pub fn handle_consumed<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+        consumed: bool,
+    ) -> Result<(), TypeError> {
+        HandleInput::handle_consumed(self, input, data, seat, consumed)
+    }
+
+    /// Evaluate deferred filters in every layer
+    ///
+    /// See [`Bindings::update`]. Changes from every layer, and pushes
+    /// scheduled by any, are seen by the filters of all layers.
+    pub fn update(&self, seat: &mut Seat) {
+        seat.run_scheduled(|action| {
+            self.layers
+                .iter()
+                .any(|layer| layer.bindings.filter(action).is_some())
+        });
+        if seat.dirty.is_empty() {
+            return;
+        }
+        let mut dirty = core::mem::take(&mut seat.dirty);
+        for layer in &self.layers {
+            layer.bindings.apply_filters(&mut dirty, seat);
+        }
+        dirty.clear();
+        // Reuse the allocation
+        seat.dirty = dirty;
+    }
+}
+
+impl HandleInput for BindingStack {
+    fn handle<I: Input, T: Clone + 'static>(
+        &self,
+        input: &I,
+        data: T,
+        seat: &mut Seat,
+    ) -> Result<(), TypeError> {
+        BindingStack::handle(self, input, data, seat)
+    }
+}
@@ -0,0 +1,442 @@
+//! Export to Steam Input's In-Game Actions format
+
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{any::TypeId, fmt::Write};
+
+use crate::{ActionId, Config, FxHashMap, NAMESPACE_SEPARATOR, Session, vector::VectorType};
+
+/// Translates a [`Session`]'s actions and default bindings into Steam Input
+/// In-Game Actions files
+///
+/// Each action namespace (see [`Session::create_action_in`]) becomes an
+/// action set, and actions without a namespace belong to the
+/// [default set](Self::set_default_set). `()` and `bool` actions become
+/// digital actions, `f64` actions analog triggers, and [`Vector2`] actions
+/// joysticks. Actions of other types are omitted. Titles shown in Steam's UI
+/// default to the names of sets and of actions within their sets.
+///
+/// [`Vector2`]: crate::Vector2
+#[derive(Clone)]
+pub struct SteamManifest<'a> {
+    session: &'a Session,
+    default_set: String,
+    titles: FxHashMap<String, String>,
+    inputs: FxHashMap<(String, String), SteamInput>,
+}
+
+impl<'a> SteamManifest<'a> {
+    pub fn new(session: &'a Session) -> Self {
+        Self {
+            session,
+            default_set: "default".to_owned(),
+            titles: FxHashMap::default(),
+            inputs: FxHashMap::default(),
+        }
+    }
+
+    /// Name of the action set containing actions without a namespace
+    pub fn set_default_set(&mut self, name: &str) {
+        self.default_set = name.to_owned();
+    }
+
+    /// Show the action or action set called `name` as `title` in Steam's UI
+    ///
+    /// Actions are named in full, including any namespace.
+    pub fn set_title(&mut self, name: &str, title: &str) {
+        self.titles.insert(name.to_owned(), title.to_owned());
+    }
+
+    /// Treat `input` from the source named `source` as corresponding to
+    /// `steam`, for [`default_configuration`](Self::default_configuration)
+    ///
+    /// `input` is written as in a [`Config`], e.g. `suggest("hid", "button
+    /// 1", SteamInput::A)`.
+    pub fn suggest(&mut self, source: &str, input: &str, steam: SteamInput) {
+        self.inputs
+            .insert((source.to_owned(), input.trim().to_owned()), steam);
+    }
+
+    /// The In-Game Actions manifest, declaring every action set and action
+    pub fn manifest(&self) -> String {
+        let sets = self.sets();
+        let mut vdf = Vdf::default();
+        vdf.open("In Game Actions");
+        vdf.open("actions");
+        for (set, actions) in &sets {
+            vdf.open(set);
+            vdf.value("title", &format!("#{}", set_token(set)));
+            for (section, kind) in [
+                ("StickPadGyro", Kind::Joystick),
+                ("AnalogTrigger", Kind::Analog),
+                ("Button", Kind::Digital),
+            ] {
+                let mut actions = actions.iter().filter(|x| x.kind == kind).peekable();
+                if actions.peek().is_none() {
+                    continue;
+                }
+                vdf.open(section);
+                for action in actions {
+                    let title = format!("#{}", action_token(set, action.name));
+                    if kind == Kind::Joystick {
+                        vdf.open(action.name);
+                        vdf.value("title", &title);
+                        vdf.value("input_mode", "joystick_move");
+                        vdf.close();
+                    } else {
+                        vdf.value(action.name, &title);
+                    }
+                }
+                vdf.close();
+            }
+            vdf.close();
+        }
+        vdf.close();
+        self.localization(&mut vdf, &sets);
+        vdf.close();
+        vdf.out
+    }
+
+    /// A default configuration for `controller_type`, e.g.
+    /// `controller_xboxone`, binding inputs as in `config`
+    ///
+    /// Only inputs registered with [`suggest`](Self::suggest) are included.
+    /// Bindings which Steam can't express are skipped, including conditional
+    /// bindings, digital actions bound to sticks, and analog actions bound to
+    /// buttons. Each stick or trigger drives at most one analog action per
+    /// set.
+    pub fn default_configuration(&self, config: &Config, controller_type: &str) -> String {
+        let sets = self.sets();
+        let mut groups = BTreeMap::<&str, BTreeMap<&'static str, Group<'_>>>::new();
+        for source in &config.sources {
+            for (name, inputs) in &source.bindings {
+                let Some(id) = self
+                    .session
+                    .action_id(name)
+                    .or_else(|| self.session.alias_target(name))
+                else {
+                    continue;
+                };
+                let Some(kind) = action_kind(self.session, id) else {
+                    continue;
+                };
+                let (set, action) = self.split(self.session.action_name(id));
+                for input in inputs {
+                    if input.contains(" if ") {
+                        continue;
+                    }
+                    let Some(&steam) = self
+                        .inputs
+                        .get(&(source.ty.clone(), input.trim().to_owned()))
+                    else {
+                        continue;
+                    };
+                    let button = match kind {
+                        Kind::Digital => match steam.button() {
+                            Some(button) => Some(button),
+                            None => continue,
+                        },
+                        Kind::Analog if steam.is_trigger() => None,
+                        Kind::Joystick if steam.is_stick() => None,
+                        _ => continue,
+                    };
+                    let (group_source, mode) = steam.group();
+                    let group = groups
+                        .entry(set)
+                        .or_default()
+                        .entry(group_source)
+                        .or_insert_with(|| Group {
+                            mode,
+                            buttons: BTreeMap::new(),
+                            analog: None,
+                        });
+                    match button {
+                        Some(button) => group.buttons.entry(button).or_default().push(action),
+                        None => group.analog = Some(action),
+                    }
+                }
+            }
+        }
+
+        let mut vdf = Vdf::default();
+        vdf.open("controller_mappings");
+        vdf.value("version", "3");
+        vdf.value("revision", "1");
+        vdf.value("title", "Default");
+        vdf.value("controller_type", controller_type);
+        vdf.open("actions");
+        for set in sets.keys() {
+            vdf.open(set);
+            vdf.value("title", &format!("#{}", set_token(set)));
+            vdf.value("legacy_set", "0");
+            vdf.close();
+        }
+        vdf.close();
+        self.localization(&mut vdf, &sets);
+        let mut id = 0;
+        let mut presets = Vec::new();
+        // Every set needs a preset, even if nothing is bound in it
+        for &set in sets.keys() {
+            let mut sources = Vec::new();
+            for (&source, group) in groups.get(set).into_iter().flatten() {
+                vdf.open("group");
+                vdf.value("id", &id.to_string());
+                vdf.value("mode", group.mode);
+                vdf.open("inputs");
+                for (&button, actions) in &group.buttons {
+                    vdf.open(button);
+                    vdf.open("activators");
+                    vdf.open("Full_Press");
+                    vdf.open("bindings");
+                    for action in actions {
+                        vdf.value("binding", &format!("game_action {set} {action}, , "));
+                    }
+                    vdf.close();
+                    vdf.close();
+                    vdf.close();
+                    vdf.close();
+                }
+                vdf.close();
+                if let Some(action) = group.analog {
+                    vdf.open("gameactions");
+                    vdf.value(set, action);
+                    vdf.close();
+                }
+                vdf.close();
+                sources.push((id, source));
+                id += 1;
+            }
+            presets.push((set, sources));
+        }
+        for (preset, (set, sources)) in presets.iter().enumerate() {
+            vdf.open("preset");
+            vdf.value("id", &preset.to_string());
+            vdf.value("name", set);
+            vdf.open("group_source_bindings");
+            for &(id, source) in sources {
+                vdf.value(&id.to_string(), &format!("{source} active"));
+            }
+            vdf.close();
+            vdf.close();
+        }
+        vdf.close();
+        vdf.out
+    }
+
+    /// Supported actions, by set, in order of name
+    fn sets(&self) -> BTreeMap<&str, Vec<SetAction<'_>>> {
+        let mut sets = BTreeMap::<&str, Vec<SetAction<'_>>>::new();
+        for id in self.session.actions() {
+            let Some(kind) = action_kind(self.session, id) else {
+                continue;
+            };
+            let full_name = self.session.action_name(id);
+            let (set, name) = self.split(full_name);
+            sets.entry(set).or_default().push(SetAction {
+                full_name,
+                name,
+                kind,
+            });
+        }
+        for actions in sets.values_mut() {
+            actions.sort_unstable_by_key(|x| x.name);
+        }
+        sets
+    }
+
+    /// The set containing the action named `name`, and its name within the
+    /// set
+    fn split<'b>(&'b self, name: &'b str) -> (&'b str, &'b str) {
+        name.split_once(NAMESPACE_SEPARATOR)
+            .unwrap_or((&self.default_set, name))
+    }
+
+    fn localization(&self, vdf: &mut Vdf, sets: &BTreeMap<&str, Vec<SetAction<'_>>>) {
+        vdf.open("localization");
+        vdf.open("english");
+        for (set, actions) in sets {
+            vdf.value(&set_token(set), self.title(set, set));
+            for action in actions {
+                vdf.value(
+                    &action_token(set, action.name),
+                    self.title(action.full_name, action.name),
+                );
+            }
+        }
+        vdf.close();
+        vdf.close();
+    }
+
+    /// Title set for `name`, or `default`
+    fn title<'b>(&'b self, name: &str, default: &'b str) -> &'b str {
+        self.titles.get(name).map_or(default, |x| x)
+    }
+}
+
+/// A physical input on a Steam Input controller
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SteamInput {
+    A,
+    B,
+    X,
+    Y,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+    Start,
+    Select,
+    LeftBumper,
+    RightBumper,
+    LeftStickClick,
+    RightStickClick,
+    /// Pulling a trigger, as an analog or digital input
+    LeftTrigger,
+    RightTrigger,
+    LeftStick,
+    RightStick,
+}
+
+impl SteamInput {
+    /// Steam's names for the group source containing the input and its mode
+    fn group(self) -> (&'static str, &'static str) {
+        use SteamInput::*;
+        match self {
+            A | B | X | Y => ("button_diamond", "four_buttons"),
+            DpadUp | DpadDown | DpadLeft | DpadRight => ("dpad", "dpad"),
+            Start | Select | LeftBumper | RightBumper => ("switch", "switches"),
+            LeftStick | LeftStickClick => ("joystick", "joystick_move"),
+            RightStick | RightStickClick => ("right_joystick", "joystick_move"),
+            LeftTrigger => ("left_trigger", "trigger"),
+            RightTrigger => ("right_trigger", "trigger"),
+        }
+    }
+
+    /// Steam's name for the input within its group, if it can drive digital
+    /// actions
+    fn button(self) -> Option<&'static str> {
+        use SteamInput::*;
+        Some(match self {
+            A => "button_a",
+            B => "button_b",
+            X => "button_x",
+            Y => "button_y",
+            DpadUp => "dpad_north",
+            DpadDown => "dpad_south",
+            DpadLeft => "dpad_west",
+            DpadRight => "dpad_east",
+            Start => "button_escape",
+            Select => "button_menu",
+            LeftBumper => "left_bumper",
+            RightBumper => "right_bumper",
+            LeftStickClick | RightStickClick | LeftTrigger | RightTrigger => "click",
+            LeftStick | RightStick => return None,
+        })
+    }
+
+    fn is_trigger(self) -> bool {
+        matches!(self, SteamInput::LeftTrigger | SteamInput::RightTrigger)
+    }
+
+    fn is_stick(self) -> bool {
+        matches!(self, SteamInput::LeftStick | SteamInput::RightStick)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Kind {
+    Digital,
+    Analog,
+    Joystick,
+}
+
+fn action_kind(session: &Session, id: ActionId) -> Option<Kind> {
+    let ty = session.definition(id).ty;
+    if ty == TypeId::of::<()>() || ty == TypeId::of::<bool>() {
+        Some(Kind::Digital)
+    } else if ty == TypeId::of::<f64>() {
+        Some(Kind::Analog)
+    } else if VectorType::find(ty).is_some() {
+        Some(Kind::Joystick)
+    } else {
+        None
+    }
+}
+
+struct SetAction<'a> {
+    full_name: &'a str,
+    /// Name within the set
+    name: &'a str,
+    kind: Kind,
+}
+
+/// Bindings of a group source within a set
+struct Group<'a> {
+    mode: &'static str,
+    buttons: BTreeMap<&'static str, Vec<&'a str>>,
+    analog: Option<&'a str>,
+}
+
+fn set_token(set: &str) -> String {
+    format!("Set_{set}")
+}
+
+fn action_token(set: &str, action: &str) -> String {
+    format!("Action_{set}_{action}")
+}
+
+/// Writes Valve's KeyValues text format
+#[derive(Default)]
+struct Vdf {
+    out: String,
+    depth: usize,
+}
+
+impl Vdf {
+    fn open(&mut self, key: &str) {
+        self.indent();
+        writeln!(self.out, "{}", Quoted(key)).unwrap();
+        self.indent();
+        self.out.push_str("{\n");
+        self.depth += 1;
+    }
+
+    fn close(&mut self) {
+        self.depth -= 1;
+        self.indent();
+        self.out.push_str("}\n");
+    }
+
+    fn value(&mut self, key: &str, value: &str) {
+        self.indent();
+        writeln!(self.out, "{}\t\t{}", Quoted(key), Quoted(value)).unwrap();
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.out.push('\t');
+        }
+    }
+}
+
+struct Quoted<'a>(&'a str);
+
+impl core::fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' | '\\' => write!(f, "\\{c}")?,
+                '\n' => f.write_str("\\n")?,
+                _ => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
@@ -0,0 +1,75 @@
+//! Locks guarding state behind shared references
+//!
+//! Backed by `std::sync` when available. Without std, a [`RefCell`] stands in,
+//! so that state can't be shared between threads.
+
+#[cfg(not(feature = "std"))]
+use core::cell::{Ref, RefCell, RefMut};
+#[cfg(feature = "std")]
+use std::sync::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+
+#[derive(Debug, Default)]
+pub(crate) struct Mutex<T: ?Sized>(
+    #[cfg(feature = "std")] std::sync::Mutex<T>,
+    #[cfg(not(feature = "std"))] RefCell<T>,
+);
+
+impl<T> Mutex<T> {
+    #[cfg(feature = "std")]
+    pub(crate) fn new(value: T) -> Self {
+        Self(value.into())
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    #[cfg(feature = "std")]
+    pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+        self.0.lock().unwrap()
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn lock(&self) -> RefMut<'_, T> {
+        self.0.borrow_mut()
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut T {
+        #[cfg(feature = "std")]
+        return self.0.get_mut().unwrap();
+        #[cfg(not(feature = "std"))]
+        return self.0.get_mut();
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct RwLock<T: ?Sized>(
+    #[cfg(feature = "std")] std::sync::RwLock<T>,
+    #[cfg(not(feature = "std"))] RefCell<T>,
+);
+
+impl<T> RwLock<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(value.into())
+    }
+}
+
+impl<T: ?Sized> RwLock<T> {
+    #[cfg(feature = "std")]
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.0.read().unwrap()
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn read(&self) -> Ref<'_, T> {
+        self.0.borrow()
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, T> {
+        self.0.write().unwrap()
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn write(&self) -> RefMut<'_, T> {
+        self.0.borrow_mut()
+    }
+}
@@ -0,0 +1,117 @@
+use core::time::Duration;
+
+use crate::{Action, Seat};
+
+/// Divides input between fixed simulation steps
+///
+/// Games which simulate at a fixed rate may run several steps per rendered
+/// frame, or none. Each call to [`advance`](Self::advance) yields the steps
+/// due since the last, and [`Step::poll`] consumes only the changes made
+/// before the end of its step, so that every change is applied in exactly one
+/// step, as close as possible to when it happened. The last step of each call
+/// consumes all remaining changes, so that none are left to be discarded.
+///
+/// Since changes must survive frames in which no steps are due, call
+/// [`Seat::flush`] only after frames in which at least one step ran.
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    step: Duration,
+    /// End of the most recent step, once started
+    time: Option<Duration>,
+}
+
+impl FixedTimestep {
+    /// Panics if `step` is zero
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "zero timestep");
+        Self { step, time: None }
+    }
+
+    /// Length of each step
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// The steps due as of [`seat.now()`](Seat::now)
+    ///
+    /// The first call starts the clock, and yields no steps.
+    pub fn advance(&mut self, seat: &Seat) -> Steps {
+        let now = seat.now();
+        let start = *self.time.get_or_insert(now);
+        let count = now.saturating_sub(start).as_nanos() / self.step.as_nanos();
+        let count = u32::try_from(count).unwrap_or(u32::MAX);
+        self.time = Some(start + self.step * count);
+        Steps {
+            start,
+            step: self.step,
+            index: 0,
+            count,
+        }
+    }
+
+    /// Time elapsed since the end of the most recent step, as a fraction of a
+    /// step
+    ///
+    /// Useful for interpolating rendered state between steps.
+    pub fn alpha(&self, seat: &Seat) -> f64 {
+        let Some(time) = self.time else {
+            return 0.0;
+        };
+        seat.now().saturating_sub(time).as_secs_f64() / self.step.as_secs_f64()
+    }
+}
+
+/// Steps due in a single [`FixedTimestep::advance`], in order
+#[derive(Debug, Clone)]
+pub struct Steps {
+    start: Duration,
+    step: Duration,
+    index: u32,
+    count: u32,
+}
+
+impl Iterator for Steps {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        if self.index == self.count {
+            return None;
+        }
+        self.index += 1;
+        Some(Step {
+            end: self.start + self.step * self.index,
+            last: self.index == self.count,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.count - self.index) as usize;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for Steps {}
+
+/// A single simulation step, from [`Steps`]
+#[derive(Debug, Copy, Clone)]
+pub struct Step {
+    end: Duration,
+    last: bool,
+}
+
+impl Step {
+    /// Time at which this step ends, according to [`Seat::now`]
+    pub fn end(&self) -> Duration {
+        self.end
+    }
+
+    /// Consume the next state change affecting `action` which belongs to this
+    /// step, if any
+    pub fn poll<T: 'static>(&self, seat: &Seat, action: Action<T>) -> Option<T> {
+        if self.last {
+            seat.poll(action)
+        } else {
+            seat.poll_before(action, self.end)
+        }
+    }
+}
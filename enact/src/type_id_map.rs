@@ -1,7 +1,6 @@
-use std::any::TypeId;
+use core::any::TypeId;
 
-pub type TypeIdMap<V> =
-    std::collections::HashMap<TypeId, V, std::hash::BuildHasherDefault<TypeIdHasher>>;
+pub type TypeIdMap<V> = hashbrown::HashMap<TypeId, V, core::hash::BuildHasherDefault<TypeIdHasher>>;
 
 /// A hasher optimized for hashing a single TypeId.
 ///
@@ -12,7 +11,7 @@ pub struct TypeIdHasher {
     hash: u64,
 }
 
-impl std::hash::Hasher for TypeIdHasher {
+impl core::hash::Hasher for TypeIdHasher {
     fn write_u64(&mut self, n: u64) {
         self.hash = n;
     }
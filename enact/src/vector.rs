@@ -0,0 +1,141 @@
+//! Interoperability between 2D vector types
+
+use core::{any::TypeId, marker::PhantomData};
+
+use crate::{Action, ActionId, PushError, Seat};
+
+/// A 2D vector type usable as the state of vector-valued actions
+///
+/// Inputs produce [`mint::Vector2<f64>`], which is converted automatically
+/// when bound to an action of another of these types, and built-in filters
+/// like [`DPad`](crate::filter::DPad) accept targets of any of them.
+/// Implemented for `mint::Vector2<f64>`, for `glam::Vec2` and `glam::DVec2`
+/// with the `glam` feature, and for `nalgebra::Vector2<f32>` and
+/// `nalgebra::Vector2<f64>` with the `nalgebra` feature.
+pub trait Vector2: sealed::Sealed + Clone + 'static {
+    fn from_array(xy: [f64; 2]) -> Self;
+    fn to_array(&self) -> [f64; 2];
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl sealed::Sealed for mint::Vector2<f64> {}
+impl Vector2 for mint::Vector2<f64> {
+    fn from_array(xy: [f64; 2]) -> Self {
+        xy.into()
+    }
+    fn to_array(&self) -> [f64; 2] {
+        (*self).into()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl sealed::Sealed for glam::Vec2 {}
+#[cfg(feature = "glam")]
+impl Vector2 for glam::Vec2 {
+    fn from_array([x, y]: [f64; 2]) -> Self {
+        glam::Vec2::new(x as f32, y as f32)
+    }
+    fn to_array(&self) -> [f64; 2] {
+        [f64::from(self.x), f64::from(self.y)]
+    }
+}
+
+#[cfg(feature = "glam")]
+impl sealed::Sealed for glam::DVec2 {}
+#[cfg(feature = "glam")]
+impl Vector2 for glam::DVec2 {
+    fn from_array(xy: [f64; 2]) -> Self {
+        glam::DVec2::from_array(xy)
+    }
+    fn to_array(&self) -> [f64; 2] {
+        glam::DVec2::to_array(self)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl sealed::Sealed for nalgebra::Vector2<f32> {}
+#[cfg(feature = "nalgebra")]
+impl Vector2 for nalgebra::Vector2<f32> {
+    fn from_array([x, y]: [f64; 2]) -> Self {
+        nalgebra::Vector2::new(x as f32, y as f32)
+    }
+    fn to_array(&self) -> [f64; 2] {
+        [f64::from(self.x), f64::from(self.y)]
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl sealed::Sealed for nalgebra::Vector2<f64> {}
+#[cfg(feature = "nalgebra")]
+impl Vector2 for nalgebra::Vector2<f64> {
+    fn from_array([x, y]: [f64; 2]) -> Self {
+        nalgebra::Vector2::new(x, y)
+    }
+    fn to_array(&self) -> [f64; 2] {
+        [self.x, self.y]
+    }
+}
+
+/// Type-erased access to actions of a [`Vector2`] type
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct VectorType {
+    ty: TypeId,
+    get: fn(&Seat, ActionId) -> Option<[f64; 2]>,
+    push: fn(&mut Seat, ActionId, [f64; 2]) -> Result<bool, PushError>,
+}
+
+/// Compares only the vector type, which determines the accessors
+impl PartialEq for VectorType {
+    fn eq(&self, other: &Self) -> bool {
+        self.ty == other.ty
+    }
+}
+
+impl VectorType {
+    pub(crate) fn of<V: Vector2>() -> Self {
+        Self {
+            ty: TypeId::of::<V>(),
+            get: |seat, id| {
+                seat.get(Action::<V> {
+                    id,
+                    _marker: PhantomData,
+                })
+                .map(|v| v.to_array())
+            },
+            push: |seat, id, xy| seat.push_changed(id, V::from_array(xy)),
+        }
+    }
+
+    /// Look up the supported vector type with ID `ty`
+    pub(crate) fn find(ty: TypeId) -> Option<Self> {
+        [
+            Self::of::<mint::Vector2<f64>>(),
+            #[cfg(feature = "glam")]
+            Self::of::<glam::Vec2>(),
+            #[cfg(feature = "glam")]
+            Self::of::<glam::DVec2>(),
+            #[cfg(feature = "nalgebra")]
+            Self::of::<nalgebra::Vector2<f32>>(),
+            #[cfg(feature = "nalgebra")]
+            Self::of::<nalgebra::Vector2<f64>>(),
+        ]
+        .into_iter()
+        .find(|v| v.ty == ty)
+    }
+
+    pub(crate) fn get(self, seat: &Seat, action: ActionId) -> Option<[f64; 2]> {
+        (self.get)(seat, action)
+    }
+
+    pub(crate) fn push(
+        self,
+        seat: &mut Seat,
+        action: ActionId,
+        xy: [f64; 2],
+    ) -> Result<bool, PushError> {
+        (self.push)(seat, action, xy)
+    }
+}
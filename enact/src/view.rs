@@ -0,0 +1,134 @@
+//! Toolkit-independent model of bindings, for settings screens
+
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+use crate::{ActionId, Bindings, DisplayNames, FxHashMap, Session, namespace_of};
+
+/// An ordered, read-only description of [`Bindings`], from
+/// [`Bindings::view`]
+///
+/// Contains everything a controls settings screen needs to render, so that
+/// any UI toolkit can present one without inspecting [`Bindings`] directly.
+#[derive(Debug, Clone, Default)]
+pub struct BindingView {
+    /// Actions grouped by namespace, with the unnamespaced group first and the
+    /// rest in order of name
+    pub groups: Vec<GroupView>,
+}
+
+/// Actions sharing a namespace, e.g. from a [`Bundle`](crate::Bundle)
+#[derive(Debug, Clone)]
+pub struct GroupView {
+    /// The namespace, or `""` for actions without one
+    pub namespace: String,
+    /// Actions in order of name
+    pub actions: Vec<ActionView>,
+}
+
+/// An action and the inputs bound to it
+#[derive(Debug, Clone)]
+pub struct ActionView {
+    pub id: ActionId,
+    /// Full name, including any namespace
+    pub name: String,
+    /// Name of the action's Rust type
+    pub type_name: &'static str,
+    /// Whether the action was marked required with [`Session::set_required`]
+    pub required: bool,
+    /// [`Filter::NAME`](crate::Filter::NAME) of the filter that writes this
+    /// action, if any
+    pub filter: Option<String>,
+    /// Bindings in order of source, then input
+    pub bindings: Vec<BindingEntry>,
+}
+
+/// A single input bound to an action
+#[derive(Debug, Clone)]
+pub struct BindingEntry {
+    /// [`Input::NAME`](crate::Input::NAME) of the input's source
+    pub source: String,
+    /// Canonical form of the input, as in [`Config`](crate::Config)
+    pub input: String,
+    /// Human-readable description of the input, from [`DisplayNames`]
+    pub display: String,
+    /// From [`Input::glyph`](crate::Input::glyph)
+    pub glyph: Option<String>,
+    /// Name of the action which must be `true` for the binding to apply, if
+    /// any
+    pub condition: Option<String>,
+    /// Names of other actions bound to the same input, in order
+    pub conflicts: Vec<String>,
+}
+
+impl Bindings {
+    /// Describe these bindings, and every action in `session`, for display
+    ///
+    /// Inputs are described according to `names`, which may be empty.
+    pub fn view(&self, session: &Session, names: &DisplayNames) -> BindingView {
+        self.check_session(session);
+        let mut entries = FxHashMap::<ActionId, Vec<BindingEntry>>::default();
+        for source in self.actions.values() {
+            for bound in source.describe() {
+                let bindings = bound
+                    .bindings
+                    .iter()
+                    .filter(|b| session.contains(b.action))
+                    .collect::<Vec<_>>();
+                for binding in &bindings {
+                    let mut conflicts = bindings
+                        .iter()
+                        .filter(|b| b.action != binding.action)
+                        .map(|b| session.action_name(b.action).to_owned())
+                        .collect::<Vec<_>>();
+                    conflicts.sort_unstable();
+                    conflicts.dedup();
+                    entries
+                        .entry(binding.action)
+                        .or_default()
+                        .push(BindingEntry {
+                            source: source.name().to_owned(),
+                            display: names
+                                .get(source.name(), &bound.input)
+                                .unwrap_or(&bound.input)
+                                .to_owned(),
+                            input: bound.input.clone(),
+                            glyph: bound.glyph.clone(),
+                            condition: binding
+                                .condition
+                                .filter(|&c| session.contains(c))
+                                .map(|c| session.action_name(c).to_owned()),
+                            conflicts,
+                        });
+                }
+            }
+        }
+
+        let mut groups = Vec::<GroupView>::new();
+        for id in session.actions() {
+            let name = session.action_name(id);
+            let namespace = namespace_of(name).unwrap_or("");
+            let mut bindings = entries.remove(&id).unwrap_or_default();
+            bindings.sort_unstable_by(|x, y| (&x.source, &x.input).cmp(&(&y.source, &y.input)));
+            let action = ActionView {
+                id,
+                name: name.to_owned(),
+                type_name: session.definition(id).ty_name,
+                required: session.required.contains(&id),
+                filter: self.producer(id).map(|f| self.filter_ty(f).to_owned()),
+                bindings,
+            };
+            match groups.iter_mut().find(|g| g.namespace == namespace) {
+                Some(group) => group.actions.push(action),
+                None => groups.push(GroupView {
+                    namespace: namespace.to_owned(),
+                    actions: vec![action],
+                }),
+            }
+        }
+        groups.sort_unstable_by(|x, y| x.namespace.cmp(&y.namespace));
+        for group in &mut groups {
+            group.actions.sort_unstable_by(|x, y| x.name.cmp(&y.name));
+        }
+        BindingView { groups }
+    }
+}
@@ -5,7 +5,6 @@ use std::{
 };
 
 use anyhow::{Context as _, Result};
-use enact::Action;
 use winit::{
     application::ApplicationHandler,
     event::{StartCause, WindowEvent},
@@ -21,7 +20,7 @@ fn main() {
 
 fn run() -> Result<()> {
     let mut session = enact::Session::new();
-    let actions = Actions::new(&mut session);
+    let actions = Actions::new(&mut session)?;
 
     let config = fs::read_to_string("config/seat1.toml").context("reading seat1.toml")?;
     let config = toml::from_str::<enact::Config>(&config).context("parsing")?;
@@ -50,19 +49,14 @@ fn run() -> Result<()> {
     Ok(())
 }
 
-struct Actions {
-    direction: Action<mint::Vector2<f64>>,
-    jump: Action<()>,
+enact::actions! {
+    struct Actions {
+        direction: mint::Vector2<f64>,
+        jump: (),
+    }
 }
 
 impl Actions {
-    fn new(session: &mut enact::Session) -> Self {
-        Self {
-            direction: session.create_action("direction").unwrap(),
-            jump: session.create_action("jump").unwrap(),
-        }
-    }
-
     fn poll(&self, seat: &enact::Seat) {
         println!(
             "{:1.0?}",
@@ -103,17 +97,15 @@ impl ApplicationHandler for App {
         event_loop: &winit::event_loop::ActiveEventLoop,
         cause: winit::event::StartCause,
     ) {
-        match cause {
-            StartCause::ResumeTimeReached {
-                requested_resume, ..
-            } => {
-                self.actions.poll(&self.seat);
-                self.seat.flush();
-                event_loop.set_control_flow(winit::event_loop::ControlFlow::WaitUntil(
-                    requested_resume + Duration::from_millis(100),
-                ));
-            }
-            _ => {}
+        if let StartCause::ResumeTimeReached {
+            requested_resume, ..
+        } = cause
+        {
+            self.actions.poll(&self.seat);
+            self.seat.flush();
+            event_loop.set_control_flow(winit::event_loop::ControlFlow::WaitUntil(
+                requested_resume + Duration::from_millis(100),
+            ));
         }
     }
 